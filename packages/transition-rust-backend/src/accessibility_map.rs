@@ -0,0 +1,37 @@
+/// This module computes accessibility maps (isochrones) on the transit network of a scenario from a capnp cache
+#[napi]
+pub mod accessibility_map {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Compute the nodes reachable from one or many locations, with their
+  /// shortest travel time, and the isochrone polygons of each duration,
+  /// without calling trRouting. The network is loaded once per call.
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the capnp
+  /// collection files and the lines and nodes subdirectories
+  /// @param {string} queryJson: json { scenario_id, location or locations,
+  /// departure_time_seconds or departure_time_range_seconds: [start, end],
+  /// durations_seconds, and the routing parameters of the route function },
+  /// where locations are node uuids or [longitude, latitude]
+  ///
+  /// @returns {string}: json { location, nodes: [{ id, travel_time_seconds,
+  /// departure_time_seconds }], polygons: geojson FeatureCollection of
+  /// MultiPolygons with { duration_seconds, area_square_meters, nodes_count }
+  /// properties }, or { maps } for many locations
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn accessibility_map(
+    cache_directory_path: String,
+    query_json: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let query: serde_json::Value =
+        serde_json::from_str(&query_json).map_err(|e| capnp::Error::failed(e.to_string()))?;
+      transition_capnp_data::accessibility_map::accessibility_map_query(
+        &cache_directory_path,
+        &query,
+      )
+    })
+  }
+}
@@ -0,0 +1,144 @@
+/// This module manages whole caches in their cache store: a local directory,
+/// a SQLite file (sqlite://<file>.sqlite) or an object storage (s3://...),
+/// the transactions staging many cache files before writing them, the garbage
+/// collection of the cache and its clipping to a study area
+#[napi]
+pub mod cache_store {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use serde_json::json;
+  use transition_capnp_data::cache_clipping;
+  use transition_capnp_data::cache_gc::{self, CacheGcOptions, DEFAULT_MAX_AGE_DAYS};
+  use transition_capnp_data::cache_store::transaction;
+
+  /// Copy all the files of a cache to another location, like a cache
+  /// directory to a single SQLite file or back
+  ///
+  /// @param {string} sourceLocation: cache directory path or cache store location
+  /// @param {string} destinationLocation: cache directory path or cache store location
+  ///
+  /// @returns {string}: json { files_count }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn convert_cache(
+    source_location: String,
+    destination_location: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let files_count =
+        transition_capnp_data::cache_store::convert(&source_location, &destination_location)?;
+      Ok(json!({ "files_count": files_count }))
+    })
+  }
+
+  /// Begin a transaction on a cache: the files written to the transaction
+  /// location are only in the cache once the transaction is committed. An
+  /// S3 cache cannot have transactions, its objects are replaced one at a
+  /// time
+  ///
+  /// @param {string} location: cache directory path or cache store location
+  ///
+  /// @returns {string}: json { transaction_id, location }, location being
+  /// the cache location to use for the writes in the transaction
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn begin_cache_transaction(location: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let transaction_id = transaction::begin(&location)?;
+      let transaction_location = transaction::transaction_location(&location, &transaction_id)?;
+      Ok(json!({ "transaction_id": transaction_id, "location": transaction_location }))
+    })
+  }
+
+  /// Replace the cache files with the files written in the transaction, all
+  /// at once: a local directory is replaced as a whole, so trRouting loading
+  /// the cache meanwhile sees all the old files or all the new ones. The
+  /// files written directly to the cache during the commit are lost. The
+  /// transaction stays open if the commit fails, to commit it again
+  ///
+  /// @param {string} location: cache directory path or cache store location
+  /// @param {string} transactionId: id returned by beginCacheTransaction
+  ///
+  /// @returns {string}: json { files_count }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn commit_cache_transaction(location: String, transaction_id: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let files_count = transaction::commit(&location, &transaction_id)?;
+      Ok(json!({ "files_count": files_count }))
+    })
+  }
+
+  /// Discard the files written in the transaction
+  ///
+  /// @param {string} location: cache directory path or cache store location
+  /// @param {string} transactionId: id returned by beginCacheTransaction
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn rollback_cache_transaction(
+    location: String,
+    transaction_id: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transaction::rollback(&location, &transaction_id)?;
+      Ok(json!({ "transaction_id": transaction_id }))
+    })
+  }
+
+  /// Find the line and node files of the objects no longer in the lines and
+  /// nodes collections, the stale custom subdirectories and the transactions
+  /// never committed, and remove them unless it is a dry run
+  ///
+  /// @param {string} location: cache directory path or cache store location
+  /// @param {boolean} dryRun: only report the files to remove
+  /// @param {number} [maxAgeDays=30]: subdirectories and transactions
+  /// unchanged for as long are stale
+  /// @param {string[]} [keepSubdirectories]: custom subdirectories still used
+  /// @param {boolean} [removeSubdirectoriesOfUnknownAge=false]: outside a
+  /// local directory, the age of the subdirectories is unknown, they are only
+  /// stale with this option
+  ///
+  /// @returns {string}: json { dry_run, orphan_files, stale_subdirectories,
+  /// stale_transactions, files_count }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn gc_cache(
+    location: String,
+    dry_run: bool,
+    max_age_days: Option<f64>,
+    keep_subdirectories: Option<Vec<String>>,
+    remove_subdirectories_of_unknown_age: Option<bool>,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let options = CacheGcOptions {
+        dry_run,
+        max_age_days: max_age_days.unwrap_or(DEFAULT_MAX_AGE_DAYS),
+        keep_subdirectories: keep_subdirectories.unwrap_or_default(),
+        remove_subdirectories_of_unknown_age: remove_subdirectories_of_unknown_age.unwrap_or(false),
+      };
+      Ok(cache_gc::gc_cache(&location, &options)?.to_json())
+    })
+  }
+
+  /// Write to the destination cache the part of the cache inside an area:
+  /// the nodes in the area, the paths cut to their nodes in the area and the
+  /// trips trimmed to them, the lines and agencies still used
+  ///
+  /// @param {string} sourceLocation: cache directory path or cache store location
+  /// @param {string} areaJson: json { bbox: [minLon, minLat, maxLon, maxLat] }
+  /// or a geojson Polygon or MultiPolygon, geometry or feature
+  /// @param {string} destinationLocation: cache directory path or cache store
+  /// location, should be empty
+  ///
+  /// @returns {string}: json { nodes, paths, lines, trips } with the kept,
+  /// clipped and removed objects
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn clip_cache(
+    source_location: String,
+    area_json: String,
+    destination_location: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let area_json: serde_json::Value = serde_json::from_str(&area_json)
+        .map_err(|e| capnp::Error::failed(format!("Invalid area json: {}", e)))?;
+      let area = cache_clipping::clip_area_from_json(&area_json)?;
+      cache_clipping::clip_cache(&source_location, &destination_location, &area)
+    })
+  }
+}
@@ -4,20 +4,20 @@ pub mod capnp_serialization {
 
   use napi::bindgen_prelude::AsyncTask;
   use napi::{Env, Task};
-  use std::fs::File;
   use transition_capnp_data::serialization::*;
 
   // ===========================================================================
   // COLLECTION GENERICS
   //
-  // Collection functions operate on a single file. The type-specific work is
+  // Collection functions operate on a single file, in any cache store location
+  // (a local path, memory://... or s3://...). The type-specific work is
   // erased behind a boxed closure that does the I/O and maps every error to
   // `napi::Error` up front, so each Task struct is plain and non-generic (which
   // keeps `AsyncTask<ConcreteTask>` in the return position, where a generic
   // param can't appear).
   // ===========================================================================
 
-  // Generic collection write task: the boxed closure creates the file and writes to it.
+  // Generic collection write task: the boxed closure writes the file to its store.
   pub struct WriteCollectionTask {
     op: Box<dyn FnOnce() -> napi::Result<()> + Send>,
   }
@@ -40,27 +40,21 @@ pub mod capnp_serialization {
   fn write_collection_generic(
     file_path: String,
     json_str: String,
-    writer: fn(&serde_json::Value, &mut File) -> Result<(), capnp::Error>,
+    writer: fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>,
   ) -> AsyncTask<WriteCollectionTask> {
     AsyncTask::new(WriteCollectionTask {
       op: Box::new(move || {
         // Parse the JSON payload; a malformed payload is a caller error (InvalidArg).
         let json: serde_json::Value = serde_json::from_str(&json_str)
           .map_err(|e| napi::Error::new(napi::Status::InvalidArg, e.to_string()))?;
-        // Create (or truncate) the destination file, then delegate to the type-specific writer.
-        let mut file = File::create(&file_path).map_err(|e| {
-          napi::Error::new(
-            napi::Status::GenericFailure,
-            format!("Cannot create {}: {}", file_path, e),
-          )
-        })?;
-        writer(&json, &mut file)
+        // The type-specific writer serializes the message, the store replaces the file with it.
+        transition_capnp_data::cache::write_file(&file_path, &json, &writer)
           .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))
       }),
     })
   }
 
-  // Generic collection read task: the boxed closure opens the file, reads and re-serializes.
+  // Generic collection read task: the boxed closure reads the file from its store and re-serializes.
   pub struct ReadCollectionTask {
     op: Box<dyn FnOnce() -> napi::Result<String> + Send>,
   }
@@ -81,19 +75,13 @@ pub mod capnp_serialization {
 
   fn read_collection_generic(
     file_path: String,
-    reader: fn(&mut File) -> Result<serde_json::Value, capnp::Error>,
+    reader: impl Fn(&mut dyn std::io::Read) -> Result<serde_json::Value, capnp::Error> + Send + 'static,
   ) -> AsyncTask<ReadCollectionTask> {
     AsyncTask::new(ReadCollectionTask {
       op: Box::new(move || {
-        // Open the source file, surfacing the path in the error message for easier debugging.
-        let mut file = File::open(&file_path).map_err(|e| {
-          napi::Error::new(
-            napi::Status::GenericFailure,
-            format!("Cannot open {}: {}", file_path, e),
-          )
-        })?;
         // Delegate to the type-specific reader, then serialize the result back to a JSON string.
-        let collection_json = reader(&mut file)
+        // A missing file surfaces its path in the error message for easier debugging.
+        let collection_json = transition_capnp_data::cache::read_file(&file_path, &reader)
           .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))?;
         serde_json::to_string(&collection_json)
           .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))
@@ -135,7 +123,7 @@ pub mod capnp_serialization {
   fn write_object_generic(
     cache_directory_path: String,
     json_str: String,
-    writer: fn(&str, &serde_json::Value) -> Result<(), capnp::Error>,
+    writer: impl FnOnce(&str, &serde_json::Value) -> Result<(), capnp::Error> + Send + 'static,
   ) -> AsyncTask<WriteObjectTask> {
     AsyncTask::new(WriteObjectTask {
       op: Box::new(move || {
@@ -228,7 +216,9 @@ pub mod capnp_serialization {
   /// Write a path collection to a capnp file
   ///
   /// @param {string} filePath: path to the capnp file to write
-  /// @param {string} jsonStr: json representation of the path collection as a string
+  /// @param {string} jsonStr: json representation of the path collection as a string.
+  /// Features can have an encoded "polyline" instead of a geometry, at the
+  /// "polyline_precision" of the json (5 by default)
   #[napi(ts_return_type = "Promise<void>")]
   pub fn write_path_collection(
     file_path: String,
@@ -261,6 +251,94 @@ pub mod capnp_serialization {
     write_collection_generic(file_path, json_str, service_collection::write_collection)
   }
 
+  /// Write a station collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the station collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_station_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, station_collection::write_collection)
+  }
+
+  /// Write a zone collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the zone collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_zone_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, zone_collection::write_collection)
+  }
+
+  /// Write a fare collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the fare collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_fare_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, fare_collection::write_collection)
+  }
+
+  /// Write a vehicle type collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the vehicle type collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_vehicle_type_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(
+      file_path,
+      json_str,
+      vehicle_type_collection::write_collection,
+    )
+  }
+
+  /// Write a household collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the household collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_household_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, household_collection::write_collection)
+  }
+
+  /// Write a person collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the person collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_person_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, person_collection::write_collection)
+  }
+
+  /// Write a OD trip collection to a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to write
+  /// @param {string} jsonStr: json representation of the OD trip collection as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_od_trip_collection(
+    file_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteCollectionTask> {
+    write_collection_generic(file_path, json_str, od_trip_collection::write_collection)
+  }
+
   // ===========================================================================
   // WRITE OBJECTS
   // ===========================================================================
@@ -277,6 +355,71 @@ pub mod capnp_serialization {
     write_object_generic(cache_directory_path, json_str, line::write_object)
   }
 
+  /// Write a line object to a capnp file, with the trips of each service in
+  /// a separate file so they can be read without the rest of the line.
+  /// trRouting does not read these files: only for caches not used by trRouting.
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory where to create the files, will be named based on the uuids
+  /// @param {string} jsonStr: json representation of the line object as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_line_object_by_service(
+    cache_directory_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteObjectTask> {
+    write_object_generic(
+      cache_directory_path,
+      json_str,
+      line::write_object_by_service,
+    )
+  }
+
+  /// Write a line object to capnp files with the given layout. The line read
+  /// back is the same whatever the layout, but trRouting does not read the
+  /// trips written by service nor the node times of the trips written with
+  /// trip patterns: only for caches not used by trRouting.
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory where to create the files, will be named based on the uuids
+  /// @param {string} jsonStr: json representation of the line object as a string
+  /// @param {boolean} tripsByService: write the trips of each service in a separate file
+  /// @param {boolean} tripPatterns: write the node times and flags shared by trips only once
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_line_object_with_options(
+    cache_directory_path: String,
+    json_str: String,
+    trips_by_service: bool,
+    trip_patterns: bool,
+  ) -> AsyncTask<WriteObjectTask> {
+    let options = line::LineWriteOptions {
+      trips_by_service,
+      trip_patterns,
+    };
+    write_object_generic(
+      cache_directory_path,
+      json_str,
+      move |cache_directory_path, json| {
+        line::write_object_with_options(cache_directory_path, json, &options)
+      },
+    )
+  }
+
+  /// Write a line object to a capnp file, only if its trip timetables match
+  /// the nodes of their path. The paths are read from the paths.capnpbin
+  /// file in the parent directory of the lines directory.
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory where to create the file, will be named based on the uuid
+  /// @param {string} jsonStr: json representation of the line object as a string
+  #[napi(ts_return_type = "Promise<void>")]
+  pub fn write_line_object_strict(
+    cache_directory_path: String,
+    json_str: String,
+  ) -> AsyncTask<WriteObjectTask> {
+    write_object_generic(
+      cache_directory_path,
+      json_str,
+      transition_capnp_data::timetable_validation::write_line_object_strict,
+    )
+  }
+
   /// Write a node object to a capnp file
   ///
   /// @param {string} cacheDirectoryPath: path to the directory where to create the file, will be named based on the uuid
@@ -333,6 +476,28 @@ pub mod capnp_serialization {
     read_collection_generic(file_path, path_collection::read_collection)
   }
 
+  /// Read a path collection from a capnp file, with the geographies encoded as polylines
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  /// @param {number} polylinePrecision: number of decimals of the encoded coordinates, 5 for the Google polyline format
+  ///
+  /// @returns {string}: json representation of the path collection as a string,
+  /// the features have a null geometry and a "polyline" string instead
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_path_collection_with_polylines(
+    file_path: String,
+    polyline_precision: u32,
+  ) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, move |file| {
+      path_collection::read_collection_with_geometry_encoding(
+        file,
+        path_collection::GeometryEncoding::Polyline {
+          precision: polyline_precision,
+        },
+      )
+    })
+  }
+
   /// Read a scenario collection from a capnp file
   ///
   /// @param {string} filePath: path to the capnp file to read
@@ -353,6 +518,76 @@ pub mod capnp_serialization {
     read_collection_generic(file_path, service_collection::read_collection)
   }
 
+  /// Read a station collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the station collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_station_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, station_collection::read_collection)
+  }
+
+  /// Read a zone collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the zone collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_zone_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, zone_collection::read_collection)
+  }
+
+  /// Read a fare collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the fare collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_fare_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, fare_collection::read_collection)
+  }
+
+  /// Read a vehicle type collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the vehicle type collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_vehicle_type_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, vehicle_type_collection::read_collection)
+  }
+
+  /// Read a household collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the household collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_household_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, household_collection::read_collection)
+  }
+
+  /// Read a person collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the person collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_person_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, person_collection::read_collection)
+  }
+
+  /// Read a OD trip collection from a capnp file
+  ///
+  /// @param {string} filePath: path to the capnp file to read
+  ///
+  /// @returns {string}: json representation of the OD trip collection as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_od_trip_collection(file_path: String) -> AsyncTask<ReadCollectionTask> {
+    read_collection_generic(file_path, od_trip_collection::read_collection)
+  }
+
   // ===========================================================================
   // READ OBJECTS
   // ===========================================================================
@@ -371,6 +606,85 @@ pub mod capnp_serialization {
     read_object_generic(object_uuid, cache_directory_path, line::read_object)
   }
 
+  /// Read the schedules and periods of a line object, without their trips
+  ///
+  /// @param {string} objectUuid: uuid of the object to find the right file
+  /// @param {string} cacheDirectoryPath: path to the directory where to find the file
+  ///
+  /// @returns {string}: json representation of the line object as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_line_object_headers(
+    object_uuid: String,
+    cache_directory_path: String,
+  ) -> AsyncTask<ReadObjectTask> {
+    read_object_generic(
+      object_uuid,
+      cache_directory_path,
+      |object_uuid, cache_directory_path| {
+        line::read_object_headers(object_uuid, cache_directory_path)
+      },
+    )
+  }
+
+  // Read a part of a line object, the service or period being selected by uuid
+  fn read_line_object_slice_generic(
+    object_uuid: String,
+    slice_uuid: String,
+    cache_directory_path: String,
+    reader: fn(&str, &str, &str) -> Result<serde_json::Value, capnp::Error>,
+  ) -> AsyncTask<ReadObjectTask> {
+    AsyncTask::new(ReadObjectTask {
+      op: Box::new(move || {
+        let json = reader(&object_uuid, &slice_uuid, &cache_directory_path)
+          .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))?;
+        serde_json::to_string(&json)
+          .map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))
+      }),
+    })
+  }
+
+  /// Read a line object with only the schedule of a service and its trips
+  ///
+  /// @param {string} objectUuid: uuid of the object to find the right file
+  /// @param {string} serviceUuid: uuid of the service to read
+  /// @param {string} cacheDirectoryPath: path to the directory where to find the file
+  ///
+  /// @returns {string}: json representation of the line object as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_line_service_object(
+    object_uuid: String,
+    service_uuid: String,
+    cache_directory_path: String,
+  ) -> AsyncTask<ReadObjectTask> {
+    read_line_object_slice_generic(
+      object_uuid,
+      service_uuid,
+      cache_directory_path,
+      line::read_service_object,
+    )
+  }
+
+  /// Read a line object with only a period, its trips and its schedule
+  ///
+  /// @param {string} objectUuid: uuid of the object to find the right file
+  /// @param {string} periodUuid: uuid of the period to read
+  /// @param {string} cacheDirectoryPath: path to the directory where to find the file
+  ///
+  /// @returns {string}: json representation of the line object as a string
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn read_line_period_object(
+    object_uuid: String,
+    period_uuid: String,
+    cache_directory_path: String,
+  ) -> AsyncTask<ReadObjectTask> {
+    read_line_object_slice_generic(
+      object_uuid,
+      period_uuid,
+      cache_directory_path,
+      line::read_period_object,
+    )
+  }
+
   /// Read a node object from a capnp file
   ///
   /// @param {string} objectUuid: uuid of the object to find the right file
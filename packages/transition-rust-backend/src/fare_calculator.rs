@@ -0,0 +1,27 @@
+/// This module prices journeys with the fare collection of a capnp cache
+#[napi]
+pub mod fare_calculator {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Compute the fare of journeys from their transit legs
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing fares.capnpbin,
+  /// and lines.capnpbin to find the agency of the legs
+  /// @param {string} queryJson: json { legs: [{ line_id, agency_id,
+  /// boarding_node_id, alighting_node_id, boarding_time_seconds,
+  /// alighting_time_seconds }] } or { journeys: [{ legs }] }
+  ///
+  /// @returns {string}: json { total_amount, currency, legs: [{ line_id,
+  /// fare_product_id, leg_group_id, transfer, amount }] } for the legs, or
+  /// { fares: [...] } with one such fare per journey
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn compute_fare(cache_directory_path: String, query_json: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let query: serde_json::Value =
+        serde_json::from_str(&query_json).map_err(|e| capnp::Error::failed(e.to_string()))?;
+      transition_capnp_data::fare_calculator::fare_query(&cache_directory_path, &query)
+    })
+  }
+}
@@ -0,0 +1,40 @@
+use napi::bindgen_prelude::AsyncTask;
+use napi::{Env, Task};
+
+// Generic task for the native computations returning json: the boxed closure
+// does the work off the main thread and the result is serialized to a string.
+pub struct JsonTask {
+  op: Box<dyn FnOnce() -> Result<String, capnp::Error> + Send>,
+}
+
+impl Task for JsonTask {
+  type Output = String;
+  type JsValue = String;
+
+  fn compute(&mut self) -> napi::Result<Self::Output> {
+    let op = std::mem::replace(&mut self.op, Box::new(|| Ok(String::new())));
+    op().map_err(|e| napi::Error::new(napi::Status::GenericFailure, e.to_string()))
+  }
+
+  fn resolve(&mut self, _env: Env, output: Self::Output) -> napi::Result<Self::JsValue> {
+    Ok(output)
+  }
+}
+
+pub fn json_task(
+  op: impl FnOnce() -> Result<serde_json::Value, capnp::Error> + Send + 'static,
+) -> AsyncTask<JsonTask> {
+  AsyncTask::new(JsonTask {
+    op: Box::new(move || {
+      serde_json::to_string(&op()?).map_err(|e| capnp::Error::failed(e.to_string()))
+    }),
+  })
+}
+
+// For the computations already returning text, like csv, that must not be
+// serialized again
+pub fn text_task(
+  op: impl FnOnce() -> Result<String, capnp::Error> + Send + 'static,
+) -> AsyncTask<JsonTask> {
+  AsyncTask::new(JsonTask { op: Box::new(op) })
+}
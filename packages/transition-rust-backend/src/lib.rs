@@ -3,7 +3,23 @@
 #[macro_use]
 extern crate napi_derive;
 
+mod accessibility_map;
+mod cache_store;
 mod capnp_serialization;
+mod fare_calculator;
+mod json_task;
+mod node_spatial_index;
+mod od_matrix;
+mod path_analytics;
+mod scenario_resolution;
+mod service_calendar;
+mod service_kpis;
+mod station_validation;
+mod timetable_validation;
+mod transferable_nodes;
+mod transit_routing;
+mod vehicle_blocking;
+mod zone_lookup;
 
 // TODO This is just an temporary example function that we can expose
 // and uses in the test to validate that the bindings work correctly
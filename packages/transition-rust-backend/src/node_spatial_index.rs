@@ -0,0 +1,95 @@
+/// This module provides spatial queries on the nodes of a capnp cache
+#[napi]
+pub mod node_spatial_index {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use transition_capnp_data::node_spatial_index::*;
+
+  fn read_index(nodes_file_path: &str) -> Result<NodeSpatialIndex, capnp::Error> {
+    let nodes_json = transition_capnp_data::cache::read_file(
+      nodes_file_path,
+      &transition_capnp_data::serialization::node_collection::read_collection,
+    )?;
+    NodeSpatialIndex::from_collection_json(&nodes_json)
+  }
+
+  /// Find the enabled nodes nearest to a point. The distance is measured to
+  /// the edge of each node's routing radius.
+  ///
+  /// @param {string} nodesFilePath: path to the nodes capnp collection file
+  /// @param {number} longitude: longitude of the point
+  /// @param {number} latitude: latitude of the point
+  /// @param {number} count: maximum number of nodes to return
+  ///
+  /// @returns {string}: json { nodes: [{ id, integer_id, coordinates, distance_meters, center_distance_meters }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn nearest_nodes(
+    nodes_file_path: String,
+    longitude: f64,
+    latitude: f64,
+    count: u32,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let index = read_index(&nodes_file_path)?;
+      Ok(node_distances_to_json(&index.nearest(
+        longitude,
+        latitude,
+        count as usize,
+      )))
+    })
+  }
+
+  /// Find the enabled nodes within a radius of a point, sorted by distance
+  ///
+  /// @param {string} nodesFilePath: path to the nodes capnp collection file
+  /// @param {number} longitude: longitude of the point
+  /// @param {number} latitude: latitude of the point
+  /// @param {number} radiusMeters: maximum distance to the node's routing radius
+  ///
+  /// @returns {string}: json { nodes: [{ id, integer_id, coordinates, distance_meters, center_distance_meters }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn nodes_within_radius(
+    nodes_file_path: String,
+    longitude: f64,
+    latitude: f64,
+    radius_meters: f64,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let index = read_index(&nodes_file_path)?;
+      Ok(node_distances_to_json(&index.within_radius(
+        longitude,
+        latitude,
+        radius_meters,
+      )))
+    })
+  }
+
+  /// Find the enabled nodes inside a bounding box
+  ///
+  /// @param {string} nodesFilePath: path to the nodes capnp collection file
+  /// @param {number} minLongitude
+  /// @param {number} minLatitude
+  /// @param {number} maxLongitude
+  /// @param {number} maxLatitude
+  ///
+  /// @returns {string}: json { nodes: [{ id, integer_id, coordinates }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn nodes_within_bbox(
+    nodes_file_path: String,
+    min_longitude: f64,
+    min_latitude: f64,
+    max_longitude: f64,
+    max_latitude: f64,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let index = read_index(&nodes_file_path)?;
+      Ok(nodes_to_json(&index.within_bbox(
+        min_longitude,
+        min_latitude,
+        max_longitude,
+        max_latitude,
+      )))
+    })
+  }
+}
@@ -0,0 +1,46 @@
+/// This module computes origin-destination travel time matrices on the transit network of a scenario from a capnp cache
+#[napi]
+pub mod od_matrix {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use std::fs::File;
+  use std::io::BufWriter;
+
+  /// Route a batch of OD trips in parallel, without calling trRouting, and
+  /// stream the results to a file as they are computed. The network is
+  /// loaded once per call.
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the capnp
+  /// collection files and the lines and nodes subdirectories
+  /// @param {string} queryJson: json { scenario_id, od_trips: [{ id, origin,
+  /// destination, departure_time_seconds }] or od_trips_csv content or
+  /// od_trips_csv_file_path or origins, destinations and
+  /// departure_time_seconds, format: "csv" or
+  /// "json", and the routing parameters of the route function }. The csv
+  /// has the id, origin_longitude, origin_latitude, destination_longitude,
+  /// destination_latitude and departure_time (seconds or HH:MM[:SS]) columns.
+  /// @param {string} outputFilePath: file receiving one result per trip, with
+  /// the id, status, travel time, transfers, walking and waiting components
+  ///
+  /// @returns {string}: json { trips_count, success_count,
+  /// no_routing_found_count, error_count }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn od_matrix(
+    cache_directory_path: String,
+    query_json: String,
+    output_file_path: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let query: serde_json::Value =
+        serde_json::from_str(&query_json).map_err(|e| capnp::Error::failed(e.to_string()))?;
+      let file = File::create(&output_file_path)
+        .map_err(|e| capnp::Error::failed(format!("Cannot create {}: {}", output_file_path, e)))?;
+      transition_capnp_data::od_matrix::od_matrix_query(
+        &cache_directory_path,
+        &query,
+        &mut BufWriter::new(file),
+      )
+    })
+  }
+}
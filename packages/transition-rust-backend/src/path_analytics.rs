@@ -0,0 +1,53 @@
+/// This module validates the path segments and computes the path lengths
+#[napi]
+pub mod path_analytics {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Validate the segments of a path and compute its segment and total
+  /// lengths, as well as the distance from each node to its snapped point
+  ///
+  /// @param {string} pathJsonStr: json of the path geojson feature, with the
+  /// nodes and segments in its properties
+  /// @param {string} nodeCoordinatesJsonStr: json array of the [longitude,
+  /// latitude] of each of the path's nodes, in order, or null if unknown
+  ///
+  /// @returns {string}: json { valid, errors, total_length_meters,
+  /// segment_lengths_meters, nodes_distances_to_geometry_meters }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn analyze_path(
+    path_json_str: String,
+    node_coordinates_json_str: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let path: serde_json::Value = serde_json::from_str(&path_json_str)
+        .map_err(|e| capnp::Error::failed(format!("Invalid path json: {}", e)))?;
+      let node_coordinates: Vec<Option<[f64; 2]>> =
+        serde_json::from_str(&node_coordinates_json_str)
+          .map_err(|e| capnp::Error::failed(format!("Invalid node coordinates json: {}", e)))?;
+      let coordinates: Vec<[f64; 2]> =
+        serde_json::from_value(path["geometry"]["coordinates"].clone()).unwrap_or_default();
+      let segments: Vec<i64> =
+        serde_json::from_value(path["properties"]["segments"].clone()).unwrap_or_default();
+      Ok(transition_capnp_data::path_analytics::analyze_path(
+        &coordinates,
+        &segments,
+        &node_coordinates,
+      ))
+    })
+  }
+
+  /// Validate and analyze all the paths of a cache directory
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory containing
+  /// nodes.capnpbin and paths.capnpbin
+  ///
+  /// @returns {string}: json { invalid_paths_count, paths: [{ id, valid, errors, ... }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn analyze_path_collection(cache_directory_path: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::path_analytics::analyze_path_collection(&cache_directory_path)
+    })
+  }
+}
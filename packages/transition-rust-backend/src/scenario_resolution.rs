@@ -0,0 +1,58 @@
+/// This module resolves the effective lines, paths and trips of a scenario from a capnp cache,
+/// and extracts them to a smaller cache
+#[napi]
+pub mod scenario_resolution {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Apply the services and the only/except rules of a scenario to the lines
+  /// of a cache. Line objects are read from the `lines` subdirectory.
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the scenarios,
+  /// services, lines and paths capnp collection files
+  /// @param {string} scenarioUuid: uuid of the scenario to resolve
+  ///
+  /// @returns {string}: json { scenario_id, lines_ids, paths_ids, schedules,
+  /// trips, summary }, lines_ids being the lines with trips left, where the
+  /// summary contains what each rule removed
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn resolve_scenario(
+    cache_directory_path: String,
+    scenario_uuid: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::scenario_resolution::resolve_scenario(
+        &cache_directory_path,
+        &scenario_uuid,
+      )
+      .map(|resolved_scenario| resolved_scenario.to_json())
+    })
+  }
+
+  /// Write a cache with only what a scenario uses: its services, lines, line
+  /// objects with only the scenario schedules, paths, nodes and agencies.
+  /// The integer ids of the nodes and paths are kept.
+  ///
+  /// @param {string} sourceCacheDirectoryPath: cache to extract from
+  /// @param {string} scenarioUuid: uuid of the scenario to extract
+  /// @param {string} destinationCacheDirectoryPath: empty cache directory or
+  /// cache store location to write to
+  ///
+  /// @returns {string}: json with the counts of the extracted objects and
+  /// the summary of the scenario resolution
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn extract_scenario_cache(
+    source_cache_directory_path: String,
+    scenario_uuid: String,
+    destination_cache_directory_path: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::scenario_extraction::extract_scenario_cache(
+        &source_cache_directory_path,
+        &scenario_uuid,
+        &destination_cache_directory_path,
+      )
+    })
+  }
+}
@@ -0,0 +1,106 @@
+/// This module answers calendar questions on the services of a capnp cache
+#[napi]
+pub mod service_calendar {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use serde_json::json;
+  use transition_capnp_data::serialization::service_collection;
+  use transition_capnp_data::service_calendar::*;
+
+  fn read_services(services_file_path: &str) -> Result<serde_json::Value, capnp::Error> {
+    transition_capnp_data::cache::read_file(
+      services_file_path,
+      &service_collection::read_collection,
+    )
+  }
+
+  fn read_calendars(services_file_path: &str) -> Result<Vec<ServiceCalendar>, capnp::Error> {
+    calendars_from_collection_json(&read_services(services_file_path)?)
+  }
+
+  /// Validate the dates of the services
+  ///
+  /// @param {string} servicesFilePath: path to the services capnp collection file
+  ///
+  /// @returns {string}: json { valid, invalid_services: [{ id, error }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn validate_services(services_file_path: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      Ok(validate_collection_json(&read_services(
+        &services_file_path,
+      )?))
+    })
+  }
+
+  /// Get the services running on a date
+  ///
+  /// @param {string} servicesFilePath: path to the services capnp collection file
+  /// @param {string} date: date in the YYYY-MM-DD format
+  ///
+  /// @returns {string}: json { date, services_ids }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn services_on_date(services_file_path: String, date: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let date = parse_date(&date)?;
+      let calendars = read_calendars(&services_file_path)?;
+      Ok(json!({
+        "date": format_date(&date),
+        "services_ids": transition_capnp_data::service_calendar::services_on_date(&calendars, &date)
+      }))
+    })
+  }
+
+  /// Get the dates on which a service runs between two dates inclusively
+  ///
+  /// @param {string} servicesFilePath: path to the services capnp collection file
+  /// @param {string} serviceUuid: uuid of the service
+  /// @param {string} startDate: first date of the range, in the YYYY-MM-DD format
+  /// @param {string} endDate: last date of the range, in the YYYY-MM-DD format,
+  /// at most 5 years after the first date
+  ///
+  /// @returns {string}: json { id, dates }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn service_dates(
+    services_file_path: String,
+    service_uuid: String,
+    start_date: String,
+    end_date: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let (start_date, end_date) = (parse_date(&start_date)?, parse_date(&end_date)?);
+      let calendars = read_calendars(&services_file_path)?;
+      let calendar = calendars
+        .iter()
+        .find(|calendar| calendar.uuid == service_uuid)
+        .ok_or_else(|| capnp::Error::failed(format!("Service {} not found", service_uuid)))?;
+      Ok(json!({
+        "id": service_uuid,
+        "dates": dates_to_json(&calendar.dates_in_range(start_date, end_date)?)
+      }))
+    })
+  }
+
+  /// Get the pairs of services running on common dates between two dates inclusively
+  ///
+  /// @param {string} servicesFilePath: path to the services capnp collection file
+  /// @param {string} startDate: first date of the range, in the YYYY-MM-DD format
+  /// @param {string} endDate: last date of the range, in the YYYY-MM-DD format,
+  /// at most 5 years after the first date
+  ///
+  /// @returns {string}: json { overlaps: [{ services_ids, common_dates_count, first_common_date }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn overlapping_services(
+    services_file_path: String,
+    start_date: String,
+    end_date: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let (start_date, end_date) = (parse_date(&start_date)?, parse_date(&end_date)?);
+      let calendars = read_calendars(&services_file_path)?;
+      transition_capnp_data::service_calendar::overlapping_services(
+        &calendars, start_date, end_date,
+      )
+    })
+  }
+}
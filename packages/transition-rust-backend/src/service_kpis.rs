@@ -0,0 +1,59 @@
+/// This module computes the supply indicators of the lines and scenarios of a capnp cache
+#[napi]
+pub mod service_kpis {
+
+  use crate::json_task::{json_task, text_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use transition_capnp_data::service_kpis::*;
+
+  /// Get the trips count, vehicle-hours, vehicle-km, headways, span of
+  /// service and capacity offered of a line, by service and period. The
+  /// headways and span of the whole line are only given for a single service
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the paths
+  /// capnp collection file and the lines subdirectory
+  /// @param {string} lineUuid: uuid of the line
+  ///
+  /// @returns {string}: json { line_id, line_shortname, kpis, services: [{
+  /// service_id, kpis, periods: [{ period_shortname, start_at_hour, end_at_hour, ...kpis }] }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn line_kpis(cache_directory_path: String, line_uuid: String) -> AsyncTask<JsonTask> {
+    json_task(move || cached_line_kpis(&cache_directory_path, &line_uuid))
+  }
+
+  /// Get the indicators of the lines effective in a scenario, after applying
+  /// its services and filters, and their total for the scenario
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the scenarios,
+  /// services, lines and paths capnp collection files and the lines subdirectory
+  /// @param {string} scenarioUuid: uuid of the scenario
+  ///
+  /// @returns {string}: json { scenario_id, kpis, lines: [line kpis] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn scenario_kpis(cache_directory_path: String, scenario_uuid: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::service_kpis::scenario_kpis(&cache_directory_path, &scenario_uuid)
+    })
+  }
+
+  /// Get the indicators of a scenario as csv, with one row per period,
+  /// service, line and for the scenario, identified by the level column
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the scenarios,
+  /// services, lines and paths capnp collection files and the lines subdirectory
+  /// @param {string} scenarioUuid: uuid of the scenario
+  ///
+  /// @returns {string}: csv content
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn scenario_kpis_csv(
+    cache_directory_path: String,
+    scenario_uuid: String,
+  ) -> AsyncTask<JsonTask> {
+    text_task(move || {
+      scenario_kpis_to_csv(&transition_capnp_data::service_kpis::scenario_kpis(
+        &cache_directory_path,
+        &scenario_uuid,
+      )?)
+    })
+  }
+}
@@ -0,0 +1,22 @@
+/// This module checks the consistency of the stations and the nodes referencing them
+#[napi]
+pub mod station_validation {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Check that the station_id of every node resolves to a station listing
+  /// the node, and that every node of a station references it
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory containing
+  /// nodes.capnpbin and stations.capnpbin
+  ///
+  /// @returns {string}: json { valid, errors, stations_count,
+  /// nodes_with_station_count, nodes_with_unknown_station }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn validate_station_collection(cache_directory_path: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::station_validation::validate_station_collection(&cache_directory_path)
+    })
+  }
+}
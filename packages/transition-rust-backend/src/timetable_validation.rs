@@ -0,0 +1,28 @@
+/// This module validates the trip timetables of the line objects against the paths of a capnp cache
+#[napi]
+pub mod timetable_validation {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Validate the trips of a line object of the cache against the nodes of
+  /// their path. The line object is read from the `lines` subdirectory.
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the paths capnp collection file
+  /// @param {string} lineUuid: uuid of the line to validate
+  ///
+  /// @returns {string}: json { line_id, valid, trips_count, invalid_trips: [{
+  /// service_id, schedule_id, period_index, period_shortname, trip_index, trip_id, errors }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn validate_line_timetables(
+    cache_directory_path: String,
+    line_uuid: String,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::timetable_validation::validate_cached_line_object(
+        &cache_directory_path,
+        &line_uuid,
+      )
+    })
+  }
+}
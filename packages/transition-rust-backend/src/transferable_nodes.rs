@@ -0,0 +1,40 @@
+/// This module computes the walking transfers between the nodes of a capnp cache
+#[napi]
+pub mod transferable_nodes {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use transition_capnp_data::transferable_nodes::*;
+
+  /// Compute the transferable nodes of every node of the cache's node
+  /// collection, using the straight line distance multiplied by a detour
+  /// factor, and write them to the node objects
+  ///
+  /// @param {string} cacheDirectoryPath: path to the directory containing nodes.capnpbin
+  /// @param {string} nodesDirectoryPath: path to the directory where to write the node objects
+  /// @param {number} maxWalkingDistanceMeters: maximum walking distance of a transfer
+  /// @param {number} detourFactor: factor applied to the straight line distance, at least 1
+  /// @param {number} walkingSpeedMetersPerSecond: walking speed used for the travel times
+  ///
+  /// @returns {string}: json { nodes_count, transfers_count }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn write_transferable_nodes(
+    cache_directory_path: String,
+    nodes_directory_path: String,
+    max_walking_distance_meters: f64,
+    detour_factor: f64,
+    walking_speed_meters_per_second: f64,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      transition_capnp_data::transferable_nodes::write_transferable_nodes(
+        &cache_directory_path,
+        &nodes_directory_path,
+        &TransferableNodesParameters {
+          max_walking_distance_meters,
+          detour_factor,
+          walking_speed_meters_per_second,
+        },
+      )
+    })
+  }
+}
@@ -0,0 +1,31 @@
+/// This module routes trips on the transit network of a scenario from a capnp cache
+#[napi]
+pub mod transit_routing {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Route between two places with the connection scan algorithm, without
+  /// calling trRouting. The network is loaded from the cache for each query.
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the capnp
+  /// collection files and the lines and nodes subdirectories
+  /// @param {string} queryJson: json { scenario_id, origin, destination,
+  /// departure_time_seconds or departure_time_range_seconds: [start, end],
+  /// min_waiting_time_seconds, max_access_egress_travel_time_seconds,
+  /// max_transfer_travel_time_seconds, max_total_travel_time_seconds,
+  /// walking_speed_meters_per_second, allow_same_line_transfers }, where
+  /// places are node uuids or [longitude, latitude]
+  ///
+  /// @returns {string}: json journey { status, departure_time_seconds,
+  /// arrival_time_seconds, total_travel_time_seconds, ..., steps } for a
+  /// departure time, or { journeys } for a departure time range
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn route(cache_directory_path: String, query_json: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let query: serde_json::Value =
+        serde_json::from_str(&query_json).map_err(|e| capnp::Error::failed(e.to_string()))?;
+      transition_capnp_data::transit_routing::route_query(&cache_directory_path, &query)
+    })
+  }
+}
@@ -0,0 +1,46 @@
+/// This module chains the trips of the lines of a capnp cache into vehicle blocks
+#[napi]
+pub mod vehicle_blocking {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+  use transition_capnp_data::vehicle_blocking::*;
+
+  /// Assign the trips of a line to vehicle blocks with a minimal fleet size,
+  /// separately for each service, using the dead head travel times between
+  /// paths of the line data
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing the lines and
+  /// paths capnp collection files and the lines subdirectory
+  /// @param {string} lineUuid: uuid of the line
+  /// @param {number} minLayoverSeconds: minimum time between two trips of a vehicle
+  /// @param {boolean} interlining: whether vehicles can be shared with the
+  /// other lines of the agency
+  /// @param {boolean} write: whether to write the line objects with their
+  /// new trip block ids back to the cache
+  ///
+  /// @returns {string}: json { lines_ids, services: [{ service_id, vehicles_count,
+  /// blocks: [{ id, trips_ids, lines_ids, start_time_seconds, end_time_seconds }],
+  /// peak_vehicles_by_period: [{ period_shortname, start_at_hour, end_at_hour,
+  /// vehicles_count }], unblocked_trips_ids }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn compute_vehicle_blocks(
+    cache_directory_path: String,
+    line_uuid: String,
+    min_layover_seconds: i64,
+    interlining: bool,
+    write: bool,
+  ) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      compute_cached_blocks(
+        &cache_directory_path,
+        &line_uuid,
+        interlining,
+        &BlockingParameters {
+          min_layover_seconds,
+        },
+        write,
+      )
+    })
+  }
+}
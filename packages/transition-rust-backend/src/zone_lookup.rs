@@ -0,0 +1,29 @@
+/// This module locates points, nodes and OD trips in the analysis zones of a capnp cache
+#[napi]
+pub mod zone_lookup {
+
+  use crate::json_task::{json_task, JsonTask};
+  use napi::bindgen_prelude::AsyncTask;
+
+  /// Find the zones containing points, and the nodes and OD trips of the
+  /// cache, with the count of each in every zone
+  ///
+  /// @param {string} cacheDirectoryPath: directory containing zones.capnpbin,
+  /// and nodes.capnpbin and odTrips.capnpbin when they are located
+  /// @param {string} queryJson: json { points: [{ id, coordinates:
+  /// [longitude, latitude] }], nodes: boolean, od_trips: boolean }
+  ///
+  /// @returns {string}: json { points: [{ id, zones }], nodes: [{ id, zones }],
+  /// od_trips: [{ id, origin_zones, destination_zones }], zones: [{ id,
+  /// integer_id, points_count, nodes_count, od_trips_origins_count,
+  /// od_trips_destinations_count, od_trips_origins_weight,
+  /// od_trips_destinations_weight }] }
+  #[napi(ts_return_type = "Promise<string>")]
+  pub fn locate_in_zones(cache_directory_path: String, query_json: String) -> AsyncTask<JsonTask> {
+    json_task(move || {
+      let query: serde_json::Value =
+        serde_json::from_str(&query_json).map_err(|e| capnp::Error::failed(e.to_string()))?;
+      transition_capnp_data::zone_lookup::locate_in_zones_query(&cache_directory_path, &query)
+    })
+  }
+}
@@ -0,0 +1,505 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Commands run once on a cache directory, printing their result instead of starting the server */
+
+const COMMANDS: [&str; 5] = ["kpis", "convert", "gc", "extract", "clip"];
+
+pub fn is_command(name: &str) -> bool {
+    COMMANDS.contains(&name)
+}
+
+fn usage(command: &str) -> capnp::Error {
+    let usage = match command {
+        "kpis" => "kpis <cache_directory_path> <scenario_uuid> [json|csv]",
+        "convert" => "convert <source_cache_location> <destination_cache_location>",
+        "gc" => "gc <cache_directory_path> [report|delete] [max_age_days] [remove_unknown_age]",
+        "extract" => "extract <cache_directory_path> <scenario_uuid> <destination_cache_location>",
+        "clip" => "clip <cache_directory_path> <area_json|area_geojson_file_path> <destination_cache_location>",
+        _ => ""
+    };
+    capnp::Error::failed(format!("Usage: json2capnp {}", usage))
+}
+
+/// Run the command, the first argument being the command name, and return
+/// what it prints
+pub fn command_output(args: &[String]) -> Result<String, capnp::Error> {
+    let command = args[0].as_str();
+    match command {
+        "kpis" => {
+            if args.len() < 3 || args.len() > 4 {
+                return Err(usage(command));
+            }
+            let kpis = transition_capnp_data::service_kpis::scenario_kpis(&args[1], &args[2])?;
+            match args.get(3).map(|format| format.as_str()) {
+                None | Some("json") => Ok(format!("{}\n", serde_json::to_string_pretty(&kpis).unwrap())),
+                Some("csv") => transition_capnp_data::service_kpis::scenario_kpis_to_csv(&kpis),
+                Some(_) => Err(usage(command))
+            }
+        },
+        // eg convert path/to/cache/dir sqlite://path/to/cache.sqlite
+        "convert" => {
+            if args.len() != 3 {
+                return Err(usage(command));
+            }
+            let files_count = transition_capnp_data::cache_store::convert(&args[1], &args[2])?;
+            Ok(format!("Copied {} files from {} to {}\n", files_count, args[1], args[2]))
+        },
+        // only reports the files to remove, unless delete is given, and
+        // never removes the subdirectories of unknown age of a cache store
+        // unless remove_unknown_age is given
+        "gc" => {
+            if args.len() < 2 || args.len() > 5 {
+                return Err(usage(command));
+            }
+            let mut options = transition_capnp_data::cache_gc::CacheGcOptions::default();
+            match args.get(2).map(|mode| mode.as_str()) {
+                None | Some("report") => options.dry_run = true,
+                Some("delete") => options.dry_run = false,
+                Some(_) => return Err(usage(command))
+            }
+            if let Some(max_age_days) = args.get(3) {
+                options.max_age_days = max_age_days.parse::<f64>().map_err(|_| usage(command))?;
+            }
+            match args.get(4).map(|flag| flag.as_str()) {
+                None => (),
+                Some("remove_unknown_age") => options.remove_subdirectories_of_unknown_age = true,
+                Some(_) => return Err(usage(command))
+            }
+            let report = transition_capnp_data::cache_gc::gc_cache(&args[1], &options)?;
+            Ok(format!("{}\n", serde_json::to_string_pretty(&report.to_json()).unwrap()))
+        },
+        "extract" => {
+            if args.len() != 4 {
+                return Err(usage(command));
+            }
+            let extraction = transition_capnp_data::scenario_extraction::extract_scenario_cache(&args[1], &args[2], &args[3])?;
+            Ok(format!("{}\n", serde_json::to_string_pretty(&extraction).unwrap()))
+        },
+        // eg clip path/to/cache/dir '{"bbox":[-73.7,45.4,-73.5,45.6]}' path/to/study_area/cache/dir
+        "clip" => {
+            if args.len() != 4 {
+                return Err(usage(command));
+            }
+            let area_json = if args[2].trim_start().starts_with('{') {
+                args[2].clone()
+            } else {
+                std::fs::read_to_string(&args[2]).map_err(|e| capnp::Error::failed(format!("Cannot read {}: {}", args[2], e)))?
+            };
+            let area_json : serde_json::Value = serde_json::from_str(&area_json).map_err(|e| capnp::Error::failed(format!("Invalid area json: {}", e)))?;
+            let area = transition_capnp_data::cache_clipping::clip_area_from_json(&area_json)?;
+            let report = transition_capnp_data::cache_clipping::clip_cache(&args[1], &args[3], &area)?;
+            Ok(format!("{}\n", serde_json::to_string_pretty(&report).unwrap()))
+        },
+        _ => Err(capnp::Error::failed(format!("Unknown command {}", command)))
+    }
+}
+
+/// Run the command and return the process exit code
+pub fn run(args: &[String]) -> i32 {
+    match command_output(args) {
+        Ok(output) => {
+            print!("{}", output);
+            0
+        },
+        Err(error) => {
+            eprintln!("{}", error.extra);
+            1
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+
+    use super::*;
+    use std::path::{Path};
+    use std::fs;
+    use pretty_assertions::{assert_eq};
+
+    fn trip(trip_uuid: &str, path_uuid: &str, departure_time_seconds: i64, arrival_time_seconds: i64) -> serde_json::Value {
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": departure_time_seconds,
+            "arrival_time_seconds": arrival_time_seconds,
+            "seated_capacity": 20,
+            "total_capacity": 50,
+            "node_arrival_times_seconds": [null, arrival_time_seconds],
+            "node_departure_times_seconds": [departure_time_seconds, null],
+            "nodes_can_board": [true, false],
+            "nodes_can_unboard": [false, true]
+        })
+    }
+
+    fn write_kpis_cache(cache_directory_path: &Path) {
+
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+
+        write("scenarios", &transition_capnp_data::serialization::scenario_collection::write_collection, json!({
+            "scenarios": [{ "id": "scenario", "services": ["weekday"], "except_lines": ["excluded"] }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "weekday" }]
+        }));
+        // outbound is ~7.8km, inbound ~3.9km
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.6, 45.5], [-73.5, 45.5]] }, "properties": { "id": "outbound", "integer_id": 1, "line_id": "line", "nodes": ["n1", "n2"], "segments": [0] } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": [[-73.5, 45.5], [-73.55, 45.5]] }, "properties": { "id": "inbound", "integer_id": 2, "line_id": "line", "nodes": ["n2", "n1"], "segments": [0] } }
+                ]
+            }
+        }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({
+            "lines": [
+                { "id": "line", "agency_id": "agency", "mode": "bus", "shortname": "1" },
+                { "id": "excluded", "agency_id": "agency", "mode": "bus", "shortname": "2" }
+            ]
+        }));
+        write("vehicleTypes", &transition_capnp_data::serialization::vehicle_type_collection::write_collection, json!({
+            "vehicle_types": [{ "id": "standard_bus", "mode": "bus", "seated_capacity": 30, "standing_capacity": 40 }]
+        }));
+        // the capacity of this trip comes from the vehicle type of its period
+        let mut trip_without_capacity = trip("t4", "inbound", 23000, 23600);
+        trip_without_capacity["seated_capacity"] = json!(null);
+        trip_without_capacity["total_capacity"] = json!(null);
+        for line_uuid in ["line", "excluded"] {
+            transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &json!({
+                "line": {
+                    "id": line_uuid,
+                    "agency_id": "agency",
+                    "mode": "bus",
+                    "shortname": if line_uuid == "line" { "1" } else { "2" },
+                    "scheduleByServiceId": {
+                        "weekday": {
+                            "id": "schedule",
+                            "service_id": "weekday",
+                            "periods": [
+                                { "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "vehicle_type_id": "standard_bus", "trips": [
+                                    trip("t1", "outbound", 21600, 22600),
+                                    trip("t2", "outbound", 22500, 23500),
+                                    trip("t3", "outbound", 24300, 25300),
+                                    trip_without_capacity.clone()
+                                ] },
+                                { "period_shortname": "midday", "start_at_hour": 9, "end_at_hour": 15, "trips": [
+                                    trip("t5", "missing", 36000, 37800)
+                                ] }
+                            ]
+                        }
+                    }
+                }
+            })).unwrap();
+        }
+
+    }
+
+    #[test]
+    fn kpis() {
+
+        let cache_directory_path = Path::new("test").join("kpis");
+        write_kpis_cache(&cache_directory_path);
+
+        let args = |format: &str| -> Vec<String> {
+            vec![String::from("kpis"), cache_directory_path.to_str().unwrap().to_string(), String::from("scenario"), format.to_string()]
+        };
+
+        let kpis : serde_json::Value = serde_json::from_str(&command_output(&args("json")).unwrap()).unwrap();
+        assert_eq!(kpis["lines"].as_array().unwrap().len(), 1);
+        let am_peak = &kpis["lines"][0]["services"][0]["periods"][0];
+        assert_eq!(am_peak["period_shortname"], "am_peak");
+        assert_eq!(am_peak["trips_count"], 4);
+        assert_eq!(am_peak["vehicle_hours"], 1.0);
+        assert_eq!((am_peak["vehicle_km"].as_f64().unwrap() * 10.0).round() / 10.0, 27.3);
+        assert_eq!(am_peak["average_headway_seconds"], 1350.0);
+        assert_eq!(am_peak["max_headway_seconds"], 1800);
+        assert_eq!(am_peak["span_of_service_seconds"], 25300 - 21600);
+        assert_eq!(am_peak["seated_capacity"], 90);
+        assert_eq!(am_peak["total_capacity"], 220);
+        // the scenario resolution removes the trip on the missing path
+        assert_eq!(kpis["lines"][0]["services"][0]["periods"][1]["trips_count"], 0);
+        assert_eq!(kpis["kpis"]["trips_count"], 4);
+        assert_eq!(kpis["kpis"]["vehicle_hours"], 1.0);
+
+        let csv = command_output(&args("csv")).unwrap();
+        let rows : Vec<&str> = csv.lines().collect();
+        assert_eq!(rows[0], "level,line_id,line_shortname,service_id,period_shortname,trips_count,vehicle_hours,vehicle_km,average_headway_seconds,max_headway_seconds,first_departure_seconds,last_arrival_seconds,span_of_service_seconds,seated_capacity,total_capacity,trips_without_path_count");
+        assert_eq!(rows.len(), 6);
+        assert_eq!(rows[2], "period,line,1,weekday,midday,0,0.0,0.0,,,,,,0,0,0");
+        assert!(rows[5].starts_with("scenario,,,,,4,1.0,"));
+
+        // the weekday and saturday trips at the same times are not 0s headways of the line
+        let period = |trips: serde_json::Value| json!({ "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": trips });
+        let line = json!({
+            "id": "line",
+            "scheduleByServiceId": {
+                "weekday": { "service_id": "weekday", "periods": [period(json!([trip("t1", "outbound", 21600, 22600), trip("t2", "outbound", 22500, 23500)]))] },
+                "saturday": { "service_id": "saturday", "periods": [period(json!([trip("t3", "outbound", 21600, 22600), trip("t4", "outbound", 22500, 23500)]))] }
+            }
+        });
+        let line_kpis = transition_capnp_data::service_kpis::line_kpis(&line, &std::collections::HashMap::new());
+        for service in line_kpis["services"].as_array().unwrap() {
+            assert_eq!(service["kpis"]["average_headway_seconds"], 900.0);
+            assert_eq!(service["kpis"]["span_of_service_seconds"], 23500 - 21600);
+        }
+        assert_eq!(line_kpis["kpis"]["trips_count"], 4);
+        assert_eq!(line_kpis["kpis"]["average_headway_seconds"], json!(null));
+        assert_eq!(line_kpis["kpis"]["max_headway_seconds"], json!(null));
+        assert_eq!(line_kpis["kpis"]["span_of_service_seconds"], json!(null));
+
+        assert!(command_output(&[String::from("kpis"), String::from("test")]).is_err());
+
+    }
+
+    #[test]
+    fn convert() {
+
+        let cache_directory_path = Path::new("test").join("convert");
+        write_kpis_cache(&cache_directory_path);
+        let database_path = Path::new("test").join("convert.sqlite");
+        let _ = fs::remove_file(&database_path);
+        let sqlite_location = format!("sqlite://{}", database_path.to_str().unwrap());
+        let directory_location = cache_directory_path.to_str().unwrap().to_string();
+
+        let output = command_output(&[String::from("convert"), directory_location.clone(), sqlite_location.clone()]).unwrap();
+        assert!(output.starts_with("Copied 7 files"));
+        // the whole cache is in the single database file, and reads like the directory
+        let kpis = |location: &str| command_output(&[String::from("kpis"), location.to_string(), String::from("scenario")]).unwrap();
+        assert_eq!(kpis(&sqlite_location), kpis(&directory_location));
+        let line_files = transition_capnp_data::cache_store::open(&format!("{}/lines", sqlite_location)).unwrap().list("").unwrap();
+        assert_eq!(line_files, vec!["line_excluded.capnpbin", "line_line.capnpbin"]);
+
+        let converted_back_directory_path = Path::new("test").join("convert_back");
+        let _ = fs::remove_dir_all(&converted_back_directory_path);
+        command_output(&[String::from("convert"), sqlite_location, converted_back_directory_path.to_str().unwrap().to_string()]).unwrap();
+        for file_name in ["scenarios.capnpbin", "lines/line_line.capnpbin"] {
+            assert_eq!(fs::read(converted_back_directory_path.join(file_name)).unwrap(), fs::read(cache_directory_path.join(file_name)).unwrap());
+        }
+
+        assert!(command_output(&[String::from("convert"), String::from("test")]).is_err());
+
+        // the files staged in an open transaction and a database in the source are not copied
+        let transaction_id = transition_capnp_data::cache_store::transaction::begin(&directory_location).unwrap();
+        let transaction_location = transition_capnp_data::cache_store::transaction::transaction_location(&directory_location, &transaction_id).unwrap();
+        transition_capnp_data::cache_store::open(&transaction_location).unwrap().put("lines/line_staged.capnpbin", b"staged").unwrap();
+        let inner_database_path = cache_directory_path.join("converted.sqlite");
+        let inner_sqlite_location = format!("sqlite://{}", inner_database_path.to_str().unwrap());
+        let output = command_output(&[String::from("convert"), directory_location.clone(), inner_sqlite_location.clone()]).unwrap();
+        assert!(output.starts_with("Copied 7 files"));
+        let keys = transition_capnp_data::cache_store::open(&inner_sqlite_location).unwrap().list("").unwrap();
+        assert_eq!(keys.len(), 7);
+        assert!(!keys.iter().any(|key| key.starts_with(".transactions") || key.starts_with("converted.sqlite")));
+        transition_capnp_data::cache_store::transaction::rollback(&directory_location, &transaction_id).unwrap();
+        fs::remove_file(&inner_database_path).unwrap();
+
+    }
+
+    #[test]
+    fn extract() {
+
+        let cache_directory_path = Path::new("test").join("extract");
+        write_kpis_cache(&cache_directory_path);
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+        write("agencies", &transition_capnp_data::serialization::agency_collection::write_collection, json!({
+            "agencies": [{ "id": "agency", "acronym": "STM" }, { "id": "unused_agency", "acronym": "RTL" }]
+        }));
+        let node = |node_uuid: &str, integer_id: u32, longitude: f64| json!({
+            "type": "Feature", "id": integer_id, "geometry": { "type": "Point", "coordinates": [longitude, 45.5] }, "properties": { "id": node_uuid, "integer_id": integer_id }
+        });
+        write("nodes", &transition_capnp_data::serialization::node_collection::write_collection, json!({
+            "nodes": { "type": "FeatureCollection", "features": [node("n1", 1, -73.6), node("unused_node", 2, -73.7), node("n2", 3, -73.5)] }
+        }));
+        transition_capnp_data::serialization::node::write_object(cache_directory_path.join("nodes").to_str().unwrap(), &json!({
+            "node": {
+                "id": "n1", "integer_id": 1, "geography": { "type": "Point", "coordinates": [-73.6, 45.5] },
+                "data": { "transferableNodes": { "nodesIds": ["unused_node", "n2"], "walkingTravelTimesSeconds": [300, 600], "walkingDistancesMeters": [400, 800] } }
+            }
+        })).unwrap();
+
+        let extracted_cache_directory_path = Path::new("test").join("extract_scenario");
+        let _ = fs::remove_dir_all(&extracted_cache_directory_path);
+        let source = cache_directory_path.to_str().unwrap().to_string();
+        let destination = extracted_cache_directory_path.to_str().unwrap().to_string();
+        let output = command_output(&[String::from("extract"), source.clone(), String::from("scenario"), destination.clone()]).unwrap();
+        let extraction : serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(extraction["lines_count"], 1);
+        assert_eq!(extraction["agencies_count"], 1);
+        assert_eq!(extraction["paths_count"], 2);
+        assert_eq!(extraction["nodes_count"], 2);
+        assert_eq!(extraction["node_objects_count"], 1);
+        assert_eq!(extraction["trips_count"], 4);
+
+        // the extracted cache gives the same scenario indicators
+        let kpis = |location: &str| command_output(&[String::from("kpis"), location.to_string(), String::from("scenario")]).unwrap();
+        assert_eq!(kpis(&destination), kpis(&source));
+        let store = transition_capnp_data::cache_store::open(&destination).unwrap();
+        assert_eq!(store.list("lines/").unwrap(), vec!["lines/line_line.capnpbin"]);
+
+        // the integer ids are the ones of the source cache
+        let nodes = transition_capnp_data::cache::read_collection_file(&destination, "nodes", &transition_capnp_data::serialization::node_collection::read_collection).unwrap();
+        let node_ids : Vec<(&str, u64)> = nodes["nodes"]["features"].as_array().unwrap().iter()
+            .map(|node| (node["properties"]["id"].as_str().unwrap(), node["properties"]["integer_id"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(node_ids, vec![("n1", 1), ("n2", 3)]);
+        let paths = transition_capnp_data::cache::read_collection_file(&destination, "paths", &transition_capnp_data::serialization::path_collection::read_collection).unwrap();
+        assert_eq!(paths["paths"]["features"][1]["properties"]["integer_id"], 2);
+        let n1 = transition_capnp_data::serialization::node::read_object(&String::from("n1"), &format!("{}/nodes", destination)).unwrap();
+        assert_eq!(n1["node"]["data"]["transferableNodes"], json!({ "nodesIds": ["n2"], "walkingTravelTimesSeconds": [600], "walkingDistancesMeters": [800] }));
+
+        assert!(command_output(&[String::from("extract"), source, String::from("missing_scenario"), destination]).is_err());
+
+    }
+
+    #[test]
+    fn clip() {
+
+        let cache_directory_path = Path::new("test").join("clip");
+        let _ = fs::remove_dir_all(&cache_directory_path);
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+        let longitudes = [("n1", -73.6), ("n2", -73.55), ("n3", -73.5), ("n4", -73.45), ("n5", -73.4)];
+        write("nodes", &transition_capnp_data::serialization::node_collection::write_collection, json!({
+            "nodes": { "type": "FeatureCollection", "features": longitudes.iter().enumerate().map(|(index, (node_uuid, longitude))| json!({
+                "type": "Feature", "id": index + 1, "geometry": { "type": "Point", "coordinates": [longitude, 45.5] }, "properties": { "id": node_uuid, "integer_id": index + 1 }
+            })).collect::<Vec<serde_json::Value>>() }
+        }));
+        let coordinates = |node_uuids: &[&str]| node_uuids.iter().map(|node_uuid| json!([longitudes.iter().find(|(uuid, _)| uuid == node_uuid).unwrap().1, 45.5])).collect::<Vec<serde_json::Value>>();
+        // the first segment of the crossing path has an intermediate coordinate
+        let mut crossing_coordinates = coordinates(&["n1", "n2", "n3", "n4"]);
+        crossing_coordinates.insert(1, json!([-73.575, 45.51]));
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": crossing_coordinates }, "properties": {
+                        "id": "crossing", "integer_id": 1, "line_id": "line", "nodes": ["n1", "n2", "n3", "n4"], "stops": ["n1", "n4"], "segments": [0, 2, 3],
+                        "data": {
+                            "nodeTypes": ["engine", "engine", "engine", "manual"],
+                            "segments": [{ "travelTimeSeconds": 120, "distanceMeters": 1000 }, { "travelTimeSeconds": 60, "distanceMeters": 500 }, { "travelTimeSeconds": 90, "distanceMeters": 800 }],
+                            "dwellTimeSeconds": [0, 20, 10, 0],
+                            "totalDistanceMeters": 2300, "totalDwellTimeSeconds": 30, "operatingTimeWithoutLayoverTimeSeconds": 300,
+                            "operatingTimeWithLayoverTimeSeconds": 480, "layoverTimeSeconds": 180, "operatingSpeedMetersPerSecond": 7.67
+                        }
+                    } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": coordinates(&["n1", "n2", "n4", "n2", "n3"]) }, "properties": {
+                        "id": "leaving_and_back", "integer_id": 2, "line_id": "line", "nodes": ["n1", "n2", "n4", "n2", "n3"], "segments": [0, 1, 2, 3],
+                        "data": { "totalDistanceMeters": 4000, "defaultRunningSpeedKmH": 20 }
+                    } },
+                    { "type": "Feature", "id": 3, "geometry": { "type": "LineString", "coordinates": coordinates(&["n4", "n5"]) }, "properties": {
+                        "id": "outside", "integer_id": 3, "line_id": "outside_line", "nodes": ["n4", "n5"], "segments": [0]
+                    } }
+                ]
+            }
+        }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({
+            "lines": [
+                { "id": "line", "agency_id": "agency", "mode": "bus" },
+                { "id": "outside_line", "agency_id": "other_agency", "mode": "bus" }
+            ]
+        }));
+        write("agencies", &transition_capnp_data::serialization::agency_collection::write_collection, json!({
+            "agencies": [{ "id": "agency", "acronym": "STM" }, { "id": "other_agency", "acronym": "RTL" }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "weekday" }]
+        }));
+        let trip = |trip_uuid: &str, path_uuid: &str, departure_times: Vec<i64>| {
+            let nodes_count = departure_times.len();
+            json!({
+                "id": trip_uuid,
+                "path_id": path_uuid,
+                "departure_time_seconds": departure_times[0],
+                "arrival_time_seconds": departure_times[nodes_count - 1],
+                "node_arrival_times_seconds": departure_times.iter().enumerate().map(|(index, time)| if index == 0 { json!(null) } else { json!(time) }).collect::<Vec<serde_json::Value>>(),
+                "node_departure_times_seconds": departure_times.iter().enumerate().map(|(index, time)| if index == nodes_count - 1 { json!(null) } else { json!(time) }).collect::<Vec<serde_json::Value>>(),
+                "nodes_can_board": (0..nodes_count).map(|index| index < nodes_count - 1).collect::<Vec<bool>>(),
+                "nodes_can_unboard": (0..nodes_count).map(|index| index > 0).collect::<Vec<bool>>()
+            })
+        };
+        for (line_uuid, trips) in [
+            ("line", vec![trip("t1", "crossing", vec![21600, 21720, 21780, 21870]), trip("t2", "leaving_and_back", vec![22000, 22060, 22120, 22180, 22240])]),
+            ("outside_line", vec![trip("t3", "outside", vec![21600, 21700])])
+        ] {
+            transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &json!({
+                "line": {
+                    "id": line_uuid,
+                    "agency_id": "agency",
+                    "mode": "bus",
+                    "scheduleByServiceId": {
+                        "weekday": { "id": "schedule", "service_id": "weekday", "periods": [{ "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": trips }] }
+                    }
+                }
+            })).unwrap();
+        }
+
+        let clipped_cache_directory_path = Path::new("test").join("clipped");
+        let _ = fs::remove_dir_all(&clipped_cache_directory_path);
+        let source = cache_directory_path.to_str().unwrap().to_string();
+        let destination = clipped_cache_directory_path.to_str().unwrap().to_string();
+        // n1, n2 and n3 are in the area
+        let output = command_output(&[String::from("clip"), source.clone(), String::from(r#"{"bbox":[-73.61,45.4,-73.49,45.6]}"#), destination.clone()]).unwrap();
+        let report : serde_json::Value = serde_json::from_str(&output).unwrap();
+        assert_eq!(report["nodes"], json!({ "kept_count": 3, "removed_count": 2 }));
+        assert_eq!(report["paths"], json!({ "kept_count": 2, "clipped": ["crossing", "leaving_and_back"], "cut_parts": ["leaving_and_back"], "removed": ["outside"] }));
+        assert_eq!(report["lines"], json!({ "kept_count": 1, "removed": ["outside_line"] }));
+        assert_eq!(report["trips"], json!({ "kept_count": 2, "clipped_count": 2, "removed_count": 1 }));
+
+        let paths = transition_capnp_data::cache::read_collection_file(&destination, "paths", &transition_capnp_data::serialization::path_collection::read_collection).unwrap();
+        let crossing = &paths["paths"]["features"][0];
+        assert_eq!(crossing["properties"]["nodes"], json!(["n1", "n2", "n3"]));
+        assert_eq!(crossing["properties"]["stops"], json!(["n1"]));
+        assert_eq!(crossing["properties"]["segments"], json!([0, 2]));
+        assert_eq!(crossing["properties"]["integer_id"], 1);
+        assert_eq!(crossing["geometry"]["coordinates"].as_array().unwrap().len(), 4);
+        assert_eq!(crossing["properties"]["data"]["nodeTypes"], json!(["engine", "engine", "engine"]));
+        assert_eq!(crossing["properties"]["data"]["segments"], json!([{ "travelTimeSeconds": 120, "distanceMeters": 1000 }, { "travelTimeSeconds": 60, "distanceMeters": 500 }]));
+        // the totals are the ones of the segments kept, the layover is not known
+        assert_eq!(crossing["properties"]["data"]["totalDistanceMeters"], 1500.0);
+        assert_eq!(crossing["properties"]["data"]["totalDwellTimeSeconds"], 30.0);
+        assert_eq!(crossing["properties"]["data"]["operatingTimeWithoutLayoverTimeSeconds"], 210.0);
+        assert_eq!(crossing["properties"]["data"]["operatingSpeedMetersPerSecond"], 7.14);
+        assert!(crossing["properties"]["data"].get("operatingTimeWithLayoverTimeSeconds").is_none());
+        assert!(crossing["properties"]["data"].get("layoverTimeSeconds").is_none());
+        // the first of the two parts in the area is kept
+        let leaving_and_back = &paths["paths"]["features"][1];
+        assert_eq!(leaving_and_back["properties"]["nodes"], json!(["n1", "n2"]));
+        assert_eq!(leaving_and_back["properties"]["segments"], json!([0]));
+        assert_eq!(leaving_and_back["geometry"]["coordinates"], json!([[-73.6, 45.5], [-73.55, 45.5]]));
+        // without segments data, the totals cannot be recomputed
+        assert_eq!(leaving_and_back["properties"]["data"], json!({ "defaultRunningSpeedKmH": 20 }));
+
+        let line = transition_capnp_data::serialization::line::read_object(&String::from("line"), &format!("{}/lines", destination)).unwrap();
+        let trips = &line["line"]["scheduleByServiceId"]["weekday"]["periods"][0]["trips"];
+        assert_eq!(trips[0]["departure_time_seconds"], 21600);
+        assert_eq!(trips[0]["arrival_time_seconds"], 21780);
+        assert_eq!(trips[0]["node_departure_times_seconds"], json!([21600, 21720, null]));
+        assert_eq!(trips[0]["nodes_can_board"], json!([true, true, false]));
+        assert_eq!(trips[1]["node_arrival_times_seconds"], json!([null, 22060]));
+        let store = transition_capnp_data::cache_store::open(&destination).unwrap();
+        assert_eq!(store.list("").unwrap(), vec!["agencies.capnpbin", "lines.capnpbin", "lines/line_line.capnpbin", "nodes.capnpbin", "paths.capnpbin", "services.capnpbin"]);
+        let agencies = transition_capnp_data::cache::read_collection_file(&destination, "agencies", &transition_capnp_data::serialization::agency_collection::read_collection).unwrap();
+        assert_eq!(agencies["agencies"].as_array().unwrap().len(), 1);
+
+        assert!(command_output(&[String::from("clip"), source, String::from(r#"{"bbox":[-73.61]}"#), destination]).is_err());
+
+    }
+
+}
@@ -26,12 +26,21 @@ extern crate rouille;
 extern crate serde_json;
 
 mod routers;
+mod cli;
 use transition_capnp_data;
 
+// Networks kept in memory for the routing queries, each one has all the trips of a scenario
+const MAX_LOADED_NETWORKS_COUNT: usize = 4;
+
 fn main() {
 
     let args: Vec<String> = env::args().collect();
 
+    // eg cargo run -- kpis path/to/cache/dir scenario_uuid csv
+    if args.len() > 1 && cli::is_command(&args[1]) {
+        std::process::exit(cli::run(&args[1..]));
+    }
+
     let port: u16;
     let cache_directory: Option<String>;
 
@@ -68,8 +77,12 @@ fn main() {
         project_cache_directory_path_str
     );
              
-    let project_cache_directory_path =
-        fs::canonicalize(Path::new(&project_cache_directory_path_str)).expect("Cache directory does not exist");
+    // the cache can also be in a memory or s3 cache store, see transition_capnp_data::cache_store::open
+    let project_cache_directory_path = if transition_capnp_data::cache_store::is_local(&project_cache_directory_path_str) {
+        fs::canonicalize(Path::new(&project_cache_directory_path_str)).expect("Cache directory does not exist").to_str().unwrap().to_string()
+    } else {
+        project_cache_directory_path_str
+    };
     
     println!(
         "Port {} | Using project {}",
@@ -77,11 +90,14 @@ fn main() {
         project_shortname
     );
 
+    // networks of the scenarios routed recently, see transition_capnp_data::transit_routing::TransitNetworkCache
+    let networks = transition_capnp_data::transit_routing::TransitNetworkCache::new(MAX_LOADED_NETWORKS_COUNT);
+
     let handle_request = move |request: &Request| -> Response {
         
         // setup config:
         let mut config: serde_json::Value = json!({
-            "project_cache_directory_path": project_cache_directory_path.to_str().unwrap(),
+            "project_cache_directory_path": project_cache_directory_path,
             "custom_subdirectory_path"    : json!(null),
             "project_shortname"           : json!(project_shortname),
         });
@@ -94,6 +110,16 @@ fn main() {
             _ => {}
         }
 
+        // with a transaction_id, the files are written in the transaction (see transition_capnp_data::cache_store::transaction)
+        let config = match routers::transaction_config(config, request) {
+            Ok(config) => config,
+            Err(response) => return response
+        };
+
+        if routers::writes_cache(request) {
+            networks.clear();
+        }
+
         let object_uuid = match &request.get_param("uuid") {
             Some(uuid) => {
                 uuid.to_owned()
@@ -113,18 +139,55 @@ fn main() {
               (POST) (/nodes)       => { routers::write_collection_route("nodes", "nodes", &config, &transition_capnp_data::serialization::node_collection::write_collection, request) },
               (POST) (/node)        => { routers::write_object_route("node", "nodes", &config, &transition_capnp_data::serialization::node::write_object, request) },
               (POST) (/lines)       => { routers::write_collection_route("lines", "lines", &config, &transition_capnp_data::serialization::line_collection::write_collection, request) },
-              (POST) (/line)        => { routers::write_object_route("line", "lines", &config, &transition_capnp_data::serialization::line::write_object, request) },
+              (POST) (/line)        => { routers::write_line_object_route(&config, request) },
               (POST) (/scenarios)   => { routers::write_collection_route("scenarios", "scenarios", &config, &transition_capnp_data::serialization::scenario_collection::write_collection, request) },
               (POST) (/services)    => { routers::write_collection_route("services", "services", &config, &transition_capnp_data::serialization::service_collection::write_collection, request) },
+              (POST) (/stations)    => { routers::write_collection_route("stations", "stations", &config, &transition_capnp_data::serialization::station_collection::write_collection, request) },
+              (POST) (/zones)       => { routers::write_collection_route("zones", "zones", &config, &transition_capnp_data::serialization::zone_collection::write_collection, request) },
+              (POST) (/fares)       => { routers::write_collection_route("fares", "fares", &config, &transition_capnp_data::serialization::fare_collection::write_collection, request) },
+              (POST) (/vehicleTypes) => { routers::write_collection_route("vehicleTypes", "vehicleTypes", &config, &transition_capnp_data::serialization::vehicle_type_collection::write_collection, request) },
+              (POST) (/households)  => { routers::write_collection_route("households", "households", &config, &transition_capnp_data::serialization::household_collection::write_collection, request) },
+              (POST) (/persons)     => { routers::write_collection_route("persons", "persons", &config, &transition_capnp_data::serialization::person_collection::write_collection, request) },
+              (POST) (/odTrips)     => { routers::write_collection_route("odTrips", "odTrips", &config, &transition_capnp_data::serialization::od_trip_collection::write_collection, request) },
 
               (GET) (/agencies)    => { routers::read_collection_route("agencies", "agencies", &config, &transition_capnp_data::serialization::agency_collection::read_collection) },
-              (GET) (/paths)       => { routers::read_collection_route("paths", "paths", &config, &transition_capnp_data::serialization::path_collection::read_collection) },
+              (GET) (/paths)       => { routers::read_path_collection_route(&config, request) },
               (GET) (/nodes)       => { routers::read_collection_route("nodes", "nodes", &config, &transition_capnp_data::serialization::node_collection::read_collection) },
               (GET) (/node)        => { routers::read_object_route("node", &object_uuid, "nodes", &config, &transition_capnp_data::serialization::node::read_object) },
               (GET) (/lines)       => { routers::read_collection_route("lines", "lines", &config, &transition_capnp_data::serialization::line_collection::read_collection) },
-              (GET) (/line)        => { routers::read_object_route("line", &object_uuid, "lines", &config, &transition_capnp_data::serialization::line::read_object) },
+              (GET) (/line)        => { routers::read_line_object_route(&config, &object_uuid, request) },
               (GET) (/scenarios)   => { routers::read_collection_route("scenarios", "scenarios", &config, &transition_capnp_data::serialization::scenario_collection::read_collection) },
               (GET) (/services)    => { routers::read_collection_route("services", "services", &config, &transition_capnp_data::serialization::service_collection::read_collection) },
+              (GET) (/stations)    => { routers::read_collection_route("stations", "stations", &config, &transition_capnp_data::serialization::station_collection::read_collection) },
+              (GET) (/zones)       => { routers::read_collection_route("zones", "zones", &config, &transition_capnp_data::serialization::zone_collection::read_collection) },
+              (GET) (/fares)       => { routers::read_collection_route("fares", "fares", &config, &transition_capnp_data::serialization::fare_collection::read_collection) },
+              (GET) (/vehicleTypes) => { routers::read_collection_route("vehicleTypes", "vehicleTypes", &config, &transition_capnp_data::serialization::vehicle_type_collection::read_collection) },
+              (GET) (/households)  => { routers::read_collection_route("households", "households", &config, &transition_capnp_data::serialization::household_collection::read_collection) },
+              (GET) (/persons)     => { routers::read_collection_route("persons", "persons", &config, &transition_capnp_data::serialization::person_collection::read_collection) },
+              (GET) (/odTrips)     => { routers::read_collection_route("odTrips", "odTrips", &config, &transition_capnp_data::serialization::od_trip_collection::read_collection) },
+
+              (GET) (/nodes/near)  => { routers::nodes_near_route(&config, request) },
+              (POST) (/nodes/transferable) => { routers::transferable_nodes_route(&config, request) },
+              (GET) (/paths/analytics) => { routers::path_analytics_route(&config) },
+              (GET) (/stations/validate) => { routers::validate_stations_route(&config) },
+              (POST) (/zones/locate) => { routers::locate_in_zones_route(&config, request) },
+              (POST) (/fares/compute) => { routers::fare_route(&config, request) },
+              (GET) (/services/on_date)  => { routers::service_calendar_route("on_date", &config, request) },
+              (GET) (/services/dates)    => { routers::service_calendar_route("dates", &config, request) },
+              (GET) (/services/overlaps) => { routers::service_calendar_route("overlaps", &config, request) },
+              (GET) (/services/validate) => { routers::service_calendar_route("validate", &config, request) },
+              (GET) (/scenarios/resolve) => { routers::resolve_scenario_route(&config, &object_uuid) },
+              (GET) (/line/validate) => { routers::validate_line_timetables_route(&config, &object_uuid) },
+              (POST) (/lines/blocks) => { routers::vehicle_blocks_route(&config, request) },
+              (POST) (/route) => { routers::transit_routing_route(&config, &networks, request) },
+              (POST) (/accessibility_map) => { routers::accessibility_map_route(&config, &networks, request) },
+              (POST) (/od_matrix) => { routers::od_matrix_route(&config, &networks, request) },
+
+              (POST) (/transactions/begin)    => { routers::begin_transaction_route(&project_cache_directory_path) },
+              (POST) (/transactions/commit)   => { routers::commit_transaction_route(&project_cache_directory_path, request) },
+              (POST) (/transactions/rollback) => { routers::rollback_transaction_route(&project_cache_directory_path, request) },
+
+              (POST) (/admin/gc) => { routers::gc_cache_route(&config, request) },
 
               _ => rouille::Response::empty_404()
             )
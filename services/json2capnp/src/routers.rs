@@ -9,9 +9,7 @@
 use rouille;
 use serde_json::json;
 use std::error::Error;
-use std::fs::File;
 use std::path::Path;
-use std::fs;
 
 pub mod node_router;
 pub mod node_collection_router;
@@ -21,6 +19,24 @@ pub mod agency_collection_router;
 pub mod path_collection_router;
 pub mod service_collection_router;
 pub mod scenario_collection_router;
+pub mod node_spatial_index_router;
+pub mod transferable_nodes_router;
+pub mod path_analytics_router;
+pub mod service_calendar_router;
+pub mod scenario_resolution_router;
+pub mod timetable_validation_router;
+pub mod vehicle_blocking_router;
+pub mod transit_routing_router;
+pub mod accessibility_map_router;
+pub mod od_matrix_router;
+pub mod demand_collections_router;
+pub mod station_collection_router;
+pub mod zone_collection_router;
+pub mod fare_collection_router;
+pub mod vehicle_type_collection_router;
+pub mod cache_store_router;
+pub mod transaction_router;
+pub mod cache_gc_router;
 
 fn failed_response(cache_name: &str, error: &dyn Error) -> rouille::Response {
 
@@ -65,7 +81,7 @@ fn success_response(cache_name: &str, json_data: Option<&serde_json::Value>) ->
 
 }
 
-pub fn write_collection_route(collection_name: &str, cache_file_name: &str, config: &serde_json::Value, write_fn: &dyn Fn(&serde_json::Value, &mut std::fs::File) -> ::std::result::Result<(), capnp::Error>, request: &rouille::Request) -> rouille::Response {
+pub fn write_collection_route(collection_name: &str, cache_file_name: &str, config: &serde_json::Value, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> ::std::result::Result<(), capnp::Error>, request: &rouille::Request) -> rouille::Response {
 
     let json : serde_json::Value   = try_or_400!(rouille::input::json_input(request));
     let json_cache_directory_path  = json.get("cache_directory_path").unwrap_or(&serde_json::Value::Null);
@@ -84,43 +100,15 @@ pub fn write_collection_route(collection_name: &str, cache_file_name: &str, conf
     }
 
 
-    let directory_path = Path::new(&cache_directory_path);
-    let absolute_directory_path = String::from(directory_path.to_str().unwrap());
-    let create_directories = fs::create_dir_all(&absolute_directory_path);
-    match create_directories {
-        Ok(()) => {},
-        Err(error) => {
-            println!("{}", error);
-        }
-    }
-
-    let collection_file_path_name;
-    
-    if cache_file_name == "households" || cache_file_name == "persons" || cache_file_name == "odTrips" {
-        collection_file_path_name = format!("{}/{}.capnpbin", absolute_directory_path, cache_file_name);
-        println!("{}", collection_file_path_name);
-    } else {
-        collection_file_path_name = format!("{}/{}.capnpbin", absolute_directory_path, cache_file_name);
-    }
-    let absolute_path = Path::new(&collection_file_path_name);
-    //println!("collection_file_path_name: {}", collection_file_path_name);
-    let file = File::create(&absolute_path);
-
-    if file.is_ok()
-    {
-        match &write_fn(&json, &mut file.unwrap()) {
-            Err(error) => return failed_response(collection_name, error),
-            Ok(()) => return success_response(collection_name, None)
-        }
-    }
-    else
-    {
-        failed_response(collection_name, &file.unwrap_err())
+    // the store creates the directories of the files it writes
+    match &transition_capnp_data::cache::write_collection_file(&cache_directory_path, cache_file_name, &json, write_fn) {
+        Err(error) => failed_response(collection_name, error),
+        Ok(()) => success_response(collection_name, None)
     }
 
 }
 
-pub fn read_collection_route(collection_name: &str, cache_file_name: &str, config: &serde_json::Value, read_fn: &dyn Fn(&mut std::fs::File) -> ::std::result::Result<serde_json::Value, capnp::Error>) -> rouille::Response {
+pub fn read_collection_route(collection_name: &str, cache_file_name: &str, config: &serde_json::Value, read_fn: &dyn Fn(&mut dyn std::io::Read) -> ::std::result::Result<serde_json::Value, capnp::Error>) -> rouille::Response {
 
     let custom_subdirectory_path  = config.get("custom_subdirectory_path").unwrap_or(&serde_json::Value::Null);
 
@@ -138,37 +126,9 @@ pub fn read_collection_route(collection_name: &str, cache_file_name: &str, confi
     }
 
 
-    let directory_path = Path::new(&cache_directory_path);
-    let absolute_directory_path = String::from(directory_path.to_str().unwrap());
-    let create_directories = fs::create_dir_all(&absolute_directory_path);
-    match create_directories {
-        Ok(()) => {},
-        Err(error) => {
-            println!("{}", error);
-        }
-    }
-
-    let collection_file_path_name;
-    if cache_file_name == "households" || cache_file_name == "persons" || cache_file_name == "odTrips" {
-        collection_file_path_name = format!("{}/{}.capnpbin", absolute_directory_path, cache_file_name);
-    } else {
-        collection_file_path_name = format!("{}/{}.capnpbin", absolute_directory_path, cache_file_name);
-    }
-    let path = Path::new(&collection_file_path_name);
-    //println!("collection_file_path_name: {}", collection_file_path_name);
-    let absolute_path = String::from(path.to_str().unwrap());
-    let file = File::open(&absolute_path);
-
-    if file.is_ok()
-    {
-        match &read_fn(&mut file.unwrap()) {
-            Err(error) => return failed_response(collection_name, error),
-            Ok(json_value) => return success_response(collection_name, Some(&json_value))
-        }
-    }
-    else
-    {
-        failed_response(collection_name, &file.unwrap_err())
+    match &transition_capnp_data::cache::read_collection_file(&cache_directory_path, cache_file_name, read_fn) {
+        Err(error) => failed_response(collection_name, error),
+        Ok(json_value) => success_response(collection_name, Some(json_value))
     }
 
 }
@@ -192,17 +152,8 @@ pub fn write_object_route(collection_name: &str, subdirectory: &str, config: &se
         //println!("cache_directory_path_custom: {}", cache_directory_path);
     }
 
-    let path = Path::new(&cache_directory_path);
-    let create_directories = fs::create_dir_all(&path);
-    match create_directories {
-        Ok(()) => {},
-        Err(error) => {
-            println!("{}", error);
-        }
-    }
-    
-    let absolute_path = String::from(path.to_str().unwrap());
-    match &write_fn(&absolute_path.as_str(), &json) {
+    // the store creates the directories of the files it writes
+    match &write_fn(cache_directory_path.as_str(), &json) {
         Err(error) => return failed_response(collection_name, error),
         Ok(()) => return success_response(collection_name, None)
     }
@@ -237,3 +188,451 @@ pub fn read_object_route(object_name: &str, object_uuid: &String, subdirectory:
     }
 
 }
+
+fn cache_directory_path(config: &serde_json::Value) -> String {
+
+    let custom_subdirectory_path  = config.get("custom_subdirectory_path").unwrap_or(&serde_json::Value::Null);
+
+    if custom_subdirectory_path.is_null() // no custom path
+    {
+        config["project_cache_directory_path"].as_str().unwrap().to_string()
+    }
+    else
+    {
+        format!("{}/{}", config["project_cache_directory_path"].as_str().unwrap(), custom_subdirectory_path.as_str().unwrap())
+    }
+
+}
+
+fn f64_param(request: &rouille::Request, param_name: &str) -> Result<Option<f64>, capnp::Error> {
+    match request.get_param(param_name) {
+        Some(value) => value.parse::<f64>().map(Some).map_err(|_| capnp::Error::failed(format!("Invalid {} parameter: {}", param_name, value))),
+        None => Ok(None)
+    }
+}
+
+fn usize_param(request: &rouille::Request, param_name: &str) -> Result<Option<usize>, capnp::Error> {
+    match request.get_param(param_name) {
+        Some(value) => value.parse::<usize>().map(Some).map_err(|_| capnp::Error::failed(format!("Invalid {} parameter: {}", param_name, value))),
+        None => Ok(None)
+    }
+}
+
+fn nodes_near(config: &serde_json::Value, request: &rouille::Request) -> Result<serde_json::Value, capnp::Error> {
+
+    let nodes_json = transition_capnp_data::cache::read_collection_file(&cache_directory_path(config), "nodes", &transition_capnp_data::serialization::node_collection::read_collection)?;
+    let index = transition_capnp_data::node_spatial_index::NodeSpatialIndex::from_collection_json(&nodes_json)?;
+
+    if let Some(bbox) = request.get_param("bbox") {
+        let bounds : Vec<f64> = bbox.split(',').filter_map(|value| value.trim().parse::<f64>().ok()).collect();
+        if bounds.len() != 4 {
+            return Err(capnp::Error::failed(format!("Invalid bbox parameter, expected minLon,minLat,maxLon,maxLat: {}", bbox)));
+        }
+        return Ok(transition_capnp_data::node_spatial_index::nodes_to_json(&index.within_bbox(bounds[0], bounds[1], bounds[2], bounds[3])));
+    }
+
+    let (longitude, latitude) = match (f64_param(request, "lon")?, f64_param(request, "lat")?) {
+        (Some(longitude), Some(latitude)) => (longitude, latitude),
+        _ => return Err(capnp::Error::failed(String::from("Missing lon and lat parameters")))
+    };
+
+    if let Some(radius_meters) = f64_param(request, "radius_meters")? {
+        return Ok(transition_capnp_data::node_spatial_index::node_distances_to_json(&index.within_radius(longitude, latitude, radius_meters)));
+    }
+    let count = usize_param(request, "count")?.unwrap_or(1);
+    Ok(transition_capnp_data::node_spatial_index::node_distances_to_json(&index.nearest(longitude, latitude, count)))
+
+}
+
+pub fn nodes_near_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    match &nodes_near(config, request) {
+        Err(error) => failed_response("nodesNear", error),
+        Ok(json_value) => success_response("nodesNear", Some(json_value))
+    }
+
+}
+
+pub fn transferable_nodes_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    let default_parameters = transition_capnp_data::transferable_nodes::TransferableNodesParameters::default();
+    let parameters = transition_capnp_data::transferable_nodes::TransferableNodesParameters {
+        max_walking_distance_meters    : json["max_walking_distance_meters"].as_f64().unwrap_or(default_parameters.max_walking_distance_meters),
+        detour_factor                  : json["detour_factor"].as_f64().unwrap_or(default_parameters.detour_factor),
+        walking_speed_meters_per_second: json["walking_speed_meters_per_second"].as_f64().unwrap_or(default_parameters.walking_speed_meters_per_second),
+    };
+
+    let cache_directory_path = cache_directory_path(config);
+    let nodes_directory_path = format!("{}/nodes", cache_directory_path);
+
+    match &transition_capnp_data::transferable_nodes::write_transferable_nodes(&cache_directory_path, &nodes_directory_path, &parameters) {
+        Err(error) => failed_response("transferableNodes", error),
+        Ok(json_value) => success_response("transferableNodes", Some(json_value))
+    }
+
+}
+
+pub fn path_analytics_route(config: &serde_json::Value) -> rouille::Response {
+
+    match &transition_capnp_data::path_analytics::analyze_path_collection(&cache_directory_path(config)) {
+        Err(error) => failed_response("pathAnalytics", error),
+        Ok(json_value) => success_response("pathAnalytics", Some(json_value))
+    }
+
+}
+
+pub fn validate_stations_route(config: &serde_json::Value) -> rouille::Response {
+
+    match &transition_capnp_data::station_validation::validate_station_collection(&cache_directory_path(config)) {
+        Err(error) => failed_response("validateStations", error),
+        Ok(json_value) => success_response("validateStations", Some(json_value))
+    }
+
+}
+
+pub fn read_path_collection_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    use transition_capnp_data::serialization::path_collection::{self, GeometryEncoding, DEFAULT_POLYLINE_PRECISION};
+
+    let geometry_encoding = match request.get_param("geometry_encoding").as_deref() {
+        Some("polyline") => match request.get_param("polyline_precision").map(|precision| precision.parse::<u32>()) {
+            Some(Err(error)) => return failed_response("paths", &error),
+            Some(Ok(precision)) => GeometryEncoding::Polyline { precision },
+            None => GeometryEncoding::Polyline { precision: DEFAULT_POLYLINE_PRECISION }
+        },
+        _ => GeometryEncoding::GeoJson
+    };
+
+    read_collection_route("paths", "paths", config, &|file| path_collection::read_collection_with_geometry_encoding(file, geometry_encoding))
+
+}
+
+fn read_services(config: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    transition_capnp_data::cache::read_collection_file(&cache_directory_path(config), "services", &transition_capnp_data::serialization::service_collection::read_collection)
+}
+
+fn date_param(request: &rouille::Request, param_name: &str) -> Result<chrono::NaiveDate, capnp::Error> {
+    match request.get_param(param_name) {
+        Some(date_str) => transition_capnp_data::service_calendar::parse_date(&date_str),
+        None => Err(capnp::Error::failed(format!("Missing {} parameter", param_name)))
+    }
+}
+
+fn service_calendar_query(query: &str, config: &serde_json::Value, request: &rouille::Request) -> Result<serde_json::Value, capnp::Error> {
+
+    use transition_capnp_data::service_calendar;
+
+    let services_json = read_services(config)?;
+    if query == "validate" {
+        return Ok(service_calendar::validate_collection_json(&services_json));
+    }
+    let calendars = service_calendar::calendars_from_collection_json(&services_json)?;
+
+    match query {
+        "on_date" => {
+            let date = date_param(request, "date")?;
+            Ok(json!({ "date": service_calendar::format_date(&date), "services_ids": service_calendar::services_on_date(&calendars, &date) }))
+        },
+        "dates" => {
+            let service_uuid = request.get_param("uuid").unwrap_or_default();
+            let calendar = calendars.iter().find(|calendar| calendar.uuid == service_uuid)
+                .ok_or_else(|| capnp::Error::failed(format!("Service {} not found", service_uuid)))?;
+            let dates = calendar.dates_in_range(date_param(request, "start_date")?, date_param(request, "end_date")?)?;
+            Ok(json!({ "id": service_uuid, "dates": service_calendar::dates_to_json(&dates) }))
+        },
+        "overlaps" => service_calendar::overlapping_services(&calendars, date_param(request, "start_date")?, date_param(request, "end_date")?),
+        _ => Err(capnp::Error::failed(format!("Unknown service calendar query {}", query)))
+    }
+
+}
+
+pub fn service_calendar_route(query: &str, config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    match &service_calendar_query(query, config, request) {
+        Err(error) => failed_response("serviceCalendar", error),
+        Ok(json_value) => success_response("serviceCalendar", Some(json_value))
+    }
+
+}
+
+pub fn resolve_scenario_route(config: &serde_json::Value, scenario_uuid: &str) -> rouille::Response {
+
+    match &transition_capnp_data::scenario_resolution::resolve_scenario(&cache_directory_path(config), scenario_uuid) {
+        Err(error) => failed_response("scenario", error),
+        Ok(resolved_scenario) => success_response("scenario", Some(&resolved_scenario.to_json()))
+    }
+
+}
+
+pub fn write_line_object_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    // trips by service and trip patterns only change the files layout, not the line read back,
+    // but trRouting cannot read their trips (see LineWriteOptions)
+    let options = transition_capnp_data::serialization::line::LineWriteOptions {
+        trips_by_service: request.get_param("by_service").as_deref() == Some("true"),
+        trip_patterns: request.get_param("trip_patterns").as_deref() == Some("true"),
+    };
+    // in strict mode, the line is only written if its trips match the cached paths
+    if request.get_param("strict").as_deref() == Some("true") {
+        write_object_route("line", "lines", config, &|cache_directory_path, json| transition_capnp_data::timetable_validation::write_line_object_strict_with_options(cache_directory_path, json, &options), request)
+    } else {
+        write_object_route("line", "lines", config, &|cache_directory_path, json| transition_capnp_data::serialization::line::write_object_with_options(cache_directory_path, json, &options), request)
+    }
+
+}
+
+pub fn read_line_object_route(config: &serde_json::Value, object_uuid: &String, request: &rouille::Request) -> rouille::Response {
+
+    // only read the trips of a service or a period, or no trips at all with headers
+    let service_uuid = request.get_param("service_id");
+    let period_uuid = request.get_param("period_id");
+    let slice = if let Some(service_uuid) = &service_uuid {
+        transition_capnp_data::serialization::line::LineSlice::Service(service_uuid)
+    } else if let Some(period_uuid) = &period_uuid {
+        transition_capnp_data::serialization::line::LineSlice::Period(period_uuid)
+    } else if request.get_param("headers").as_deref() == Some("true") {
+        transition_capnp_data::serialization::line::LineSlice::Headers
+    } else {
+        transition_capnp_data::serialization::line::LineSlice::Full
+    };
+    read_object_route("line", object_uuid, "lines", config, &|object_uuid, cache_directory_path| transition_capnp_data::serialization::line::read_object_slice(object_uuid, cache_directory_path, slice))
+
+}
+
+pub fn validate_line_timetables_route(config: &serde_json::Value, line_uuid: &str) -> rouille::Response {
+
+    match &transition_capnp_data::timetable_validation::validate_cached_line_object(&cache_directory_path(config), line_uuid) {
+        Err(error) => failed_response("line", error),
+        Ok(validation) => success_response("line", Some(validation))
+    }
+
+}
+
+pub fn vehicle_blocks_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    let line_uuid = match json["line_id"].as_str() {
+        Some(line_uuid) => line_uuid,
+        None => return failed_response("vehicleBlocks", &capnp::Error::failed(String::from("Missing line_id")))
+    };
+    let parameters = transition_capnp_data::vehicle_blocking::BlockingParameters {
+        min_layover_seconds: json["min_layover_seconds"].as_i64().unwrap_or(transition_capnp_data::vehicle_blocking::BlockingParameters::default().min_layover_seconds),
+    };
+    let interlining = json["interlining"].as_bool().unwrap_or(false);
+    let write = json["write"].as_bool().unwrap_or(false);
+
+    match &transition_capnp_data::vehicle_blocking::compute_cached_blocks(&cache_directory_path(config), line_uuid, interlining, &parameters, write) {
+        Err(error) => failed_response("vehicleBlocks", error),
+        Ok(json_value) => success_response("vehicleBlocks", Some(json_value))
+    }
+
+}
+
+// Chunks of OD matrix results computed ahead of the client
+const OD_MATRIX_STREAMED_CHUNKS_COUNT: usize = 4;
+
+// POST routes which only read the cache, the other ones write to it
+const QUERY_POST_ROUTES: [&str; 5] = ["/route", "/accessibility_map", "/od_matrix", "/zones/locate", "/fares/compute"];
+
+/// Whether the request can change the cache, so the networks loaded from it
+/// may be outdated
+pub fn writes_cache(request: &rouille::Request) -> bool {
+    request.method() == "POST" && !QUERY_POST_ROUTES.contains(&request.url().as_str())
+}
+
+pub fn transit_routing_route(config: &serde_json::Value, networks: &transition_capnp_data::transit_routing::TransitNetworkCache, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    match &transition_capnp_data::transit_routing::route_query_with_networks(networks, &cache_directory_path(config), &json) {
+        Err(error) => failed_response("route", error),
+        Ok(json_value) => success_response("route", Some(json_value))
+    }
+
+}
+
+pub fn accessibility_map_route(config: &serde_json::Value, networks: &transition_capnp_data::transit_routing::TransitNetworkCache, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    match &transition_capnp_data::accessibility_map::accessibility_map_query_with_networks(networks, &cache_directory_path(config), &json) {
+        Err(error) => failed_response("accessibilityMap", error),
+        Ok(json_value) => success_response("accessibilityMap", Some(json_value))
+    }
+
+}
+
+pub fn locate_in_zones_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    match &transition_capnp_data::zone_lookup::locate_in_zones_query(&cache_directory_path(config), &json) {
+        Err(error) => failed_response("locateInZones", error),
+        Ok(json_value) => success_response("locateInZones", Some(json_value))
+    }
+
+}
+
+pub fn fare_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    match &transition_capnp_data::fare_calculator::fare_query(&cache_directory_path(config), &json) {
+        Err(error) => failed_response("fare", error),
+        Ok(json_value) => success_response("fare", Some(json_value))
+    }
+
+}
+
+// Results of a streamed response, sent by the thread computing them
+struct ChannelWriter(std::sync::mpsc::SyncSender<Vec<u8>>);
+
+impl std::io::Write for ChannelWriter {
+
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // the receiver is dropped once the client is gone, which stops the computation
+        self.0.send(buf.to_vec()).map_err(|_| std::io::Error::from(std::io::ErrorKind::BrokenPipe))?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+
+}
+
+struct ChannelReader {
+    receiver: std::sync::mpsc::Receiver<Vec<u8>>,
+    buffer: Vec<u8>,
+    position: usize,
+}
+
+impl std::io::Read for ChannelReader {
+
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        while self.position == self.buffer.len() {
+            match self.receiver.recv() {
+                Ok(buffer) => {
+                    self.buffer = buffer;
+                    self.position = 0;
+                },
+                // the computing thread is done
+                Err(_) => return Ok(0)
+            }
+        }
+        let length = buf.len().min(self.buffer.len() - self.position);
+        buf[..length].copy_from_slice(&self.buffer[self.position..self.position + length]);
+        self.position += length;
+        Ok(length)
+    }
+
+}
+
+pub fn od_matrix_route(config: &serde_json::Value, networks: &transition_capnp_data::transit_routing::TransitNetworkCache, request: &rouille::Request) -> rouille::Response {
+
+    let json : serde_json::Value = try_or_400!(rouille::input::json_input(request));
+
+    // the trips csv comes as content, the server never opens the paths of the requests
+    let query = match transition_capnp_data::od_matrix::OdMatrixQuery::from_json(&json, false) {
+        Ok(query) => query,
+        Err(error) => return failed_response("odMatrix", &error)
+    };
+    let network = match networks.get(&cache_directory_path(config), &query.scenario_uuid) {
+        Ok(network) => network,
+        Err(error) => return failed_response("odMatrix", &error)
+    };
+
+    // the results are streamed in the requested format as the chunks of trips are routed
+    let (sender, receiver) = std::sync::mpsc::sync_channel(OD_MATRIX_STREAMED_CHUNKS_COUNT);
+    std::thread::spawn(move || {
+        if let Err(error) = query.compute(&network, &mut ChannelWriter(sender)) {
+            println!("OD matrix interrupted: {}", error);
+        }
+    });
+    let content_type = match json["format"].as_str() {
+        Some("json") => "application/json; charset=utf-8",
+        _ => "text/csv; charset=utf-8"
+    };
+    rouille::Response {
+        status_code: 200,
+        headers    : vec![("Content-Type".into(), content_type.into())],
+        data       : rouille::ResponseBody::from_reader(ChannelReader { receiver, buffer: Vec::new(), position: 0 }),
+        upgrade    : None
+    }
+
+}
+
+/// Config of a request with a transaction_id parameter: the cache is the
+/// shadow directory of the transaction, so the request writes are staged in it
+pub fn transaction_config(config: serde_json::Value, request: &rouille::Request) -> Result<serde_json::Value, rouille::Response> {
+
+    let transaction_id = match request.get_param("transaction_id") {
+        Some(transaction_id) => transaction_id,
+        None => return Ok(config)
+    };
+    let mut config = config;
+    match transition_capnp_data::cache_store::transaction::transaction_location(config["project_cache_directory_path"].as_str().unwrap(), &transaction_id) {
+        Err(error) => Err(failed_response("transaction", &error)),
+        Ok(location) => {
+            config["project_cache_directory_path"] = json!(location);
+            Ok(config)
+        }
+    }
+
+}
+
+pub fn begin_transaction_route(project_cache_directory_path: &str) -> rouille::Response {
+
+    match &transition_capnp_data::cache_store::transaction::begin(project_cache_directory_path) {
+        Err(error) => failed_response("transaction", error),
+        Ok(transaction_id) => success_response("transaction", Some(&json!({ "transaction_id": transaction_id })))
+    }
+
+}
+
+pub fn commit_transaction_route(project_cache_directory_path: &str, request: &rouille::Request) -> rouille::Response {
+
+    let transaction_id = request.get_param("transaction_id").unwrap_or_default();
+    match &transition_capnp_data::cache_store::transaction::commit(project_cache_directory_path, &transaction_id) {
+        Err(error) => failed_response("transaction", error),
+        Ok(files_count) => success_response("transaction", Some(&json!({ "transaction_id": transaction_id, "files_count": files_count })))
+    }
+
+}
+
+pub fn rollback_transaction_route(project_cache_directory_path: &str, request: &rouille::Request) -> rouille::Response {
+
+    let transaction_id = request.get_param("transaction_id").unwrap_or_default();
+    match &transition_capnp_data::cache_store::transaction::rollback(project_cache_directory_path, &transaction_id) {
+        Err(error) => failed_response("transaction", error),
+        Ok(_) => success_response("transaction", Some(&json!({ "transaction_id": transaction_id })))
+    }
+
+}
+
+pub fn gc_cache_route(config: &serde_json::Value, request: &rouille::Request) -> rouille::Response {
+
+    // a dry run unless delete=true, the files to remove are only reported
+    let mut options = transition_capnp_data::cache_gc::CacheGcOptions {
+        dry_run: request.get_param("delete").as_deref() != Some("true"),
+        remove_subdirectories_of_unknown_age: request.get_param("remove_unknown_age").as_deref() == Some("true"),
+        ..Default::default()
+    };
+    match f64_param(request, "max_age_days") {
+        Err(error) => return failed_response("gc", &error),
+        Ok(Some(max_age_days)) => options.max_age_days = max_age_days,
+        Ok(None) => ()
+    }
+    if let Some(keep) = request.get_param("keep") {
+        options.keep_subdirectories = keep.split(',').map(|subdirectory| subdirectory.trim().to_string()).filter(|subdirectory| !subdirectory.is_empty()).collect();
+    }
+
+    match &transition_capnp_data::cache_gc::gc_cache(&cache_directory_path(config), &options) {
+        Err(error) => failed_response("gc", error),
+        Ok(report) => success_response("gc", Some(&report.to_json()))
+    }
+
+}
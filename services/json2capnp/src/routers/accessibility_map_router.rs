@@ -0,0 +1,177 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use std::sync::Arc;
+    use transition_capnp_data::transit_routing::TransitNetworkCache;
+    use pretty_assertions::{assert_eq};
+
+    fn trip(trip_uuid: &str, path_uuid: &str, departure_times: serde_json::Value, arrival_times: serde_json::Value) -> serde_json::Value {
+        let nodes_count = departure_times.as_array().unwrap().len();
+        let mut nodes_can_board = vec![true; nodes_count];
+        nodes_can_board[nodes_count - 1] = false;
+        let mut nodes_can_unboard = vec![true; nodes_count];
+        nodes_can_unboard[0] = false;
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": departure_times[0],
+            "arrival_time_seconds": arrival_times[nodes_count - 1],
+            "node_arrival_times_seconds": arrival_times,
+            "node_departure_times_seconds": departure_times,
+            "nodes_can_board": nodes_can_board,
+            "nodes_can_unboard": nodes_can_unboard
+        })
+    }
+
+    fn accessibility_map_response(config: &serde_json::Value, networks: &TransitNetworkCache, query: serde_json::Value) -> serde_json::Value {
+        let request = Request::fake_http(
+            "POST",
+            "/accessibility_map",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            query.to_string().into_bytes(),
+        );
+        let response = routers::accessibility_map_route(config, networks, &request);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    #[test]
+    fn accessibility_map() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "accessibility_map",
+            "project_shortname"           : "test"
+        });
+        let networks = TransitNetworkCache::new(2);
+
+        let cache_directory_path = Path::new("test").join("accessibility_map");
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+
+        // the line goes from a to c, nodes are ~1560m apart
+        write("nodes", &transition_capnp_data::serialization::node_collection::write_collection, json!({
+            "nodes": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "Point", "coordinates": [-73.60, 45.50] }, "properties": { "id": "a", "integer_id": 1 } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "Point", "coordinates": [-73.58, 45.50] }, "properties": { "id": "b", "integer_id": 2 } },
+                    { "type": "Feature", "id": 3, "geometry": { "type": "Point", "coordinates": [-73.56, 45.50] }, "properties": { "id": "c", "integer_id": 3 } }
+                ]
+            }
+        }));
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.60, 45.50], [-73.58, 45.50], [-73.56, 45.50]] }, "properties": { "id": "p1", "integer_id": 1, "line_id": "line1", "nodes": ["a", "b", "c"], "segments": [0, 1] } }
+                ]
+            }
+        }));
+        write("scenarios", &transition_capnp_data::serialization::scenario_collection::write_collection, json!({
+            "scenarios": [{ "id": "scenario", "services": ["service"] }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "service" }]
+        }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({
+            "lines": [{ "id": "line1", "agency_id": "agency", "mode": "bus" }]
+        }));
+        transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &json!({
+            "line": {
+                "id": "line1",
+                "agency_id": "agency",
+                "mode": "bus",
+                "scheduleByServiceId": {
+                    "service": {
+                        "id": "line1_schedule",
+                        "service_id": "service",
+                        "periods": [{ "period_shortname": "all_day", "start_at_hour": 6, "end_at_hour": 12, "trips": [
+                            trip("t1", "p1", json!([28800, 29160, null]), json!([null, 29100, 29400])),
+                            trip("t2", "p1", json!([30600, 30960, null]), json!([null, 30900, 31200]))
+                        ] }]
+                    }
+                }
+            }
+        })).unwrap();
+
+        // leaving at 28620 catches t1 after the min waiting time
+        let json_response = accessibility_map_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "location": "a",
+            "departure_time_range_seconds": [28000, 31000],
+            "durations_seconds": [1200, 600]
+        }));
+        assert_eq!(json_response["status"], "success");
+        let map = &json_response["data"];
+        assert_eq!(map["nodes"], json!([
+            { "id": "a", "travel_time_seconds": 0, "departure_time_seconds": 28000 },
+            { "id": "b", "travel_time_seconds": 480, "departure_time_seconds": 28620 },
+            { "id": "c", "travel_time_seconds": 780, "departure_time_seconds": 28620 }
+        ]));
+        let features = map["polygons"]["features"].as_array().unwrap();
+        assert_eq!(features.len(), 2);
+
+        // within 10 minutes, the circles around a and b are disjoint
+        assert_eq!(features[0]["properties"]["duration_seconds"], 600);
+        assert_eq!(features[0]["properties"]["nodes_count"], 2);
+        assert_eq!(features[0]["geometry"]["type"], "MultiPolygon");
+        assert_eq!(features[0]["geometry"]["coordinates"].as_array().unwrap().len(), 2);
+        let walking_speed = 1.3888888888;
+        let expected_area = std::f64::consts::PI * ((600.0 * walking_speed) * (600.0 * walking_speed) + (120.0 * walking_speed) * (120.0 * walking_speed));
+        let area = features[0]["properties"]["area_square_meters"].as_f64().unwrap();
+        assert!((area - expected_area).abs() / expected_area < 0.01, "area {} is not close to {}", area, expected_area);
+
+        // within 20 minutes, the circles around a, b and c are merged
+        assert_eq!(features[1]["properties"]["duration_seconds"], 1200);
+        assert_eq!(features[1]["properties"]["nodes_count"], 3);
+        assert_eq!(features[1]["geometry"]["coordinates"].as_array().unwrap().len(), 1);
+
+        // leaving at 28700 is too late for t1, many locations share the network
+        let json_response = accessibility_map_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "locations": ["a", [-73.56, 45.50]],
+            "departure_time_seconds": 28700,
+            "durations_seconds": [1800]
+        }));
+        let maps = json_response["data"]["maps"].as_array().unwrap();
+        assert_eq!(maps.len(), 2);
+        assert_eq!(maps[0]["nodes"][1], json!({ "id": "b", "travel_time_seconds": 30900 - 28700, "departure_time_seconds": 28700 }));
+        assert_eq!(maps[0]["polygons"]["features"][0]["properties"]["nodes_count"], 1);
+        assert_eq!(maps[1]["location"], json!([-73.56, 45.50]));
+        assert_eq!(maps[1]["nodes"], json!([{ "id": "c", "travel_time_seconds": 0, "departure_time_seconds": 28700 }]));
+
+        let json_response = accessibility_map_response(&config, &networks, json!({ "scenario_id": "scenario", "location": "a", "departure_time_seconds": 28700, "durations_seconds": [0] }));
+        assert_eq!(json_response["status"], "fail");
+
+        // the network stays loaded for the next queries
+        let mapped_cache_directory_path = format!("{}/accessibility_map", config["project_cache_directory_path"].as_str().unwrap());
+        let network = networks.get(&mapped_cache_directory_path, "scenario").unwrap();
+        accessibility_map_response(&config, &networks, json!({ "scenario_id": "scenario", "location": "a", "departure_time_seconds": 28700 }));
+        assert!(Arc::ptr_eq(&network, &networks.get(&mapped_cache_directory_path, "scenario").unwrap()));
+
+    }
+
+}
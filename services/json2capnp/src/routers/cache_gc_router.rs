@@ -0,0 +1,138 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+    use transition_capnp_data::cache_store;
+
+    fn post(url: &str, json: &serde_json::Value) -> Request {
+        Request::fake_http(
+            "POST",
+            url,
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            json.to_string().as_bytes().to_vec(),
+        )
+    }
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn line(line_uuid: &str) -> serde_json::Value {
+        json!({
+            "line": {
+                "id": line_uuid,
+                "agency_id": "agency",
+                "mode": "bus",
+                "shortname": "1",
+                "scheduleByServiceId": {
+                    "weekday": {
+                        "id": "weekday_schedule",
+                        "service_id": "weekday",
+                        "periods": [
+                            { "id": "am_peak", "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": [] }
+                        ]
+                    }
+                }
+            }
+        })
+    }
+
+    fn gc(config: &serde_json::Value, params: &str) -> serde_json::Value {
+        let gc_json = response_json(routers::gc_cache_route(config, &Request::fake_http("POST", format!("/admin/gc?{}", params), vec![], vec![])));
+        assert_eq!(gc_json["status"], "success");
+        gc_json["data"].clone()
+    }
+
+    #[test]
+    fn cache_gc() {
+
+        let cache_directory_path = Path::new("test").join("cache_gc");
+        let _ = fs::remove_dir_all(&cache_directory_path);
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let project_cache_directory_path = cache_directory_path.to_str().unwrap();
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": project_cache_directory_path,
+            "custom_subdirectory_path"    : null,
+            "project_shortname"           : "test"
+        });
+
+        let lines = json!({ "lines": [{ "id": "line", "agency_id": "agency", "mode": "bus" }] });
+        let response = routers::write_collection_route("lines", "lines", &config, &transition_capnp_data::serialization::line_collection::write_collection, &post("/lines", &lines));
+        assert_eq!(response_json(response)["status"], "success");
+        for line_uuid in ["line", "deleted_line"] {
+            let response = routers::write_line_object_route(&config, &post("/line?by_service=true", &line(line_uuid)));
+            assert_eq!(response_json(response)["status"], "success");
+        }
+        let store = cache_store::open(project_cache_directory_path).unwrap();
+        // there is no nodes collection to tell which node files are orphans
+        store.put("nodes/node_deleted.capnpbin", b"node").unwrap();
+        store.put("simulation/agencies.capnpbin", b"agencies").unwrap();
+        store.put("kept/agencies.capnpbin", b"agencies").unwrap();
+        let transaction_id = cache_store::transaction::begin(project_cache_directory_path).unwrap();
+
+        let report = gc(&config, "");
+        assert_eq!(report["dry_run"], true);
+        assert_eq!(report["orphan_files"], json!(["lines/line_deleted_line.capnpbin", "lines/line_deleted_line_trips_weekday.capnpbin"]));
+        assert_eq!(report["stale_subdirectories"], json!([]));
+        assert_eq!(report["stale_transactions"], json!([]));
+
+        let report = gc(&config, "max_age_days=0&keep=kept");
+        assert_eq!(report["stale_subdirectories"], json!(["simulation"]));
+        assert_eq!(report["stale_transactions"], json!([transaction_id]));
+        assert_eq!(report["files_count"], 4);
+        assert!(store.get("simulation/agencies.capnpbin").unwrap().is_some());
+
+        let report = gc(&config, "max_age_days=0&keep=kept&delete=true");
+        assert_eq!(report["dry_run"], false);
+        assert_eq!(report["files_count"], 4);
+        assert_eq!(store.list("").unwrap(), vec![
+            "kept/agencies.capnpbin",
+            "lines.capnpbin",
+            "lines/line_line.capnpbin",
+            "lines/line_line_trips_weekday.capnpbin",
+            "nodes/node_deleted.capnpbin"
+        ]);
+        assert!(!cache_directory_path.join("simulation").exists());
+        assert!(cache_store::transaction::commit(project_cache_directory_path, &transaction_id).is_err());
+
+        assert_eq!(gc(&config, "max_age_days=0&keep=kept")["files_count"], 0);
+        assert_eq!(response_json(routers::gc_cache_route(&config, &Request::fake_http("POST", "/admin/gc?max_age_days=never", vec![], vec![])))["status"], "fail");
+
+        // the age of the subdirectories of a memory cache is unknown, they are only removed on demand
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": "memory://cache_gc",
+            "custom_subdirectory_path"    : null,
+            "project_shortname"           : "test"
+        });
+        let store = cache_store::open("memory://cache_gc").unwrap();
+        store.put("simulation/agencies.capnpbin", b"agencies").unwrap();
+        store.put("kept/agencies.capnpbin", b"agencies").unwrap();
+        let report = gc(&config, "keep=kept&delete=true");
+        assert_eq!(report["stale_subdirectories"], json!([]));
+        assert!(store.get("simulation/agencies.capnpbin").unwrap().is_some());
+        let report = gc(&config, "keep=kept&delete=true&remove_unknown_age=true");
+        assert_eq!(report["stale_subdirectories"], json!(["simulation"]));
+        assert_eq!(store.list("").unwrap(), vec!["kept/agencies.capnpbin"]);
+
+    }
+}
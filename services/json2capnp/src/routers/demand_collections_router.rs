@@ -0,0 +1,152 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn write_and_read(
+        config: &serde_json::Value,
+        collection_name: &str,
+        data: &serde_json::Value,
+        write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> ::std::result::Result<(), capnp::Error>,
+        read_fn: &dyn Fn(&mut dyn std::io::Read) -> ::std::result::Result<serde_json::Value, capnp::Error>,
+    ) -> serde_json::Value {
+        // the written collection goes to the directory of the request, read from the config one
+        let mut data = data.clone();
+        data["cache_directory_path"] = json!("demand");
+        let request = Request::fake_http(
+            "POST",
+            format!("/{}", collection_name),
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.to_string().into_bytes(),
+        );
+        let response = routers::write_collection_route(collection_name, collection_name, config, write_fn, &request);
+        assert_eq!(response.status_code, 200);
+
+        let response = routers::read_collection_route(collection_name, collection_name, config, read_fn);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        assert_eq!(response.status_code, 200);
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+        json_response["data"][collection_name].clone()
+    }
+
+    #[test]
+    fn demand_collections() {
+
+        fs::create_dir_all("test/demand").unwrap();
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "demand",
+            "project_shortname"           : "test"
+        });
+
+        let households = json!({
+            "households": [
+                {
+                    "id": "h1",
+                    "integer_id": 1,
+                    "internal_id": "100",
+                    "data_source_id": "ds1",
+                    "size": 3,
+                    "car_number": 1,
+                    "income_level": "medium",
+                    "category": null,
+                    "home_geography": { "type": "Point", "coordinates": [-73.6, 45.5] },
+                    "expansion_factor": 24.625,
+                    "data": { "foo": "bar" }
+                },
+                {
+                    "id": "h2"
+                }
+            ]
+        });
+        let households_read = write_and_read(&config, "households", &households, &transition_capnp_data::serialization::household_collection::write_collection, &transition_capnp_data::serialization::household_collection::read_collection);
+        assert_eq!(households_read[0], households["households"][0]);
+        assert_eq!(households_read[1], json!({
+            "id": "h2",
+            "integer_id": 0,
+            "internal_id": null,
+            "data_source_id": null,
+            "size": null,
+            "car_number": null,
+            "income_level": null,
+            "category": null,
+            "home_geography": null,
+            "expansion_factor": null,
+            "data": {}
+        }));
+
+        let persons = json!({
+            "persons": [
+                {
+                    "id": "p1",
+                    "integer_id": 1,
+                    "internal_id": "100-1",
+                    "data_source_id": "ds1",
+                    "household_id": "h1",
+                    "age": 34,
+                    "gender": "female",
+                    "occupation": "fullTimeWorker",
+                    "driving_license_owner": true,
+                    "transit_pass_owner": false,
+                    "expansion_factor": 30.5,
+                    "data": {}
+                }
+            ]
+        });
+        let persons_read = write_and_read(&config, "persons", &persons, &transition_capnp_data::serialization::person_collection::write_collection, &transition_capnp_data::serialization::person_collection::read_collection);
+        assert_eq!(persons_read, persons["persons"]);
+
+        let od_trips = json!({
+            "odTrips": [
+                {
+                    "id": "t1",
+                    "integer_id": 1,
+                    "internal_id": null,
+                    "data_source_id": "ds1",
+                    "household_id": "h1",
+                    "person_id": "p1",
+                    "origin_geography": { "type": "Point", "coordinates": [-73.6, 45.5] },
+                    "destination_geography": { "type": "Point", "coordinates": [-73.55, 45.51] },
+                    "departure_time_seconds": 28800,
+                    "arrival_time_seconds": null,
+                    "mode": "transit",
+                    "origin_activity": "home",
+                    "destination_activity": "work",
+                    "expansion_factor": 30.5,
+                    "data": {}
+                }
+            ]
+        });
+        let od_trips_read = write_and_read(&config, "odTrips", &od_trips, &transition_capnp_data::serialization::od_trip_collection::write_collection, &transition_capnp_data::serialization::od_trip_collection::read_collection);
+        assert_eq!(od_trips_read, od_trips["odTrips"]);
+
+        // OD trips need an origin and a destination
+        let invalid_od_trips = json!({ "cache_directory_path": "demand", "odTrips": [{ "id": "t2", "origin_geography": { "type": "Point", "coordinates": [-73.6, 45.5] } }] });
+        let request = Request::fake_http("POST", "/odTrips", vec![("Content-Type".to_owned(), "application/json; charset=utf-8".to_owned())], invalid_od_trips.to_string().into_bytes());
+        let response = routers::write_collection_route("odTrips", "odTrips", &config, &transition_capnp_data::serialization::od_trip_collection::write_collection, &request);
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+        assert_eq!(json_response["status"], "fail");
+
+    }
+}
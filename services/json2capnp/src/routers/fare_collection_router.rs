@@ -0,0 +1,145 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn json_request(url: &str, data: &serde_json::Value) -> Request {
+        Request::fake_http(
+            "POST",
+            url,
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.to_string().into_bytes(),
+        )
+    }
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn leg(line_uuid: &str, alighting_node_uuid: &str, boarding_time_seconds: i64, alighting_time_seconds: i64) -> serde_json::Value {
+        json!({
+            "line_id": line_uuid,
+            "boarding_node_id": "downtown",
+            "alighting_node_id": alighting_node_uuid,
+            "boarding_time_seconds": boarding_time_seconds,
+            "alighting_time_seconds": alighting_time_seconds
+        })
+    }
+
+    #[test]
+    fn fare_collection() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "fares",
+            "project_shortname"           : "test"
+        });
+
+        let fares = json!({
+            "cache_directory_path": "fares",
+            "fares": {
+                "fare_products": [
+                    { "id": "bus_fare", "name": "Bus", "amount": 3.5, "currency": "CAD", "rider_category": null, "data": {} },
+                    { "id": "metro_fare", "name": "Metro", "amount": 3.75, "currency": "CAD", "rider_category": null, "data": {} },
+                    { "id": "airport_fare", "name": "Airport", "amount": 11.0, "currency": "CAD", "rider_category": null, "data": {} },
+                    { "id": "transfer_fee", "name": null, "amount": 0.5, "currency": "CAD", "rider_category": "adult", "data": { "foo": "bar" } },
+                    { "id": "rtl_fare", "name": "RTL", "amount": 4.0, "currency": "CAD", "rider_category": null, "data": {} },
+                    { "id": "rtl_pass", "name": "RTL pass", "amount": 6.0, "currency": "CAD", "rider_category": null, "data": {} }
+                ],
+                "fare_areas": [
+                    { "id": "airport", "name": "Airport", "nodes": ["airport_stop"] }
+                ],
+                "fare_leg_rules": [
+                    { "id": "local", "leg_group_id": "local", "agency_id": "stm", "line_id": null, "from_area_id": null, "to_area_id": null, "fare_product_id": "bus_fare", "rule_priority": 0 },
+                    { "id": "metro", "leg_group_id": "local", "agency_id": null, "line_id": "metro_green", "from_area_id": null, "to_area_id": null, "fare_product_id": "metro_fare", "rule_priority": 0 },
+                    { "id": "airport", "leg_group_id": "airport", "agency_id": null, "line_id": "bus_747", "from_area_id": null, "to_area_id": "airport", "fare_product_id": "airport_fare", "rule_priority": 1 },
+                    { "id": "rtl", "leg_group_id": "rtl", "agency_id": "rtl", "line_id": null, "from_area_id": null, "to_area_id": null, "fare_product_id": "rtl_fare", "rule_priority": 0 }
+                ],
+                "fare_transfer_rules": [
+                    { "from_leg_group_id": "local", "to_leg_group_id": "local", "transfer_count": 2, "duration_limit_seconds": 7200, "duration_limit_type": 1, "fare_transfer_type": 0, "fare_product_id": null },
+                    { "from_leg_group_id": "local", "to_leg_group_id": "airport", "transfer_count": null, "duration_limit_seconds": null, "duration_limit_type": 0, "fare_transfer_type": 1, "fare_product_id": "transfer_fee" },
+                    { "from_leg_group_id": "rtl", "to_leg_group_id": "rtl", "transfer_count": null, "duration_limit_seconds": null, "duration_limit_type": 0, "fare_transfer_type": 2, "fare_product_id": "rtl_pass" }
+                ]
+            }
+        });
+        let response = routers::write_collection_route("fares", "fares", &config, &transition_capnp_data::serialization::fare_collection::write_collection, &json_request("/fares", &fares));
+        assert_eq!(response_json(response)["status"], "success");
+
+        let response = routers::read_collection_route("fares", "fares", &config, &transition_capnp_data::serialization::fare_collection::read_collection);
+        assert_eq!(response_json(response)["data"]["fares"], fares["fares"]);
+
+        let lines = json!({
+            "lines": [
+                { "id": "bus_10", "agency_id": "stm", "mode": "bus" },
+                { "id": "metro_green", "agency_id": "stm", "mode": "metro" },
+                { "id": "bus_747", "agency_id": "stm", "mode": "bus" },
+                { "id": "regional_bus", "agency_id": "exo", "mode": "bus" },
+                { "id": "rtl_8", "agency_id": "rtl", "mode": "bus" },
+                { "id": "rtl_44", "agency_id": "rtl", "mode": "bus" }
+            ]
+        });
+        let mut file = fs::File::create(Path::new("test").join("fares").join("lines.capnpbin")).unwrap();
+        transition_capnp_data::serialization::line_collection::write_collection(&lines, &mut file).unwrap();
+
+        let query = json!({
+            "journeys": [
+                // free transfer to the metro
+                { "legs": [leg("bus_10", "station", 28800, 29400), leg("metro_green", "downtown", 29700, 30600)] },
+                // too late for the transfer
+                { "legs": [leg("bus_10", "station", 28800, 29400), leg("metro_green", "downtown", 36100, 36900)] },
+                // transfer fee and airport fare
+                { "legs": [leg("bus_10", "station", 28800, 29400), leg("bus_747", "airport_stop", 29700, 32400)] },
+                // only two transfers in the same fare
+                { "legs": [leg("bus_10", "a", 28800, 28900), leg("metro_green", "b", 29000, 29100), leg("bus_10", "c", 29200, 29300), leg("metro_green", "d", 29400, 29500)] },
+                // walking only
+                { "legs": [] },
+                // the pass replaces all the fares paid since the first leg
+                { "legs": [leg("rtl_8", "a", 28800, 28900), leg("rtl_44", "b", 29000, 29100), leg("rtl_8", "c", 29200, 29300)] }
+            ]
+        });
+        let json_response = response_json(routers::fare_route(&config, &json_request("/fares/compute", &query)));
+        assert_eq!(json_response["status"], "success");
+        let fares = json_response["data"]["fares"].as_array().unwrap();
+        assert_eq!(fares[0], json!({
+            "total_amount": 3.5,
+            "currency": "CAD",
+            "legs": [
+                { "line_id": "bus_10", "fare_product_id": "bus_fare", "leg_group_id": "local", "transfer": false, "amount": 3.5 },
+                { "line_id": "metro_green", "fare_product_id": "metro_fare", "leg_group_id": "local", "transfer": true, "amount": 0.0 }
+            ]
+        }));
+        assert_eq!(fares[1]["total_amount"], 7.25);
+        assert_eq!(fares[2]["total_amount"], 15.0);
+        assert_eq!(fares[2]["legs"][1]["fare_product_id"], "airport_fare");
+        assert_eq!(fares[3]["total_amount"], 7.25);
+        assert_eq!(fares[4], json!({ "total_amount": 0.0, "currency": null, "legs": [] }));
+        assert_eq!(fares[5]["total_amount"], 6.0);
+        let amounts : Vec<&serde_json::Value> = fares[5]["legs"].as_array().unwrap().iter().map(|leg| &leg["amount"]).collect();
+        assert_eq!(amounts, vec![&json!(4.0), &json!(2.0), &json!(0.0)]);
+
+        let query = json!({ "legs": [leg("regional_bus", "station", 28800, 29400)] });
+        let json_response = response_json(routers::fare_route(&config, &json_request("/fares/compute", &query)));
+        assert_eq!(json_response["status"], "fail");
+        assert_eq!(json_response["error"], "Failed: No fare leg rule matches leg 0 on line regional_bus");
+
+    }
+}
@@ -102,6 +102,7 @@ mod tests {
                                             "nodes_can_board": [true,true,true,true,true,true,true,true,true,true,true,false],
                                             "nodes_can_unboard": [false,true,true,true,true,true,true,true,true,true,true,true],
                                             "block_id": null,
+                                            "vehicle_type_id": "articulated_bus",
                                             "total_capacity": 50,
                                             "seated_capacity": 20
                                         }
@@ -118,6 +119,7 @@ mod tests {
                                     "end_at_hour": 9.5,
                                     "interval_seconds": 900,
                                     "number_of_units": null,
+                                    "vehicle_type_id": "standard_bus",
                                     "trips": [
                                         {
                                             "id":"a1f5fbc9-f692-49f3-a00f-430e5075c25f",
@@ -209,6 +211,7 @@ mod tests {
                                     "end_at_hour": 6.0,
                                     "interval_seconds": null,
                                     "number_of_units": 2,
+                                    "vehicle_type_id": null,
                                     "is_frozen": null,
                                     "trips": [
                                         {
@@ -222,6 +225,7 @@ mod tests {
                                             "nodes_can_board": [true,true,true,true,true,false,true,true,true,true,true,false],
                                             "nodes_can_unboard": [false,true,true,true,true,true,true,false,true,true,true,true],
                                             "block_id": null,
+                                            "vehicle_type_id": null,
                                             "total_capacity": 50,
                                             "seated_capacity": 20,
                                             "is_frozen": null
@@ -237,6 +241,7 @@ mod tests {
                                             "nodes_can_board": [true,true,true,true,true,true,true,true,true,true,true,false],
                                             "nodes_can_unboard": [false,true,true,true,true,true,true,true,true,true,true,true],
                                             "block_id": null,
+                                            "vehicle_type_id": "articulated_bus",
                                             "total_capacity": 50,
                                             "seated_capacity": 20,
                                             "is_frozen": null
@@ -255,6 +260,7 @@ mod tests {
                                     "end_at_hour": 9.5,
                                     "interval_seconds": 900,
                                     "number_of_units": null,
+                                    "vehicle_type_id": "standard_bus",
                                     "is_frozen": null,
                                     "trips": [
                                         {
@@ -268,6 +274,7 @@ mod tests {
                                             "nodes_can_board": [true,true,true,true,true,true,true,true,true,true,true,false],
                                             "nodes_can_unboard": [false,true,true,true,true,true,true,true,true,true,true,true],
                                             "block_id": null,
+                                            "vehicle_type_id": null,
                                             "total_capacity": 50,
                                             "seated_capacity":20,
                                             "is_frozen": null
@@ -286,6 +293,7 @@ mod tests {
                                     "end_at_hour": 28.0,
                                     "interval_seconds": null,
                                     "number_of_units": null,
+                                    "vehicle_type_id": null,
                                     "trips": [],
                                     "is_frozen": null
                                 }
@@ -353,4 +361,167 @@ mod tests {
         assert!(json_response["data"].is_null());
 
     }
+
+    fn line_response(config: &serde_json::Value, query: &str) -> serde_json::Value {
+        let request = Request::fake_http("GET", format!("/line?{}", query), vec![], vec![]);
+        let response = routers::read_line_object_route(config, &String::from("line"), &request);
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn write_line(config: &serde_json::Value, query: &str, line: &serde_json::Value) {
+        let request = Request::fake_http(
+            "POST",
+            format!("/line?{}", query),
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            line.to_string().as_bytes().to_vec(),
+        );
+        assert_eq!(routers::write_line_object_route(config, &request).status_code, 200);
+    }
+
+    #[test]
+    fn line_by_service() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "lines_by_service",
+            "project_shortname"           : "test"
+        });
+        let lines_directory_path = Path::new("test").join("lines_by_service");
+
+        let trip = |trip_uuid: &str, departure_time_seconds: i64| json!({
+            "id": trip_uuid,
+            "path_id": "path",
+            "departure_time_seconds": departure_time_seconds,
+            "arrival_time_seconds": departure_time_seconds + 600,
+            "node_arrival_times_seconds": [null, departure_time_seconds + 600],
+            "node_departure_times_seconds": [departure_time_seconds, null],
+            "nodes_can_board": [true, false],
+            "nodes_can_unboard": [false, true]
+        });
+        let line = json!({
+            "cache_directory_path": "lines_by_service",
+            "line": {
+                "id": "line",
+                "agency_id": "agency",
+                "mode": "bus",
+                "shortname": "1",
+                "scheduleByServiceId": {
+                    "weekday": {
+                        "id": "weekday_schedule",
+                        "service_id": "weekday",
+                        "periods": [
+                            { "id": "am_peak", "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": [trip("t1", 21600), trip("t2", 22500)] },
+                            { "id": "pm_peak", "period_shortname": "pm_peak", "start_at_hour": 15, "end_at_hour": 18, "trips": [trip("t3", 54000)] }
+                        ]
+                    },
+                    "weekend": {
+                        "id": "weekend_schedule",
+                        "service_id": "weekend",
+                        "periods": [
+                            { "id": "all_day", "period_shortname": "all_day", "start_at_hour": 8, "end_at_hour": 20, "trips": [trip("t4", 28800)] }
+                        ]
+                    }
+                }
+            }
+        });
+
+        // the complete line is the same with both layouts
+        write_line(&config, "", &line);
+        let complete_line = line_response(&config, "id=line")["data"]["line"].clone();
+        assert_eq!(complete_line["scheduleByServiceId"]["weekday"]["periods"][0]["trips"][1]["id"], "t2");
+
+        write_line(&config, "by_service=true", &line);
+        assert!(lines_directory_path.join("line_line_trips_weekday.capnpbin").exists());
+        assert!(lines_directory_path.join("line_line_trips_weekend.capnpbin").exists());
+        assert_eq!(line_response(&config, "id=line")["data"]["line"], complete_line);
+
+        let headers = line_response(&config, "id=line&headers=true");
+        let weekday_periods = headers["data"]["line"]["scheduleByServiceId"]["weekday"]["periods"].as_array().unwrap();
+        assert_eq!(weekday_periods.len(), 2);
+        assert!(weekday_periods.iter().all(|period| period.get("trips").is_none()));
+
+        let weekend = line_response(&config, "id=line&service_id=weekend");
+        let schedules = weekend["data"]["line"]["scheduleByServiceId"].as_object().unwrap();
+        assert_eq!(schedules.keys().collect::<Vec<&String>>(), vec!["weekend"]);
+        assert_eq!(schedules["weekend"], complete_line["scheduleByServiceId"]["weekend"]);
+
+        let pm_peak = line_response(&config, "id=line&period_id=pm_peak");
+        let schedules = pm_peak["data"]["line"]["scheduleByServiceId"].as_object().unwrap();
+        assert_eq!(schedules.keys().collect::<Vec<&String>>(), vec!["weekday"]);
+        assert_eq!(schedules["weekday"]["periods"], json!([complete_line["scheduleByServiceId"]["weekday"]["periods"][1]]));
+
+        // writing the complete line again removes the trips files
+        write_line(&config, "", &line);
+        assert!(!lines_directory_path.join("line_line_trips_weekday.capnpbin").exists());
+        assert_eq!(line_response(&config, "id=line")["data"]["line"], complete_line);
+
+    }
+
+    #[test]
+    fn line_trip_patterns() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "lines_trip_patterns",
+            "project_shortname"           : "test"
+        });
+        let line_file_path = Path::new("test").join("lines_trip_patterns").join("line_line.capnpbin");
+
+        let trip = |trip_uuid: &str, departure_time_seconds: i64| json!({
+            "id": trip_uuid,
+            "path_id": "path",
+            "departure_time_seconds": departure_time_seconds,
+            "arrival_time_seconds": departure_time_seconds + 900,
+            "node_arrival_times_seconds": [null, departure_time_seconds + 300, departure_time_seconds + 600, departure_time_seconds + 900],
+            "node_departure_times_seconds": [departure_time_seconds, departure_time_seconds + 330, departure_time_seconds + 600, null],
+            "nodes_can_board": [true, true, true, false],
+            "nodes_can_unboard": [false, true, true, true]
+        });
+        // a slower trip has its own pattern, a trip with a null flag keeps its node times
+        let mut slow_trip = trip("slow", 30000);
+        slow_trip["node_arrival_times_seconds"][1] = json!(30400);
+        let mut trip_with_null_flag = trip("null_flag", 32000);
+        trip_with_null_flag["nodes_can_board"][2] = json!(null);
+        let am_trips: Vec<serde_json::Value> = (0..20).map(|i| trip(&format!("am{}", i), 21600 + i * 600)).collect();
+        let line = json!({
+            "cache_directory_path": "lines_trip_patterns",
+            "line": {
+                "id": "line",
+                "agency_id": "agency",
+                "mode": "bus",
+                "shortname": "1",
+                "scheduleByServiceId": {
+                    "weekday": {
+                        "id": "weekday_schedule",
+                        "service_id": "weekday",
+                        "periods": [
+                            { "id": "am_peak", "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": am_trips },
+                            { "id": "off_peak", "period_shortname": "off_peak", "start_at_hour": 9, "end_at_hour": 15, "trips": [slow_trip, trip_with_null_flag, trip("t1", 36000)] }
+                        ]
+                    }
+                }
+            }
+        });
+
+        write_line(&config, "", &line);
+        let complete_line = line_response(&config, "id=line")["data"]["line"].clone();
+        let complete_line_file_size = fs::metadata(&line_file_path).unwrap().len();
+
+        // the trips are expanded back from their patterns
+        write_line(&config, "trip_patterns=true", &line);
+        assert!(fs::metadata(&line_file_path).unwrap().len() < complete_line_file_size);
+        assert_eq!(line_response(&config, "id=line")["data"]["line"], complete_line);
+        assert_eq!(line_response(&config, "id=line&period_id=off_peak")["data"]["line"]["scheduleByServiceId"]["weekday"]["periods"][0], complete_line["scheduleByServiceId"]["weekday"]["periods"][1]);
+
+        write_line(&config, "trip_patterns=true&by_service=true", &line);
+        assert_eq!(line_response(&config, "id=line")["data"]["line"], complete_line);
+
+    }
 }
@@ -0,0 +1,92 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn nodes_near_response(config: &serde_json::Value, url: &str) -> serde_json::Value {
+        let request = Request::fake_http("GET", url, vec![], vec![]);
+        let response = routers::nodes_near_route(config, &request);
+        assert_eq!(response.status_code, 200);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn node_ids(json_response: &serde_json::Value) -> Vec<&str> {
+        json_response["data"]["nodes"].as_array().unwrap().iter().map(|node| node["id"].as_str().unwrap()).collect()
+    }
+
+    #[test]
+    fn nodes_near() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "nodes_near",
+            "project_shortname"           : "test"
+        });
+
+        // a: at the query point, b: ~157m east, c: ~785m east with a 700m routing radius,
+        // d: ~314m north but disabled, e: far away
+        let data = r##"
+            {
+                "nodes": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.600, 45.500] }, "id": 1, "properties": { "id": "a", "integer_id": 1, "is_enabled": true, "routing_radius_meters": 0 } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.598, 45.500] }, "id": 2, "properties": { "id": "b", "integer_id": 2, "is_enabled": null } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.590, 45.500] }, "id": 3, "properties": { "id": "c", "integer_id": 3, "is_enabled": true, "routing_radius_meters": 700 } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.600, 45.503] }, "id": 4, "properties": { "id": "d", "integer_id": 4, "is_enabled": false } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-72.000, 46.000] }, "id": 5, "properties": { "id": "e", "integer_id": 5, "is_enabled": true } }
+                    ]
+                }
+            }
+        "##;
+
+        let cache_directory_path = Path::new("test").join("nodes_near");
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("nodes.capnpbin")).unwrap();
+        transition_capnp_data::serialization::node_collection::write_collection(&serde_json::from_str(data).unwrap(), &mut file).unwrap();
+
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6&lat=45.5&count=3");
+        assert_eq!(json_response["status"], "success");
+        assert_eq!(node_ids(&json_response), vec!["a", "c", "b"]);
+        assert_eq!(json_response["data"]["nodes"][0]["distance_meters"], 0.0);
+
+        // the count is capped to the number of nodes
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6&lat=45.5&count=10000000000");
+        assert_eq!(node_ids(&json_response), vec!["a", "c", "b", "e"]);
+
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6&lat=45.5&count=1e30");
+        assert_eq!(json_response["status"], "fail");
+
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6&lat=45.5&radius_meters=500");
+        assert_eq!(node_ids(&json_response), vec!["a", "c", "b"]);
+
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6&lat=45.5&radius_meters=50");
+        assert_eq!(node_ids(&json_response), vec!["a"]);
+
+        let json_response = nodes_near_response(&config, "/nodes/near?bbox=-73.601,45.499,-73.595,45.504");
+        let mut ids = node_ids(&json_response);
+        ids.sort();
+        assert_eq!(ids, vec!["a", "b"]);
+
+        let json_response = nodes_near_response(&config, "/nodes/near?lon=-73.6");
+        assert_eq!(json_response["status"], "fail");
+
+    }
+
+}
@@ -0,0 +1,190 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+    use transition_capnp_data::transit_routing::TransitNetworkCache;
+
+    fn trip(trip_uuid: &str, path_uuid: &str, departure_times: serde_json::Value, arrival_times: serde_json::Value) -> serde_json::Value {
+        let nodes_count = departure_times.as_array().unwrap().len();
+        let mut nodes_can_board = vec![true; nodes_count];
+        nodes_can_board[nodes_count - 1] = false;
+        let mut nodes_can_unboard = vec![true; nodes_count];
+        nodes_can_unboard[0] = false;
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": departure_times[0],
+            "arrival_time_seconds": arrival_times[nodes_count - 1],
+            "node_arrival_times_seconds": arrival_times,
+            "node_departure_times_seconds": departure_times,
+            "nodes_can_board": nodes_can_board,
+            "nodes_can_unboard": nodes_can_unboard
+        })
+    }
+
+    fn od_matrix_response(config: &serde_json::Value, query: serde_json::Value) -> (u16, String) {
+        let request = Request::fake_http(
+            "POST",
+            "/od_matrix",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            query.to_string().into_bytes(),
+        );
+        let response = routers::od_matrix_route(config, &TransitNetworkCache::new(0), &request);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        (response.status_code, buffer)
+    }
+
+    #[test]
+    fn od_matrix() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "od_matrix",
+            "project_shortname"           : "test"
+        });
+
+        let cache_directory_path = Path::new("test").join("od_matrix");
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+
+        // line 1 goes from a to c, line 2 from d to e, c and d are ~111m apart
+        write("nodes", &transition_capnp_data::serialization::node_collection::write_collection, json!({
+            "nodes": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "Point", "coordinates": [-73.60, 45.50] }, "properties": { "id": "a", "integer_id": 1 } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "Point", "coordinates": [-73.58, 45.50] }, "properties": { "id": "b", "integer_id": 2 } },
+                    { "type": "Feature", "id": 3, "geometry": { "type": "Point", "coordinates": [-73.56, 45.50] }, "properties": { "id": "c", "integer_id": 3 } },
+                    { "type": "Feature", "id": 4, "geometry": { "type": "Point", "coordinates": [-73.56, 45.501] }, "properties": { "id": "d", "integer_id": 4 } },
+                    { "type": "Feature", "id": 5, "geometry": { "type": "Point", "coordinates": [-73.54, 45.501] }, "properties": { "id": "e", "integer_id": 5 } }
+                ]
+            }
+        }));
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.60, 45.50], [-73.58, 45.50], [-73.56, 45.50]] }, "properties": { "id": "p1", "integer_id": 1, "line_id": "line1", "nodes": ["a", "b", "c"], "segments": [0, 1] } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": [[-73.56, 45.501], [-73.54, 45.501]] }, "properties": { "id": "p2", "integer_id": 2, "line_id": "line2", "nodes": ["d", "e"], "segments": [0] } }
+                ]
+            }
+        }));
+        write("scenarios", &transition_capnp_data::serialization::scenario_collection::write_collection, json!({
+            "scenarios": [{ "id": "scenario", "services": ["service"] }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "service" }]
+        }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({
+            "lines": [
+                { "id": "line1", "agency_id": "agency", "mode": "bus" },
+                { "id": "line2", "agency_id": "agency", "mode": "bus" }
+            ]
+        }));
+        let line = |line_uuid: &str, trips: serde_json::Value| json!({
+            "line": {
+                "id": line_uuid,
+                "agency_id": "agency",
+                "mode": "bus",
+                "scheduleByServiceId": {
+                    "service": {
+                        "id": format!("{}_schedule", line_uuid),
+                        "service_id": "service",
+                        "periods": [{ "period_shortname": "all_day", "start_at_hour": 6, "end_at_hour": 12, "trips": trips }]
+                    }
+                }
+            }
+        });
+        for line_object in [
+            line("line1", json!([
+                trip("t1", "p1", json!([28800, 29160, null]), json!([null, 29100, 29400])),
+                trip("t2", "p1", json!([30600, 30960, null]), json!([null, 30900, 31200]))
+            ])),
+            // u1 leaves d too early to transfer from t1
+            line("line2", json!([
+                trip("u1", "p2", json!([29500, null]), json!([null, 29800])),
+                trip("u2", "p2", json!([29700, null]), json!([null, 30000])),
+                trip("u3", "p2", json!([31500, null]), json!([null, 31800]))
+            ])),
+        ] {
+            transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &line_object).unwrap();
+        }
+
+        // the second trip leaves a after the last trip of line 1
+        let od_trips_csv = "id,origin_longitude,origin_latitude,destination_longitude,destination_latitude,departure_time\n\
+            first,-73.6005,45.50,-73.54,45.501,07:55\n\
+            second,-73.6005,45.50,-73.54,45.501,31000\n";
+        let (status_code, csv) = od_matrix_response(&config, json!({
+            "scenario_id": "scenario",
+            "od_trips_csv": od_trips_csv
+        }));
+        assert_eq!(status_code, 200);
+        let rows : Vec<&str> = csv.lines().collect();
+        assert_eq!(rows, vec![
+            "id,status,departure_time_seconds,arrival_time_seconds,total_travel_time_seconds,in_vehicle_time_seconds,access_travel_time_seconds,egress_travel_time_seconds,transfer_walking_time_seconds,waiting_time_seconds,first_waiting_time_seconds,number_of_transfers,walking_only_travel_time_seconds,error",
+            "first,success,28500,30000,1500,900,29,0,81,490,271,1,,",
+            "second,no_routing_found,31000,,,,,,,,,,,"
+        ]);
+
+        // all the pairs of origins and destinations, an unknown node is an error for its pairs only
+        let (status_code, json_results) = od_matrix_response(&config, json!({
+            "scenario_id": "scenario",
+            "origins": ["a"],
+            "destinations": ["e", "unknown"],
+            "departure_time_seconds": 28500,
+            "format": "json"
+        }));
+        assert_eq!(status_code, 200);
+        let results : serde_json::Value = serde_json::from_str(&json_results).unwrap();
+        assert_eq!(results.as_array().unwrap().len(), 2);
+        assert_eq!(results[0]["id"], "0_0");
+        assert_eq!(results[0]["arrival_time_seconds"], 30000);
+        assert_eq!(results[1]["id"], "0_1");
+        assert_eq!(results[1]["status"], "error");
+        assert_eq!(results[1]["error"], "Node unknown not found");
+
+        let (_, json_response) = od_matrix_response(&config, json!({ "scenario_id": "unknown", "origins": ["a"], "destinations": ["e"], "departure_time_seconds": 28500 }));
+        let json_response : serde_json::Value = serde_json::from_str(&json_response).unwrap();
+        assert_eq!(json_response["status"], "fail");
+
+        // the server does not open the files of the requests
+        let od_trips_file_path = fs::canonicalize(&cache_directory_path).unwrap().join("od_trips.csv");
+        fs::write(&od_trips_file_path, od_trips_csv).unwrap();
+        let (_, json_response) = od_matrix_response(&config, json!({ "scenario_id": "scenario", "od_trips_csv_file_path": od_trips_file_path }));
+        let json_response : serde_json::Value = serde_json::from_str(&json_response).unwrap();
+        assert_eq!(json_response["status"], "fail");
+
+        // the errors do not repeat the csv values
+        let (_, json_response) = od_matrix_response(&config, json!({
+            "scenario_id": "scenario",
+            "od_trips_csv": "id,origin_longitude,origin_latitude,destination_longitude,destination_latitude,departure_time\nfirst,secret,45.50,-73.54,45.501,07:55\n"
+        }));
+        let json_response : serde_json::Value = serde_json::from_str(&json_response).unwrap();
+        assert_eq!(json_response["status"], "fail");
+        assert!(!json_response.to_string().contains("secret"));
+
+    }
+
+}
@@ -0,0 +1,103 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use pretty_assertions::{assert_eq};
+
+    #[test]
+    fn path_analytics() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "path_analytics",
+            "project_shortname"           : "test"
+        });
+
+        let nodes_data = r##"
+            {
+                "nodes": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.600, 45.500] }, "id": 1, "properties": { "id": "a", "integer_id": 1 } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.598, 45.500] }, "id": 2, "properties": { "id": "b", "integer_id": 2 } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.596, 45.5001] }, "id": 3, "properties": { "id": "c", "integer_id": 3 } }
+                    ]
+                }
+            }
+        "##;
+
+        let paths_data = r##"
+            {
+                "paths": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        {
+                            "type": "Feature",
+                            "geometry": { "type": "LineString", "coordinates": [[-73.600, 45.500], [-73.599, 45.500], [-73.598, 45.500], [-73.596, 45.500]] },
+                            "id": 1,
+                            "properties": { "id": "valid_path", "integer_id": 1, "line_id": "line", "nodes": ["a", "b", "c"], "segments": [0, 2] }
+                        },
+                        {
+                            "type": "Feature",
+                            "geometry": { "type": "LineString", "coordinates": [[-73.600, 45.500], [-73.598, 45.500], [-73.596, 45.500]] },
+                            "id": 2,
+                            "properties": { "id": "invalid_path", "integer_id": 2, "line_id": "line", "nodes": ["a", "b", "c"], "segments": [0, 3, 1] }
+                        }
+                    ]
+                }
+            }
+        "##;
+
+        let cache_directory_path = Path::new("test").join("path_analytics");
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("nodes.capnpbin")).unwrap();
+        transition_capnp_data::serialization::node_collection::write_collection(&serde_json::from_str(nodes_data).unwrap(), &mut file).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("paths.capnpbin")).unwrap();
+        transition_capnp_data::serialization::path_collection::write_collection(&serde_json::from_str(paths_data).unwrap(), &mut file).unwrap();
+
+        let response = routers::path_analytics_route(&config);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(json_response["data"]["invalid_paths_count"], 1);
+
+        let valid_path = &json_response["data"]["paths"][0];
+        assert_eq!(valid_path["id"], "valid_path");
+        assert_eq!(valid_path["valid"], true);
+        let segment_lengths : Vec<f64> = valid_path["segment_lengths_meters"].as_array().unwrap().iter().map(|length| length.as_f64().unwrap()).collect();
+        assert_eq!(segment_lengths.len(), 2);
+        // both segments are 0.002 degree of longitude long
+        assert!((segment_lengths[0] - segment_lengths[1]).abs() < 0.01);
+        assert!((segment_lengths[0] + segment_lengths[1] - valid_path["total_length_meters"].as_f64().unwrap()).abs() < 0.01);
+        let nodes_distances : Vec<f64> = valid_path["nodes_distances_to_geometry_meters"].as_array().unwrap().iter().map(|distance| distance.as_f64().unwrap()).collect();
+        assert_eq!(nodes_distances[0], 0.0);
+        assert_eq!(nodes_distances[1], 0.0);
+        assert!((nodes_distances[2] - 11.1).abs() < 0.1);
+
+        let invalid_path = &json_response["data"]["paths"][1];
+        assert_eq!(invalid_path["id"], "invalid_path");
+        assert_eq!(invalid_path["valid"], false);
+        assert_eq!(invalid_path["errors"], json!([
+            "Path has 3 segments for 3 nodes, expected 2",
+            "Segment 1 index 3 is out of the geography's 3 coordinates",
+            "Segment 2 index 1 is before the previous segment index 3"
+        ]));
+        assert_eq!(invalid_path["segment_lengths_meters"], json!([]));
+
+    }
+
+}
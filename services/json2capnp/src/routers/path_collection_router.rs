@@ -237,4 +237,88 @@ mod tests {
         assert_eq!(json_response["data"]["paths"], json_compare_data);
 
     }
+
+    #[test]
+    fn path_collections_polyline() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "path_collections_polyline",
+            "project_shortname"           : "test"
+        });
+        fs::create_dir_all(Path::new("test").join("path_collections_polyline")).unwrap();
+
+        // Example polyline of the Google encoded polyline algorithm documentation
+        let data = r##"
+            {
+                "cache_directory_path": "path_collections_polyline",
+                "polyline_precision": 5,
+                "paths": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        {
+                            "type": "Feature",
+                            "geometry": null,
+                            "polyline": "_p~iF~ps|U_ulLnnqC_mqNvxq`@",
+                            "id": 1,
+                            "properties": {
+                                "id": "515923f9-a768-49e5-81b6-8237d60a6125",
+                                "integer_id": 1,
+                                "line_id": "715923f9-a768-49e5-81b6-8237d60a6125"
+                            }
+                        }
+                    ]
+                }
+            }
+        "##;
+
+        let request = Request::fake_http(
+            "POST",
+            "/paths",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.as_bytes().to_vec(),
+        );
+
+        let response = routers::write_collection_route(
+            "paths",
+            "paths",
+            &config,
+            &transition_capnp_data::serialization::path_collection::write_collection,
+            &request,
+        );
+        assert_eq!(response.status_code, 200);
+
+        let read_paths = |url: &str| -> serde_json::Value {
+            let request = Request::fake_http("GET", url, vec![], vec![]);
+            let response = routers::read_path_collection_route(&config, &request);
+            assert_eq!(response.status_code, 200);
+            let (mut res_data, _) = response.data.into_reader_and_size();
+            let mut buffer = String::new();
+            res_data.read_to_string(&mut buffer).unwrap();
+            serde_json::from_str(buffer.as_str()).unwrap()
+        };
+
+        let json_response = read_paths("/paths");
+        assert_eq!(json_response["data"]["paths"]["features"][0]["geometry"], json!({
+            "type": "LineString",
+            "coordinates": [[-120.2, 38.5], [-120.95, 40.7], [-126.453, 43.252]]
+        }));
+
+        let json_response = read_paths("/paths?geometry_encoding=polyline");
+        assert_eq!(json_response["data"]["polyline_precision"], 5);
+        assert_eq!(json_response["data"]["paths"]["features"][0]["geometry"], json!(null));
+        assert_eq!(json_response["data"]["paths"]["features"][0]["polyline"], "_p~iF~ps|U_ulLnnqC_mqNvxq`@");
+
+        let json_response = read_paths("/paths?geometry_encoding=polyline&polyline_precision=6");
+        assert_eq!(json_response["data"]["polyline_precision"], 6);
+        assert_eq!(json_response["data"]["paths"]["features"][0]["polyline"], "_izlhA~rlgdF_{geC~ywl@_kwzCn`{nI");
+
+        let json_response = read_paths("/paths?geometry_encoding=polyline&polyline_precision=12");
+        assert_eq!(json_response["status"], "fail");
+
+    }
 }
+
@@ -0,0 +1,141 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use pretty_assertions::{assert_eq};
+
+    fn trip(trip_uuid: &str, path_uuid: &str) -> serde_json::Value {
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": 21600,
+            "arrival_time_seconds": 21900,
+            "node_arrival_times_seconds": [null, 21900],
+            "node_departure_times_seconds": [21600, null],
+            "nodes_can_board": [true, false],
+            "nodes_can_unboard": [false, true]
+        })
+    }
+
+    fn line(line_uuid: &str, agency_uuid: &str, mode: &str, trips_by_service: Vec<(&str, Vec<serde_json::Value>)>) -> serde_json::Value {
+        let mut schedules = json!({});
+        for (service_uuid, trips) in trips_by_service {
+            schedules[service_uuid] = json!({
+                "id": format!("{}_{}", line_uuid, service_uuid),
+                "service_id": service_uuid,
+                "periods": [{ "period_shortname": "morning", "start_at_hour": 6, "end_at_hour": 9, "trips": trips }]
+            });
+        }
+        json!({ "line": { "id": line_uuid, "agency_id": agency_uuid, "mode": mode, "shortname": line_uuid, "scheduleByServiceId": schedules } })
+    }
+
+    #[test]
+    fn resolve_scenario() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "resolve_scenario",
+            "project_shortname"           : "test"
+        });
+
+        let cache_directory_path = Path::new("test").join("resolve_scenario");
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+
+        write("scenarios", &transition_capnp_data::serialization::scenario_collection::write_collection, json!({
+            "scenarios": [{
+                "id": "scenario",
+                "services": ["s1", "unknown_service"],
+                "except_lines": ["l3"],
+                "only_modes": ["bus"],
+                "except_nodes": ["n9"]
+            }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "s1" }, { "id": "s2" }]
+        }));
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.6, 45.5], [-73.5, 45.5]] }, "properties": { "id": "p1", "integer_id": 1, "line_id": "l1", "nodes": ["n1", "n2"], "segments": [0] } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": [[-73.6, 45.5], [-73.4, 45.5]] }, "properties": { "id": "p2", "integer_id": 2, "line_id": "l1", "nodes": ["n1", "n9"], "segments": [0] } }
+                ]
+            }
+        }));
+
+        let lines = [
+            line("l1", "a1", "bus", vec![("s1", vec![trip("t1", "p1"), trip("t2", "p2"), trip("t3", "missing_path")]), ("s2", vec![trip("t4", "p1")])]),
+            line("l2", "a1", "tram", vec![("s1", vec![trip("t5", "p1")])]),
+            line("l3", "a2", "bus", vec![("s1", vec![trip("t6", "p1"), trip("t7", "p1")])]),
+            // lines with no trips left once the services and the nodes are applied
+            line("l5", "a1", "bus", vec![("s2", vec![trip("t8", "p1")])]),
+            line("l6", "a1", "bus", vec![("s1", vec![trip("t9", "p2")])]),
+        ];
+        let mut lines_collection : Vec<serde_json::Value> = lines.iter().map(|line| line["line"].clone()).collect();
+        // line without a line object
+        lines_collection.push(json!({ "id": "l4", "agency_id": "a1", "mode": "bus" }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({ "lines": lines_collection }));
+        for line in lines.iter() {
+            transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), line).unwrap();
+        }
+
+        let response = routers::resolve_scenario_route(&config, "scenario");
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(json_response["data"]["lines_ids"], json!(["l1"]));
+        assert_eq!(json_response["data"]["paths_ids"], json!(["p1"]));
+        assert_eq!(json_response["data"]["schedules"], json!([{ "id": "l1_s1", "line_id": "l1", "service_id": "s1", "trips_count": 1 }]));
+        assert_eq!(json_response["data"]["trips"], json!([{
+            "id": "t1",
+            "line_id": "l1",
+            "path_id": "p1",
+            "service_id": "s1",
+            "schedule_id": "l1_s1",
+            "period_shortname": "morning",
+            "departure_time_seconds": 21600,
+            "arrival_time_seconds": 21900
+        }]));
+        assert_eq!(json_response["data"]["summary"], json!({
+            "only_agencies": { "removed_lines": 0, "removed_trips": 0 },
+            "except_agencies": { "removed_lines": 0, "removed_trips": 0 },
+            "only_lines": { "removed_lines": 0, "removed_trips": 0 },
+            "except_lines": { "removed_lines": 1, "removed_trips": 2 },
+            "only_modes": { "removed_lines": 1, "removed_trips": 1 },
+            "except_modes": { "removed_lines": 0, "removed_trips": 0 },
+            "services": { "removed_lines": 1, "removed_schedules": 2, "removed_trips": 2, "unknown_services": ["unknown_service"] },
+            "only_nodes": { "removed_lines": 0, "removed_trips": 0 },
+            "except_nodes": { "removed_lines": 1, "removed_trips": 2 },
+            "missing_paths": { "removed_lines": 0, "removed_trips": 1 },
+            "missing_line_objects": ["l4"]
+        }));
+
+        let response = routers::resolve_scenario_route(&config, "unknown_scenario");
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+        assert_eq!(json_response["status"], "fail");
+
+    }
+
+}
@@ -0,0 +1,139 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn service_calendar_response(query: &str, config: &serde_json::Value, url: &str) -> serde_json::Value {
+        let request = Request::fake_http("GET", url, vec![], vec![]);
+        let response = routers::service_calendar_route(query, config, &request);
+        assert_eq!(response.status_code, 200);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn write_services(data: &str) {
+        let cache_directory_path = Path::new("test").join("service_calendar");
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("services.capnpbin")).unwrap();
+        transition_capnp_data::serialization::service_collection::write_collection(&serde_json::from_str(data).unwrap(), &mut file).unwrap();
+    }
+
+    #[test]
+    fn service_calendar() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "service_calendar",
+            "project_shortname"           : "test"
+        });
+
+        // 2026-10-12 is a monday holiday on which the weekend service runs,
+        // the november service starts on saturday 2026-10-31. The weekend
+        // only date 2026-11-02 is out of its period, so it is ignored.
+        write_services(r##"
+            {
+                "services": [
+                    {
+                        "id": "weekday",
+                        "monday": true, "tuesday": true, "wednesday": true, "thursday": true, "friday": true, "saturday": false, "sunday": false,
+                        "start_date": "2026-10-01", "end_date": "2026-10-31",
+                        "only_dates": [], "except_dates": ["2026-10-12"]
+                    },
+                    {
+                        "id": "weekend",
+                        "monday": false, "tuesday": false, "wednesday": false, "thursday": false, "friday": false, "saturday": true, "sunday": true,
+                        "start_date": "2026-10-01", "end_date": "2026-10-31",
+                        "only_dates": ["2026-10-12", "2026-11-02"], "except_dates": []
+                    },
+                    {
+                        "id": "november",
+                        "monday": true, "tuesday": true, "wednesday": true, "thursday": true, "friday": true, "saturday": true, "sunday": true,
+                        "start_date": "2026-10-31", "end_date": "2026-11-30"
+                    }
+                ]
+            }
+        "##);
+
+        let json_response = service_calendar_response("on_date", &config, "/services/on_date?date=2026-10-19");
+        assert_eq!(json_response["data"], json!({ "date": "2026-10-19", "services_ids": ["weekday"] }));
+
+        let json_response = service_calendar_response("on_date", &config, "/services/on_date?date=2026-10-12");
+        assert_eq!(json_response["data"]["services_ids"], json!(["weekend"]));
+
+        let json_response = service_calendar_response("on_date", &config, "/services/on_date?date=2026-11-02");
+        assert_eq!(json_response["data"]["services_ids"], json!(["november"]));
+
+        let json_response = service_calendar_response("dates", &config, "/services/dates?uuid=weekday&start_date=2026-10-09&end_date=2026-10-14");
+        assert_eq!(json_response["data"]["dates"], json!(["2026-10-09", "2026-10-13", "2026-10-14"]));
+
+        let json_response = service_calendar_response("overlaps", &config, "/services/overlaps?start_date=2026-10-01&end_date=2026-11-30");
+        assert_eq!(json_response["data"]["overlaps"], json!([{
+            "services_ids": ["weekend", "november"],
+            "common_dates_count": 1,
+            "first_common_date": "2026-10-31"
+        }]));
+
+        let json_response = service_calendar_response("overlaps", &config, "/services/overlaps?start_date=2026-10-01&end_date=2026-10-30");
+        assert_eq!(json_response["data"]["overlaps"], json!([]));
+
+        let json_response = service_calendar_response("overlaps", &config, "/services/overlaps?start_date=0001-01-01&end_date=9999-12-31");
+        assert_eq!(json_response["status"], "fail");
+
+        let json_response = service_calendar_response("on_date", &config, "/services/on_date?date=2026-10-1");
+        assert_eq!(json_response["status"], "fail");
+
+        let json_response = service_calendar_response("validate", &config, "/services/validate");
+        assert_eq!(json_response["data"], json!({ "valid": true, "invalid_services": [] }));
+
+        write_services(r##"
+            {
+                "services": [
+                    {
+                        "id": "weekday",
+                        "monday": true, "tuesday": true, "wednesday": true, "thursday": true, "friday": true,
+                        "start_date": "2026-10-01", "end_date": "2026-10-31"
+                    },
+                    {
+                        "id": "all_week",
+                        "monday": true, "tuesday": true, "wednesday": true, "thursday": true, "friday": true, "saturday": true, "sunday": true,
+                        "start_date": "2026-10-15", "end_date": "2026-11-15"
+                    },
+                    {
+                        "id": "invalid",
+                        "start_date": "2026-12-01", "end_date": "2026-11-01",
+                        "only_dates": ["2026/12/25"]
+                    }
+                ]
+            }
+        "##);
+
+        let json_response = service_calendar_response("validate", &config, "/services/validate");
+        assert_eq!(json_response["data"], json!({
+            "valid": false,
+            "invalid_services": [{
+                "id": "invalid",
+                "error": "Service invalid has invalid dates: only_dates: Invalid date 2026/12/25, expected YYYY-MM-DD, start_date 2026-12-01 is after end_date 2026-11-01"
+            }]
+        }));
+
+        let json_response = service_calendar_response("overlaps", &config, "/services/overlaps?start_date=2026-10-01&end_date=2026-12-31");
+        assert_eq!(json_response["status"], "fail");
+
+    }
+
+}
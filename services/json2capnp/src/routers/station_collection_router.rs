@@ -0,0 +1,126 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    #[test]
+    fn station_collection() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "stations",
+            "project_shortname"           : "test"
+        });
+
+        let stations = json!({
+            "cache_directory_path": "stations",
+            "stations": {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "id": 1,
+                        "geometry": { "type": "Point", "coordinates": [-73.6, 45.5] },
+                        "properties": {
+                            "id": "central",
+                            "integer_id": 1,
+                            "internal_id": null,
+                            "code": "C",
+                            "name": "Central",
+                            "color": "#FF0000",
+                            "description": null,
+                            "is_enabled": true,
+                            "is_frozen": null,
+                            "nodes": ["platform_1", "platform_2", "unknown_platform"],
+                            "data": { "foo": "bar" }
+                        }
+                    },
+                    {
+                        "type": "Feature",
+                        "id": 2,
+                        "geometry": { "type": "Point", "coordinates": [-73.59, 45.51] },
+                        "properties": {
+                            "id": "north",
+                            "integer_id": 2,
+                            "internal_id": null,
+                            "code": null,
+                            "name": "North",
+                            "color": null,
+                            "description": null,
+                            "is_enabled": null,
+                            "is_frozen": null,
+                            "nodes": [],
+                            "data": {}
+                        }
+                    }
+                ]
+            }
+        });
+
+        let request = Request::fake_http(
+            "POST",
+            "/stations",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            stations.to_string().into_bytes(),
+        );
+        let response = routers::write_collection_route("stations", "stations", &config, &transition_capnp_data::serialization::station_collection::write_collection, &request);
+        assert_eq!(response_json(response)["status"], "success");
+
+        let response = routers::read_collection_route("stations", "stations", &config, &transition_capnp_data::serialization::station_collection::read_collection);
+        assert_eq!(response_json(response)["data"]["stations"], stations["stations"]);
+
+        let nodes_data = r##"
+            {
+                "nodes": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.6, 45.5] }, "id": 1, "properties": { "id": "platform_1", "integer_id": 1, "station_id": "central" } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.6, 45.5] }, "id": 2, "properties": { "id": "platform_2", "integer_id": 2, "station_id": "north" } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.6, 45.5] }, "id": 3, "properties": { "id": "platform_3", "integer_id": 3, "station_id": "south" } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.6, 45.5] }, "id": 4, "properties": { "id": "stop", "integer_id": 4 } }
+                    ]
+                }
+            }
+        "##;
+        let mut file = fs::File::create(Path::new("test").join("stations").join("nodes.capnpbin")).unwrap();
+        transition_capnp_data::serialization::node_collection::write_collection(&serde_json::from_str(nodes_data).unwrap(), &mut file).unwrap();
+
+        let json_response = response_json(routers::validate_stations_route(&config));
+        assert_eq!(json_response["data"], json!({
+            "valid": false,
+            "errors": [
+                "Station central has node platform_2 which references station north",
+                "Station central has unknown node unknown_platform",
+                "Node platform_2 references station north which does not list it",
+                "Node platform_3 references unknown station south"
+            ],
+            "stations_count": 2,
+            "nodes_with_station_count": 3,
+            "nodes_with_unknown_station": ["platform_3"]
+        }));
+
+    }
+}
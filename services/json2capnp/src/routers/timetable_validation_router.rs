@@ -0,0 +1,177 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn line_request(url: &str, trips: serde_json::Value) -> Request {
+        let data = json!({
+            "cache_directory_path": "validate_timetables/lines",
+            "line": {
+                "id": "line",
+                "agency_id": "agency",
+                "mode": "bus",
+                "scheduleByServiceId": {
+                    "service": {
+                        "id": "schedule",
+                        "service_id": "service",
+                        "periods": [{ "period_shortname": "morning", "start_at_hour": 6, "end_at_hour": 9, "trips": trips }]
+                    }
+                }
+            }
+        });
+        Request::fake_http(
+            "POST",
+            url,
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.to_string().into_bytes(),
+        )
+    }
+
+    #[test]
+    fn validate_timetables() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "validate_timetables",
+            "project_shortname"           : "test"
+        });
+
+        let cache_directory_path = Path::new("test").join("validate_timetables");
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let _ = fs::remove_file(cache_directory_path.join("lines").join("line_line.capnpbin"));
+        let mut file = fs::File::create(cache_directory_path.join("paths.capnpbin")).unwrap();
+        transition_capnp_data::serialization::path_collection::write_collection(&json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.6, 45.5], [-73.5, 45.5], [-73.4, 45.5]] }, "properties": { "id": "path", "integer_id": 1, "line_id": "line", "nodes": ["n1", "n2", "n3"], "segments": [0, 1] } }
+                ]
+            }
+        }), &mut file).unwrap();
+
+        let valid_trip = json!({
+            "id": "valid",
+            "path_id": "path",
+            "node_arrival_times_seconds": [null, 21900, 22200],
+            "node_departure_times_seconds": [21600, 21930, null],
+            "nodes_can_board": [true, true, false],
+            "nodes_can_unboard": [false, true, true]
+        });
+        let invalid_trips = json!([
+            valid_trip,
+            {
+                "id": "wrong_length",
+                "path_id": "path",
+                "node_arrival_times_seconds": [null, 21900],
+                "node_departure_times_seconds": [21600, null],
+                "nodes_can_board": [true, false],
+                "nodes_can_unboard": [false, true]
+            },
+            {
+                "id": "wrong_times",
+                "path_id": "path",
+                "node_arrival_times_seconds": [21500, 21900, 21800],
+                "node_departure_times_seconds": [21600, 21850, null],
+                "nodes_can_board": [true, true, true],
+                "nodes_can_unboard": [false, true, true]
+            },
+            {
+                "id": "unknown_path",
+                "path_id": "other_path",
+                "node_arrival_times_seconds": [],
+                "node_departure_times_seconds": [],
+                "nodes_can_board": [],
+                "nodes_can_unboard": []
+            }
+        ]);
+
+        // strict mode refuses to write a line with invalid trips
+        let json_response = response_json(routers::write_line_object_route(&config, &line_request("/line?strict=true", invalid_trips.clone())));
+        assert_eq!(json_response["status"], "fail");
+        assert!(!cache_directory_path.join("lines").join("line_line.capnpbin").exists());
+
+        let json_response = response_json(routers::write_line_object_route(&config, &line_request("/line?strict=true", json!([valid_trip]))));
+        assert_eq!(json_response["status"], "success");
+        let json_response = response_json(routers::validate_line_timetables_route(&config, "line"));
+        assert_eq!(json_response["data"], json!({ "line_id": "line", "valid": true, "trips_count": 1, "invalid_trips": [] }));
+
+        // the layout options apply in strict mode too
+        let json_response = response_json(routers::write_line_object_route(&config, &line_request("/line?strict=true&by_service=true", json!([valid_trip]))));
+        assert_eq!(json_response["status"], "success");
+        assert!(cache_directory_path.join("lines").join("line_line_trips_service.capnpbin").exists());
+
+        // without strict mode, the line is written as is and can be validated afterwards
+        let json_response = response_json(routers::write_line_object_route(&config, &line_request("/line", invalid_trips)));
+        assert_eq!(json_response["status"], "success");
+
+        let json_response = response_json(routers::validate_line_timetables_route(&config, "line"));
+        assert_eq!(json_response["data"], json!({
+            "line_id": "line",
+            "valid": false,
+            "trips_count": 4,
+            "invalid_trips": [
+                {
+                    "service_id": "service",
+                    "schedule_id": "schedule",
+                    "period_index": 0,
+                    "period_shortname": "morning",
+                    "trip_index": 1,
+                    "trip_id": "wrong_length",
+                    "errors": [
+                        "node_arrival_times_seconds has 2 values for 3 path nodes",
+                        "node_departure_times_seconds has 2 values for 3 path nodes",
+                        "nodes_can_board has 2 values for 3 path nodes",
+                        "nodes_can_unboard has 2 values for 3 path nodes"
+                    ]
+                },
+                {
+                    "service_id": "service",
+                    "schedule_id": "schedule",
+                    "period_index": 0,
+                    "period_shortname": "morning",
+                    "trip_index": 2,
+                    "trip_id": "wrong_times",
+                    "errors": [
+                        "First node has an arrival time",
+                        "Last node allows boarding",
+                        "Node 1 departure time 21850 is before its arrival time 21900",
+                        "Node 2 arrival time 21800 is before node 1 departure time 21850"
+                    ]
+                },
+                {
+                    "service_id": "service",
+                    "schedule_id": "schedule",
+                    "period_index": 0,
+                    "period_shortname": "morning",
+                    "trip_index": 3,
+                    "trip_id": "unknown_path",
+                    "errors": ["Unknown path \"other_path\""]
+                }
+            ]
+        }));
+
+    }
+
+}
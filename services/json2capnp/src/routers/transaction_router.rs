@@ -0,0 +1,199 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::fs;
+    use std::io::Read;
+    use std::path::Path;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+    use transition_capnp_data::cache_store;
+
+    fn post(url: &str, json: &serde_json::Value) -> Request {
+        Request::fake_http(
+            "POST",
+            url,
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            json.to_string().as_bytes().to_vec(),
+        )
+    }
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    fn agencies(acronym: &str) -> serde_json::Value {
+        json!({ "agencies": [{ "id": "agency", "acronym": acronym, "is_enabled": true }] })
+    }
+
+    fn line() -> serde_json::Value {
+        json!({
+            "line": {
+                "id": "line",
+                "agency_id": "agency",
+                "mode": "bus",
+                "shortname": "1",
+                "scheduleByServiceId": {
+                    "weekday": {
+                        "id": "weekday_schedule",
+                        "service_id": "weekday",
+                        "periods": [
+                            { "id": "am_peak", "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 9, "trips": [] }
+                        ]
+                    }
+                }
+            }
+        })
+    }
+
+    fn read_agency_acronym(config: &serde_json::Value) -> serde_json::Value {
+        let response = routers::read_collection_route("agencies", "agencies", config, &transition_capnp_data::serialization::agency_collection::read_collection);
+        response_json(response)["data"]["agencies"][0]["acronym"].clone()
+    }
+
+    fn write_agencies(config: &serde_json::Value, acronym: &str) {
+        let response = routers::write_collection_route("agencies", "agencies", config, &transition_capnp_data::serialization::agency_collection::write_collection, &post("/agencies", &agencies(acronym)));
+        assert_eq!(response_json(response)["status"], "success");
+    }
+
+    #[test]
+    fn transactions() {
+
+        let cache_directory_path = Path::new("test").join("transactions");
+        let _ = fs::remove_dir_all(&cache_directory_path);
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let project_cache_directory_path = cache_directory_path.to_str().unwrap();
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": project_cache_directory_path,
+            "custom_subdirectory_path"    : null,
+            "project_shortname"           : "test"
+        });
+        write_agencies(&config, "STM");
+
+        let begin_json = response_json(routers::begin_transaction_route(project_cache_directory_path));
+        let transaction_id = begin_json["data"]["transaction_id"].as_str().unwrap().to_string();
+        let transaction_request = |url: &str| Request::fake_http("POST", format!("{}?transaction_id={}", url, transaction_id), vec![], vec![]);
+        let transaction_config = routers::transaction_config(config.clone(), &transaction_request("/agencies")).ok().unwrap();
+
+        // the writes of the transaction are only read in the transaction
+        write_agencies(&transaction_config, "RTL");
+        let response = routers::write_line_object_route(&transaction_config, &post("/line?by_service=true", &line()));
+        assert_eq!(response_json(response)["status"], "success");
+        assert_eq!(read_agency_acronym(&config), "STM");
+        assert_eq!(read_agency_acronym(&transaction_config), "RTL");
+        let line_json = response_json(routers::read_line_object_route(&transaction_config, &String::from("line"), &Request::fake_http("GET", "/line", vec![], vec![])));
+        assert_eq!(line_json["data"]["line"]["shortname"], "1");
+        assert_eq!(response_json(routers::read_line_object_route(&config, &String::from("line"), &Request::fake_http("GET", "/line", vec![], vec![])))["status"], "fail");
+
+        // the trips file written by service is removed in the transaction
+        let response = routers::write_line_object_route(&transaction_config, &post("/line", &line()));
+        assert_eq!(response_json(response)["status"], "success");
+
+        let commit_json = response_json(routers::commit_transaction_route(project_cache_directory_path, &transaction_request("/transactions/commit")));
+        assert_eq!(commit_json["status"], "success");
+        assert_eq!(commit_json["data"]["files_count"], 3);
+        assert_eq!(read_agency_acronym(&config), "RTL");
+        let store = cache_store::open(project_cache_directory_path).unwrap();
+        assert_eq!(store.list("").unwrap(), vec!["agencies.capnpbin", "lines/line_line.capnpbin"]);
+        assert!(!cache_directory_path.join(".transactions").join(&transaction_id).exists());
+
+        // the transaction is closed once committed
+        let commit_json = response_json(routers::commit_transaction_route(project_cache_directory_path, &transaction_request("/transactions/commit")));
+        assert_eq!(commit_json["status"], "fail");
+        assert_eq!(response_json(routers::read_collection_route("agencies", "agencies", &transaction_config, &transition_capnp_data::serialization::agency_collection::read_collection))["status"], "fail");
+
+        // a rolled back transaction leaves the cache unchanged
+        let begin_json = response_json(routers::begin_transaction_route(project_cache_directory_path));
+        let transaction_id = begin_json["data"]["transaction_id"].as_str().unwrap().to_string();
+        let transaction_config = routers::transaction_config(config.clone(), &Request::fake_http("POST", format!("/agencies?transaction_id={}", transaction_id), vec![], vec![])).ok().unwrap();
+        write_agencies(&transaction_config, "STL");
+        let rollback_json = response_json(routers::rollback_transaction_route(project_cache_directory_path, &Request::fake_http("POST", format!("/transactions/rollback?transaction_id={}", transaction_id), vec![], vec![])));
+        assert_eq!(rollback_json["status"], "success");
+        assert_eq!(read_agency_acronym(&config), "RTL");
+        assert_eq!(store.list("").unwrap(), vec!["agencies.capnpbin", "lines/line_line.capnpbin"]);
+
+        // the transaction id is a directory name in the cache
+        assert!(routers::transaction_config(config.clone(), &Request::fake_http("POST", "/agencies?transaction_id=../lines", vec![], vec![])).is_err());
+
+    }
+
+    // A reader loading the cache during a commit, like trRouting, sees all
+    // the old files or all the new ones. The reader opens the directory once
+    // and reads the files in it, and starts again when a file is removed.
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn commit_while_reading() {
+        use std::os::fd::AsRawFd;
+        use std::sync::Arc;
+        use std::sync::atomic::{AtomicBool, Ordering};
+
+        let cache_directory_path = Path::new("test").join("transaction_commit_while_reading");
+        let _ = fs::remove_dir_all(&cache_directory_path);
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let location = cache_directory_path.to_str().unwrap().to_string();
+        let keys : Vec<String> = (0..200).map(|i| format!("lines/line_{}.capnpbin", i)).collect();
+        let store = cache_store::open(&location).unwrap();
+        for key in keys.iter() {
+            store.put(key, b"old").unwrap();
+        }
+
+        let transaction_id = cache_store::transaction::begin(&location).unwrap();
+        let transaction_store = cache_store::open(&cache_store::transaction::transaction_location(&location, &transaction_id).unwrap()).unwrap();
+        for key in keys.iter() {
+            transaction_store.put(key, b"new").unwrap();
+        }
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let reader_stop = stop.clone();
+        let (reader_location, reader_keys) = (location.clone(), keys.clone());
+        let reader = std::thread::spawn(move || {
+            let mut versions_read : Vec<Vec<u8>> = Vec::new();
+            while !reader_stop.load(Ordering::Relaxed) {
+                let directory = fs::File::open(&reader_location).unwrap();
+                let contents : Result<Vec<Vec<u8>>, std::io::Error> = reader_keys.iter()
+                    .map(|key| fs::read(format!("/proc/self/fd/{}/{}", directory.as_raw_fd(), key)))
+                    .collect();
+                let mut contents = match contents {
+                    Ok(contents) => contents,
+                    Err(error) => {
+                        assert_eq!(error.kind(), std::io::ErrorKind::NotFound);
+                        continue;
+                    }
+                };
+                contents.dedup();
+                assert_eq!(contents.len(), 1, "old and new files read together");
+                versions_read.push(contents.remove(0));
+            }
+            versions_read
+        });
+
+        assert_eq!(cache_store::transaction::commit(&location, &transaction_id).unwrap(), keys.len());
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        stop.store(true, Ordering::Relaxed);
+        let versions_read = reader.join().unwrap();
+        assert_eq!(versions_read.last().unwrap(), b"new");
+
+        for key in keys.iter() {
+            assert_eq!(store.get(key).unwrap(), Some(b"new".to_vec()));
+        }
+        assert!(store.list(".transactions/").unwrap().is_empty());
+        let copies_count = fs::read_dir("test").unwrap().filter(|entry| entry.as_ref().unwrap().file_name().to_string_lossy().starts_with(".transaction_commit_while_reading.commit-")).count();
+        assert_eq!(copies_count, 0);
+
+    }
+}
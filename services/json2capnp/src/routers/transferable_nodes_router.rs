@@ -0,0 +1,93 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    #[test]
+    fn transferable_nodes() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "transferable_nodes",
+            "project_shortname"           : "test"
+        });
+
+        // b is ~157m from a, c is ~785m from a and d is next to a but disabled
+        let nodes_data = r##"
+            {
+                "nodes": {
+                    "type": "FeatureCollection",
+                    "features": [
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.600, 45.500] }, "id": 1, "properties": { "id": "a", "integer_id": 1, "name": "Node a", "is_enabled": true, "data": { "foo": "bar" } } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.598, 45.500] }, "id": 2, "properties": { "id": "b", "integer_id": 2, "is_enabled": null } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.590, 45.500] }, "id": 3, "properties": { "id": "c", "integer_id": 3, "is_enabled": true } },
+                        { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.600, 45.5001] }, "id": 4, "properties": { "id": "d", "integer_id": 4, "is_enabled": false } }
+                    ]
+                }
+            }
+        "##;
+
+        let cache_directory_path = Path::new("test").join("transferable_nodes");
+        fs::create_dir_all(&cache_directory_path).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("nodes.capnpbin")).unwrap();
+        transition_capnp_data::serialization::node_collection::write_collection(&serde_json::from_str(nodes_data).unwrap(), &mut file).unwrap();
+
+        let data = r##"
+            {
+                "max_walking_distance_meters": 500,
+                "detour_factor": 1.2,
+                "walking_speed_meters_per_second": 1.0
+            }
+        "##;
+
+        let request = Request::fake_http(
+            "POST",
+            "/nodes/transferable",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.as_bytes().to_vec(),
+        );
+
+        let response = routers::transferable_nodes_route(&config, &request);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(json_response["data"], json!({ "nodes_count": 4, "transfers_count": 2 }));
+
+        let nodes_directory_path = cache_directory_path.join("nodes");
+        let node_a = transition_capnp_data::serialization::node::read_object(&String::from("a"), nodes_directory_path.to_str().unwrap()).unwrap();
+        assert_eq!(node_a["node"]["name"], "Node a");
+        assert_eq!(node_a["node"]["data"]["foo"], "bar");
+        assert_eq!(node_a["node"]["data"]["transferableNodes"], json!({
+            "nodesIds": ["a", "b"],
+            "walkingTravelTimesSeconds": [0, 188],
+            "walkingDistancesMeters": [0, 188]
+        }));
+
+        let node_c = transition_capnp_data::serialization::node::read_object(&String::from("c"), nodes_directory_path.to_str().unwrap()).unwrap();
+        assert_eq!(node_c["node"]["data"]["transferableNodes"]["nodesIds"], json!(["c"]));
+
+        let node_d = transition_capnp_data::serialization::node::read_object(&String::from("d"), nodes_directory_path.to_str().unwrap()).unwrap();
+        assert_eq!(node_d["node"]["data"]["transferableNodes"]["nodesIds"], json!(["d"]));
+
+    }
+
+}
@@ -0,0 +1,226 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use std::sync::Arc;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+    use transition_capnp_data::transit_routing::TransitNetworkCache;
+
+    fn trip(trip_uuid: &str, path_uuid: &str, departure_times: serde_json::Value, arrival_times: serde_json::Value) -> serde_json::Value {
+        let nodes_count = departure_times.as_array().unwrap().len();
+        let mut nodes_can_board = vec![true; nodes_count];
+        nodes_can_board[nodes_count - 1] = false;
+        let mut nodes_can_unboard = vec![true; nodes_count];
+        nodes_can_unboard[0] = false;
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": departure_times[0],
+            "arrival_time_seconds": arrival_times[nodes_count - 1],
+            "node_arrival_times_seconds": arrival_times,
+            "node_departure_times_seconds": departure_times,
+            "nodes_can_board": nodes_can_board,
+            "nodes_can_unboard": nodes_can_unboard
+        })
+    }
+
+    fn route_response(config: &serde_json::Value, networks: &TransitNetworkCache, query: serde_json::Value) -> serde_json::Value {
+        let request = Request::fake_http(
+            "POST",
+            "/route",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            query.to_string().into_bytes(),
+        );
+        let response = routers::transit_routing_route(config, networks, &request);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    #[test]
+    fn transit_routing() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "transit_routing",
+            "project_shortname"           : "test"
+        });
+
+        let cache_directory_path = Path::new("test").join("transit_routing");
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+        let write = |cache_file_name: &str, write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>, json: serde_json::Value| {
+            let mut file = fs::File::create(cache_directory_path.join(format!("{}.capnpbin", cache_file_name))).unwrap();
+            write_fn(&json, &mut file).unwrap();
+        };
+
+        // line 1 goes from a to c, line 2 from d to e, c and d are ~111m apart
+        write("nodes", &transition_capnp_data::serialization::node_collection::write_collection, json!({
+            "nodes": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "Point", "coordinates": [-73.60, 45.50] }, "properties": { "id": "a", "integer_id": 1 } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "Point", "coordinates": [-73.58, 45.50] }, "properties": { "id": "b", "integer_id": 2 } },
+                    { "type": "Feature", "id": 3, "geometry": { "type": "Point", "coordinates": [-73.56, 45.50] }, "properties": { "id": "c", "integer_id": 3 } },
+                    { "type": "Feature", "id": 4, "geometry": { "type": "Point", "coordinates": [-73.56, 45.501] }, "properties": { "id": "d", "integer_id": 4 } },
+                    { "type": "Feature", "id": 5, "geometry": { "type": "Point", "coordinates": [-73.54, 45.501] }, "properties": { "id": "e", "integer_id": 5 } }
+                ]
+            }
+        }));
+        write("paths", &transition_capnp_data::serialization::path_collection::write_collection, json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.60, 45.50], [-73.58, 45.50], [-73.56, 45.50]] }, "properties": { "id": "p1", "integer_id": 1, "line_id": "line1", "nodes": ["a", "b", "c"], "segments": [0, 1] } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": [[-73.56, 45.501], [-73.54, 45.501]] }, "properties": { "id": "p2", "integer_id": 2, "line_id": "line2", "nodes": ["d", "e"], "segments": [0] } }
+                ]
+            }
+        }));
+        write("scenarios", &transition_capnp_data::serialization::scenario_collection::write_collection, json!({
+            "scenarios": [{ "id": "scenario", "services": ["service"] }]
+        }));
+        write("services", &transition_capnp_data::serialization::service_collection::write_collection, json!({
+            "services": [{ "id": "service" }]
+        }));
+        write("lines", &transition_capnp_data::serialization::line_collection::write_collection, json!({
+            "lines": [
+                { "id": "line1", "agency_id": "agency", "mode": "bus" },
+                { "id": "line2", "agency_id": "agency", "mode": "bus" }
+            ]
+        }));
+        let line = |line_uuid: &str, trips: serde_json::Value| json!({
+            "line": {
+                "id": line_uuid,
+                "agency_id": "agency",
+                "mode": "bus",
+                "scheduleByServiceId": {
+                    "service": {
+                        "id": format!("{}_schedule", line_uuid),
+                        "service_id": "service",
+                        "periods": [{ "period_shortname": "all_day", "start_at_hour": 6, "end_at_hour": 12, "trips": trips }]
+                    }
+                }
+            }
+        });
+        for line_object in [
+            line("line1", json!([
+                trip("t1", "p1", json!([28800, 29160, null]), json!([null, 29100, 29400])),
+                trip("t2", "p1", json!([30600, 30960, null]), json!([null, 30900, 31200]))
+            ])),
+            // u1 leaves d too early to transfer from t1
+            line("line2", json!([
+                trip("u1", "p2", json!([29500, null]), json!([null, 29800])),
+                trip("u2", "p2", json!([29700, null]), json!([null, 30000])),
+                trip("u3", "p2", json!([31500, null]), json!([null, 31800]))
+            ])),
+        ] {
+            transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &line_object).unwrap();
+        }
+
+        let networks = TransitNetworkCache::new(2);
+        let json_response = route_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "origin": [-73.6005, 45.50],
+            "destination": "e",
+            "departure_time_seconds": 28500
+        }));
+        assert_eq!(json_response["status"], "success");
+        let journey = &json_response["data"];
+        assert_eq!(journey["status"], "success");
+        assert_eq!(journey["arrival_time_seconds"], 30000);
+        assert_eq!(journey["number_of_transfers"], 1);
+        assert_eq!(journey["in_vehicle_time_seconds"], 600 + 300);
+        assert_eq!(journey["egress_travel_time_seconds"], 0);
+        assert_eq!(journey["walking_only_travel_time_seconds"], json!(null));
+        let steps : Vec<(&str, &str)> = journey["steps"].as_array().unwrap().iter().map(|step| {
+            (step["action"].as_str().unwrap(), step["trip_id"].as_str().or_else(|| step["type"].as_str()).unwrap())
+        }).collect();
+        assert_eq!(steps, vec![
+            ("walking", "access"),
+            ("boarding", "t1"),
+            ("unboarding", "t1"),
+            ("walking", "transfer"),
+            ("boarding", "u2"),
+            ("unboarding", "u2"),
+            ("walking", "egress")
+        ]);
+        assert_eq!(journey["total_travel_time_seconds"], 30000 - 28500);
+        assert_eq!(
+            journey["total_travel_time_seconds"].as_i64().unwrap(),
+            ["in_vehicle_time_seconds", "access_travel_time_seconds", "egress_travel_time_seconds", "transfer_walking_time_seconds", "waiting_time_seconds"].iter()
+                .map(|component| journey[*component].as_i64().unwrap()).sum::<i64>()
+        );
+
+        // a shorter min waiting time catches u1
+        let json_response = route_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "origin": [-73.6005, 45.50],
+            "destination": "e",
+            "departure_time_seconds": 28500,
+            "min_waiting_time_seconds": 0
+        }));
+        assert_eq!(json_response["data"]["arrival_time_seconds"], 29800);
+
+        // too short transfers
+        let json_response = route_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "origin": [-73.6005, 45.50],
+            "destination": "e",
+            "departure_time_seconds": 28500,
+            "max_transfer_travel_time_seconds": 30
+        }));
+        assert_eq!(json_response["data"]["status"], "no_routing_found");
+
+        let json_response = route_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "origin": "a",
+            "destination": "e",
+            "departure_time_range_seconds": [27000, 31000]
+        }));
+        let journeys = json_response["data"]["journeys"].as_array().unwrap();
+        assert_eq!(journeys.len(), 2);
+        assert_eq!(journeys[0]["departure_time_seconds"], 28800 - 180);
+        assert_eq!(journeys[0]["arrival_time_seconds"], 30000);
+        assert_eq!(journeys[1]["departure_time_seconds"], 30600 - 180);
+        assert_eq!(journeys[1]["arrival_time_seconds"], 31800);
+
+        let json_response = route_response(&config, &networks, json!({ "scenario_id": "scenario", "origin": "unknown", "destination": "e", "departure_time_seconds": 28500 }));
+        assert_eq!(json_response["status"], "fail");
+
+        // the network is loaded again once the cache changed
+        let routed_cache_directory_path = fs::canonicalize(&cache_directory_path).unwrap();
+        let network = networks.get(routed_cache_directory_path.to_str().unwrap(), "scenario").unwrap();
+        assert!(Arc::ptr_eq(&network, &networks.get(routed_cache_directory_path.to_str().unwrap(), "scenario").unwrap()));
+        // file times can be as coarse as the kernel clock tick
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        transition_capnp_data::serialization::line::write_object(lines_directory_path.to_str().unwrap(), &line("line2", json!([
+            trip("u3", "p2", json!([31500, null]), json!([null, 31800]))
+        ]))).unwrap();
+        let json_response = route_response(&config, &networks, json!({
+            "scenario_id": "scenario",
+            "origin": [-73.6005, 45.50],
+            "destination": "e",
+            "departure_time_seconds": 28500
+        }));
+        assert_eq!(json_response["data"]["arrival_time_seconds"], 31800);
+        assert!(!Arc::ptr_eq(&network, &networks.get(routed_cache_directory_path.to_str().unwrap(), "scenario").unwrap()));
+
+    }
+
+}
@@ -0,0 +1,165 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn trip(trip_uuid: &str, path_uuid: &str, departure_time_seconds: i64, arrival_time_seconds: i64) -> serde_json::Value {
+        json!({
+            "id": trip_uuid,
+            "path_id": path_uuid,
+            "departure_time_seconds": departure_time_seconds,
+            "arrival_time_seconds": arrival_time_seconds,
+            "block_id": "old_block",
+            "node_arrival_times_seconds": [null, arrival_time_seconds],
+            "node_departure_times_seconds": [departure_time_seconds, null],
+            "nodes_can_board": [true, false],
+            "nodes_can_unboard": [false, true]
+        })
+    }
+
+    fn blocks_response(config: &serde_json::Value, parameters: serde_json::Value) -> serde_json::Value {
+        let request = Request::fake_http(
+            "POST",
+            "/lines/blocks",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            parameters.to_string().into_bytes(),
+        );
+        let response = routers::vehicle_blocks_route(config, &request);
+        assert_eq!(response.status_code, 200);
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    #[test]
+    fn vehicle_blocks() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "vehicle_blocks",
+            "project_shortname"           : "test"
+        });
+
+        let cache_directory_path = Path::new("test").join("vehicle_blocks");
+        let lines_directory_path = cache_directory_path.join("lines");
+        fs::create_dir_all(&lines_directory_path).unwrap();
+
+        let mut file = fs::File::create(cache_directory_path.join("paths.capnpbin")).unwrap();
+        transition_capnp_data::serialization::path_collection::write_collection(&json!({
+            "paths": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "id": 1, "geometry": { "type": "LineString", "coordinates": [[-73.6, 45.5], [-73.5, 45.5]] }, "properties": { "id": "outbound", "integer_id": 1, "line_id": "a", "nodes": ["n1", "n2"], "segments": [0] } },
+                    { "type": "Feature", "id": 2, "geometry": { "type": "LineString", "coordinates": [[-73.5, 45.5], [-73.6, 45.5]] }, "properties": { "id": "inbound", "integer_id": 2, "line_id": "a", "nodes": ["n2", "n1"], "segments": [0] } },
+                    { "type": "Feature", "id": 3, "geometry": { "type": "LineString", "coordinates": [[-73.5, 45.5], [-73.6, 45.6]] }, "properties": { "id": "b_path", "integer_id": 3, "line_id": "b", "nodes": ["n2", "n3"], "segments": [0] } }
+                ]
+            }
+        }), &mut file).unwrap();
+        let mut file = fs::File::create(cache_directory_path.join("lines.capnpbin")).unwrap();
+        transition_capnp_data::serialization::line_collection::write_collection(&json!({
+            "lines": [
+                { "id": "a", "agency_id": "agency", "mode": "bus" },
+                { "id": "b", "agency_id": "agency", "mode": "bus" },
+                { "id": "c", "agency_id": "other_agency", "mode": "bus" }
+            ]
+        }), &mut file).unwrap();
+
+        let line = |line_uuid: &str, agency_uuid: &str, data: serde_json::Value, trips: serde_json::Value| json!({
+            "line": {
+                "id": line_uuid,
+                "agency_id": agency_uuid,
+                "mode": "bus",
+                "data": data,
+                "scheduleByServiceId": {
+                    "weekday": {
+                        "id": format!("{}_weekday", line_uuid),
+                        "service_id": "weekday",
+                        "periods": [
+                            { "period_shortname": "am_peak", "start_at_hour": 6, "end_at_hour": 7, "trips": trips },
+                            { "period_shortname": "midday", "start_at_hour": 7, "end_at_hour": 9, "trips": [] }
+                        ]
+                    }
+                }
+            }
+        });
+        let lines = [
+            line("a", "agency", json!({
+                "deadHeadTravelTimesBetweenPathsByPathId": {
+                    "outbound": { "outbound": 300, "inbound": 0 },
+                    "inbound": { "outbound": 0, "inbound": 300 }
+                }
+            }), json!([
+                trip("a1", "outbound", 21600, 22200),
+                trip("a2", "outbound", 22000, 22600),
+                trip("a3", "inbound", 22700, 23300)
+            ])),
+            // b starts where the outbound path ends
+            line("b", "agency", json!({}), json!([trip("b1", "b_path", 22900, 23400)])),
+            line("c", "other_agency", json!({}), json!([trip("c1", "b_path", 22900, 23400)])),
+        ];
+        // b is written by service, the blocks keep its layout
+        for line in lines.iter() {
+            let options = transition_capnp_data::serialization::line::LineWriteOptions { trips_by_service: line["line"]["id"] == "b", ..Default::default() };
+            transition_capnp_data::serialization::line::write_object_with_options(lines_directory_path.to_str().unwrap(), line, &options).unwrap();
+        }
+
+        // a2 cannot reach a3 in time with the layover
+        let json_response = blocks_response(&config, json!({ "line_id": "a", "min_layover_seconds": 300 }));
+        let service = &json_response["data"]["services"][0];
+        assert_eq!(json_response["data"]["lines_ids"], json!(["a"]));
+        assert_eq!(service["vehicles_count"], 2);
+        assert_eq!(service["blocks"][0]["trips_ids"], json!(["a1", "a3"]));
+        assert_eq!(service["blocks"][0]["start_time_seconds"], 21600);
+        assert_eq!(service["blocks"][0]["end_time_seconds"], 23300);
+        assert_eq!(service["blocks"][1]["trips_ids"], json!(["a2"]));
+        assert_eq!(service["peak_vehicles_by_period"], json!([
+            { "period_shortname": "am_peak", "start_at_hour": 6.0, "end_at_hour": 7.0, "vehicles_count": 2 },
+            { "period_shortname": "midday", "start_at_hour": 7.0, "end_at_hour": 9.0, "vehicles_count": 0 }
+        ]));
+        // nothing is written without the write parameter
+        let line_a = transition_capnp_data::serialization::line::read_object(&String::from("a"), lines_directory_path.to_str().unwrap()).unwrap();
+        assert_eq!(line_a["line"]["scheduleByServiceId"]["weekday"]["periods"][0]["trips"][0]["block_id"], "old_block");
+
+        // without layover, a2 can also reach a3, but a1 and a2 still overlap
+        let json_response = blocks_response(&config, json!({ "line_id": "a", "min_layover_seconds": 0 }));
+        assert_eq!(json_response["data"]["services"][0]["vehicles_count"], 2);
+
+        // interlined with b, the vehicle of a2 continues on b1, c is from another agency
+        let json_response = blocks_response(&config, json!({ "line_id": "a", "min_layover_seconds": 300, "interlining": true, "write": true }));
+        let service = &json_response["data"]["services"][0];
+        assert_eq!(json_response["data"]["lines_ids"], json!(["a", "b"]));
+        assert_eq!(service["vehicles_count"], 2);
+        assert_eq!(service["blocks"][0]["trips_ids"], json!(["a1", "a3"]));
+        assert_eq!(service["blocks"][1]["trips_ids"], json!(["a2", "b1"]));
+        assert_eq!(service["blocks"][1]["lines_ids"], json!(["a", "b"]));
+
+        let line_a = transition_capnp_data::serialization::line::read_object(&String::from("a"), lines_directory_path.to_str().unwrap()).unwrap();
+        let line_b = transition_capnp_data::serialization::line::read_object(&String::from("b"), lines_directory_path.to_str().unwrap()).unwrap();
+        let a_trips = &line_a["line"]["scheduleByServiceId"]["weekday"]["periods"][0]["trips"];
+        assert_eq!(a_trips[0]["block_id"], service["blocks"][0]["id"]);
+        assert_eq!(a_trips[2]["block_id"], service["blocks"][0]["id"]);
+        assert_eq!(a_trips[1]["block_id"], service["blocks"][1]["id"]);
+        assert_eq!(line_b["line"]["scheduleByServiceId"]["weekday"]["periods"][0]["trips"][0]["block_id"], service["blocks"][1]["id"]);
+        assert!(lines_directory_path.join("line_b_trips_weekday.capnpbin").exists());
+        assert!(!lines_directory_path.join("line_a_trips_weekday.capnpbin").exists());
+
+    }
+
+}
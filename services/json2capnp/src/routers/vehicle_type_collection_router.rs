@@ -0,0 +1,146 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    #[test]
+    fn vehicle_type_collection() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "vehicle_types",
+            "project_shortname"           : "test"
+        });
+
+        let data = json!({
+            "cache_directory_path": "vehicle_types",
+            "vehicle_types": [
+                {
+                    "id": "articulated_bus",
+                    "internal_id": "A18",
+                    "name": "Articulated bus",
+                    "mode": "bus",
+                    "seated_capacity": 48,
+                    "standing_capacity": 67,
+                    "length_meters": 18.3,
+                    "energy_type": "hybrid",
+                    "cost_per_km": 2.15,
+                    "cost_per_hour": 98.5,
+                    "description": null,
+                    "is_frozen": true,
+                    "data": { "foo": "bar" }
+                },
+                {
+                    "id": "minibus"
+                }
+            ]
+        });
+
+        let compare_data = json!([
+            {
+                "id": "articulated_bus",
+                "internal_id": "A18",
+                "name": "Articulated bus",
+                "mode": "bus",
+                "seated_capacity": 48,
+                "standing_capacity": 67,
+                "length_meters": 18.3,
+                "energy_type": "hybrid",
+                "cost_per_km": 2.15,
+                "cost_per_hour": 98.5,
+                "description": null,
+                "is_frozen": true,
+                "data": { "foo": "bar" }
+            },
+            {
+                "id": "minibus",
+                "internal_id": null,
+                "name": null,
+                "mode": null,
+                "seated_capacity": null,
+                "standing_capacity": null,
+                "length_meters": null,
+                "energy_type": null,
+                "cost_per_km": null,
+                "cost_per_hour": null,
+                "description": null,
+                "is_frozen": null,
+                "data": {}
+            }
+        ]);
+
+        let request = Request::fake_http(
+            "POST",
+            "/vehicleTypes",
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.to_string().as_bytes().to_vec(),
+        );
+
+        let response = routers::write_collection_route(
+            "vehicleTypes",
+            "vehicleTypes",
+            &config,
+            &transition_capnp_data::serialization::vehicle_type_collection::write_collection,
+            &request,
+        );
+        assert_eq!(response.status_code, 200);
+
+        let response = routers::read_collection_route(
+            "vehicleTypes",
+            "vehicleTypes",
+            &config,
+            &transition_capnp_data::serialization::vehicle_type_collection::read_collection,
+        );
+
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        let json_response : serde_json::Value = serde_json::from_str(buffer.as_str()).unwrap();
+
+        assert_eq!(response.status_code, 200);
+        assert_eq!(json_response["data"]["vehicle_types"], compare_data);
+
+        let vehicle_types = transition_capnp_data::vehicle_types::VehicleTypes::from_collection_json(&json_response["data"]);
+        let articulated_bus = vehicle_types.get("articulated_bus").unwrap();
+        assert_eq!(articulated_bus.total_capacity(), Some(115));
+        assert_eq!(vehicle_types.get("minibus").unwrap().total_capacity(), None);
+
+        // trips without explicit capacity use their vehicle type, then the one of their period
+        let mut line_json = json!({
+            "scheduleByServiceId": {
+                "weekday": {
+                    "periods": [{
+                        "vehicle_type_id": "articulated_bus",
+                        "trips": [
+                            { "id": "explicit", "seated_capacity": 20, "total_capacity": 50, "vehicle_type_id": null },
+                            { "id": "from_period", "seated_capacity": null, "total_capacity": null, "vehicle_type_id": null },
+                            { "id": "from_trip", "seated_capacity": null, "total_capacity": null, "vehicle_type_id": "minibus" }
+                        ]
+                    }]
+                }
+            }
+        });
+        assert_eq!(vehicle_types.resolve_line_capacities(&mut line_json), 1);
+        let trips = &line_json["scheduleByServiceId"]["weekday"]["periods"][0]["trips"];
+        assert_eq!(trips[0]["total_capacity"], 50);
+        assert_eq!(trips[1]["seated_capacity"], 48);
+        assert_eq!(trips[1]["total_capacity"], 115);
+        assert_eq!(trips[2]["total_capacity"], json!(null));
+
+    }
+}
@@ -0,0 +1,126 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+#[cfg(test)]
+mod tests {
+
+    use crate::routers;
+    use std::path::{Path};
+    use std::fs;
+    use rouille::Request;
+    use pretty_assertions::{assert_eq};
+
+    fn json_request(url: &str, data: &serde_json::Value) -> Request {
+        Request::fake_http(
+            "POST",
+            url,
+            vec![(
+                "Content-Type".to_owned(),
+                "application/json; charset=utf-8".to_owned(),
+            )],
+            data.to_string().into_bytes(),
+        )
+    }
+
+    fn response_json(response: rouille::Response) -> serde_json::Value {
+        assert_eq!(response.status_code, 200);
+        let (mut res_data, _) = response.data.into_reader_and_size();
+        let mut buffer = String::new();
+        res_data.read_to_string(&mut buffer).unwrap();
+        serde_json::from_str(buffer.as_str()).unwrap()
+    }
+
+    #[test]
+    fn zone_collection() {
+
+        let config: serde_json::Value = json!({
+            "project_cache_directory_path": fs::canonicalize(Path::new("test")).unwrap(),
+            "custom_subdirectory_path"    : "zones",
+            "project_shortname"           : "test"
+        });
+
+        // west and east squares sharing the -73.5 meridian, the east one with a hole
+        let zones = json!({
+            "cache_directory_path": "zones",
+            "zones": {
+                "type": "FeatureCollection",
+                "features": [
+                    {
+                        "type": "Feature",
+                        "id": 1,
+                        "geometry": { "type": "Polygon", "coordinates": [[[-73.6, 45.4], [-73.5, 45.4], [-73.5, 45.5], [-73.6, 45.5], [-73.6, 45.4]]] },
+                        "properties": { "id": "west", "integer_id": 1, "internal_id": "W", "shortname": "w", "name": "West", "data_source_id": null, "data": { "population": 1000 } }
+                    },
+                    {
+                        "type": "Feature",
+                        "id": 2,
+                        "geometry": { "type": "MultiPolygon", "coordinates": [
+                            [[[-73.5, 45.4], [-73.4, 45.4], [-73.4, 45.5], [-73.5, 45.5], [-73.5, 45.4]], [[-73.46, 45.44], [-73.44, 45.44], [-73.44, 45.46], [-73.46, 45.46], [-73.46, 45.44]]],
+                            [[[-73.3, 45.4], [-73.2, 45.4], [-73.2, 45.5], [-73.3, 45.5], [-73.3, 45.4]]]
+                        ] },
+                        "properties": { "id": "east", "integer_id": 2, "internal_id": null, "shortname": null, "name": "East", "data_source_id": "census", "data": {} }
+                    }
+                ]
+            }
+        });
+        let response = routers::write_collection_route("zones", "zones", &config, &transition_capnp_data::serialization::zone_collection::write_collection, &json_request("/zones", &zones));
+        assert_eq!(response_json(response)["status"], "success");
+
+        let response = routers::read_collection_route("zones", "zones", &config, &transition_capnp_data::serialization::zone_collection::read_collection);
+        let zones_read = response_json(response)["data"]["zones"].clone();
+        assert_eq!(zones_read["features"][0]["properties"], zones["zones"]["features"][0]["properties"]);
+        assert_eq!(zones_read["features"][1]["properties"], zones["zones"]["features"][1]["properties"]);
+        assert_eq!(zones_read["features"][0]["geometry"], zones["zones"]["features"][0]["geometry"]);
+        assert_eq!(zones_read["features"][1]["geometry"], zones["zones"]["features"][1]["geometry"]);
+
+        let nodes = json!({
+            "nodes": {
+                "type": "FeatureCollection",
+                "features": [
+                    { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.55, 45.45] }, "id": 1, "properties": { "id": "in_west", "integer_id": 1 } },
+                    { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.45, 45.45] }, "id": 2, "properties": { "id": "in_east_hole", "integer_id": 2 } },
+                    { "type": "Feature", "geometry": { "type": "Point", "coordinates": [-73.25, 45.45] }, "id": 3, "properties": { "id": "in_east_island", "integer_id": 3 } }
+                ]
+            }
+        });
+        let mut file = fs::File::create(Path::new("test").join("zones").join("nodes.capnpbin")).unwrap();
+        transition_capnp_data::serialization::node_collection::write_collection(&nodes, &mut file).unwrap();
+        let od_trips = json!({
+            "odTrips": [
+                { "id": "t1", "origin_geography": { "type": "Point", "coordinates": [-73.55, 45.45] }, "destination_geography": { "type": "Point", "coordinates": [-73.25, 45.45] }, "expansion_factor": 2.5 },
+                { "id": "t2", "origin_geography": { "type": "Point", "coordinates": [-73.55, 45.45] }, "destination_geography": { "type": "Point", "coordinates": [-72.0, 45.45] } }
+            ]
+        });
+        let mut file = fs::File::create(Path::new("test").join("zones").join("odTrips.capnpbin")).unwrap();
+        transition_capnp_data::serialization::od_trip_collection::write_collection(&od_trips, &mut file).unwrap();
+
+        let query = json!({
+            "points": [{ "id": "border", "coordinates": [-73.5, 45.45] }, { "id": "outside", "coordinates": [-74.0, 45.45] }],
+            "nodes": true,
+            "od_trips": true
+        });
+        let json_response = response_json(routers::locate_in_zones_route(&config, &json_request("/zones/locate", &query)));
+        assert_eq!(json_response["status"], "success");
+        let data = &json_response["data"];
+        assert_eq!(data["points"], json!([{ "id": "border", "zones": ["west", "east"] }, { "id": "outside", "zones": [] }]));
+        assert_eq!(data["nodes"], json!([
+            { "id": "in_west", "zones": ["west"] },
+            { "id": "in_east_hole", "zones": [] },
+            { "id": "in_east_island", "zones": ["east"] }
+        ]));
+        assert_eq!(data["od_trips"], json!([
+            { "id": "t1", "origin_zones": ["west"], "destination_zones": ["east"] },
+            { "id": "t2", "origin_zones": ["west"], "destination_zones": [] }
+        ]));
+        assert_eq!(data["zones"], json!([
+            { "id": "west", "integer_id": 1, "points_count": 1, "nodes_count": 1, "od_trips_origins_count": 2, "od_trips_destinations_count": 0, "od_trips_origins_weight": 3.5, "od_trips_destinations_weight": 0.0 },
+            { "id": "east", "integer_id": 2, "points_count": 1, "nodes_count": 1, "od_trips_origins_count": 0, "od_trips_destinations_count": 1, "od_trips_origins_weight": 0.0, "od_trips_destinations_weight": 2.5 }
+        ]));
+
+    }
+}
@@ -0,0 +1,228 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Nodes reachable from a location by transit and their isochrone polygons */
+
+use geo::{unary_union, Area, Buffer, MultiPolygon, Point};
+use crate::transit_routing::{Place, RoutingParameters, TransitNetwork, TransitNetworkCache};
+use crate::utils::EARTH_RADIUS_METERS;
+
+/// Node reached from the location, with the departure time giving the
+/// shortest travel time
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ReachableNode {
+    pub node: usize,
+    pub travel_time_seconds: i64,
+    pub departure_time_seconds: i64,
+}
+
+// Equirectangular projection in meters around a center, precise enough at
+// the scale of the walking distances around the reached nodes
+struct LocalProjection {
+    longitude: f64,
+    latitude: f64,
+    meters_per_degree_longitude: f64,
+    meters_per_degree_latitude: f64,
+}
+
+impl LocalProjection {
+
+    fn new(longitude: f64, latitude: f64) -> Self {
+        let meters_per_degree_latitude = EARTH_RADIUS_METERS * std::f64::consts::PI / 180.0;
+        LocalProjection {
+            longitude,
+            latitude,
+            meters_per_degree_longitude: meters_per_degree_latitude * latitude.to_radians().cos(),
+            meters_per_degree_latitude,
+        }
+    }
+
+    fn project(&self, longitude: f64, latitude: f64) -> Point<f64> {
+        Point::new((longitude - self.longitude) * self.meters_per_degree_longitude, (latitude - self.latitude) * self.meters_per_degree_latitude)
+    }
+
+    fn unproject(&self, x: f64, y: f64) -> [f64; 2] {
+        [self.longitude + x / self.meters_per_degree_longitude, self.latitude + y / self.meters_per_degree_latitude]
+    }
+}
+
+fn round_coordinate(coordinate: f64) -> f64 {
+    (coordinate * 1e6).round() / 1e6
+}
+
+/// Shortest travel time to each node reached from the location, leaving at
+/// the start time, or at any time between the start and end times. Every
+/// departure catching a vehicle in the time range is scanned. Nodes are
+/// sorted by travel time.
+pub fn reachable_nodes(
+    network: &TransitNetwork,
+    location: &Place,
+    start_time_seconds: i64,
+    end_time_seconds: i64,
+    parameters: &RoutingParameters,
+) -> Result<Vec<ReachableNode>, capnp::Error> {
+    parameters.validate()?;
+    if end_time_seconds < start_time_seconds {
+        return Err(capnp::Error::failed(format!("Invalid time range: {} is before {}", end_time_seconds, start_time_seconds)));
+    }
+    let access_nodes = network.access_nodes(location, parameters)?;
+
+    let mut best_nodes : Vec<Option<ReachableNode>> = vec![None; network.nodes_count()];
+    // nodes reached by walking only, whatever the departure time
+    for (node, walking_time_seconds, _) in access_nodes.iter() {
+        best_nodes[*node] = Some(ReachableNode { node: *node, travel_time_seconds: *walking_time_seconds, departure_time_seconds: start_time_seconds });
+    }
+
+    let mut departure_times = network.access_departure_times(&access_nodes, start_time_seconds, end_time_seconds, parameters);
+    if departure_times.first() != Some(&start_time_seconds) {
+        departure_times.insert(0, start_time_seconds);
+    }
+    for departure_time_seconds in departure_times {
+        let scan = network.scan(&access_nodes, &[], departure_time_seconds, parameters);
+        for (node, arrival_time) in scan.arrival_times().into_iter().enumerate() {
+            let travel_time_seconds = match arrival_time {
+                Some(arrival_time) if arrival_time - departure_time_seconds <= parameters.max_total_travel_time_seconds => arrival_time - departure_time_seconds,
+                _ => continue
+            };
+            if best_nodes[node].is_none_or(|best_node| travel_time_seconds < best_node.travel_time_seconds) {
+                best_nodes[node] = Some(ReachableNode { node, travel_time_seconds, departure_time_seconds });
+            }
+        }
+    }
+
+    let mut nodes : Vec<ReachableNode> = best_nodes.into_iter().flatten().collect();
+    nodes.sort_by_key(|node| (node.travel_time_seconds, node.node));
+    Ok(nodes)
+}
+
+/// Area reachable within a duration: the walking circles around the location
+/// and around each reached node, with the remaining time, limited to the max
+/// access and egress travel time, merged in a multipolygon in a local
+/// projection in meters.
+fn isochrone_polygon(
+    projection: &LocalProjection,
+    network: &TransitNetwork,
+    nodes: &[ReachableNode],
+    duration_seconds: i64,
+    parameters: &RoutingParameters,
+) -> MultiPolygon<f64> {
+    let walking_radius_meters = |walking_time_seconds: i64| walking_time_seconds.min(parameters.max_access_egress_travel_time_seconds) as f64 * parameters.walking_speed_meters_per_second;
+
+    let mut circles : Vec<MultiPolygon<f64>> = vec![projection.project(projection.longitude, projection.latitude).buffer(walking_radius_meters(duration_seconds))];
+    for reachable_node in nodes.iter().filter(|reachable_node| reachable_node.travel_time_seconds < duration_seconds) {
+        let node = &network.node_index().nodes()[reachable_node.node];
+        circles.push(projection.project(node.longitude, node.latitude).buffer(walking_radius_meters(duration_seconds - reachable_node.travel_time_seconds)));
+    }
+    unary_union(circles.iter())
+}
+
+fn multipolygon_to_geojson(projection: &LocalProjection, multipolygon: &MultiPolygon<f64>) -> serde_json::Value {
+    let ring_coordinates = |ring: &geo::LineString<f64>| -> Vec<[f64; 2]> {
+        ring.coords().map(|coord| {
+            let [longitude, latitude] = projection.unproject(coord.x, coord.y);
+            [round_coordinate(longitude), round_coordinate(latitude)]
+        }).collect()
+    };
+    json!({
+        "type": "MultiPolygon",
+        "coordinates": multipolygon.iter().map(|polygon| {
+            std::iter::once(polygon.exterior()).chain(polygon.interiors().iter()).map(ring_coordinates).collect::<Vec<Vec<[f64; 2]>>>()
+        }).collect::<Vec<Vec<Vec<[f64; 2]>>>>()
+    })
+}
+
+/// Reachable nodes and isochrone polygons from a location. There is one
+/// polygon feature per duration, each containing the area reachable within
+/// its duration, with the area in square meters.
+pub fn accessibility_map(
+    network: &TransitNetwork,
+    location: &Place,
+    start_time_seconds: i64,
+    end_time_seconds: i64,
+    durations_seconds: &[i64],
+    parameters: &RoutingParameters,
+) -> Result<serde_json::Value, capnp::Error> {
+    for duration_seconds in durations_seconds {
+        if *duration_seconds <= 0 || *duration_seconds > parameters.max_total_travel_time_seconds {
+            return Err(capnp::Error::failed(format!("Invalid duration {}, expected between 1 and the max total travel time {}", duration_seconds, parameters.max_total_travel_time_seconds)));
+        }
+    }
+    let (longitude, latitude) = network.place_coordinates(location)?;
+    let nodes = reachable_nodes(network, location, start_time_seconds, end_time_seconds, parameters)?;
+    let projection = LocalProjection::new(longitude, latitude);
+
+    let mut sorted_durations_seconds = durations_seconds.to_vec();
+    sorted_durations_seconds.sort_unstable();
+    sorted_durations_seconds.dedup();
+    let features : Vec<serde_json::Value> = sorted_durations_seconds.iter().map(|duration_seconds| {
+        let polygon = isochrone_polygon(&projection, network, &nodes, *duration_seconds, parameters);
+        json!({
+            "type": "Feature",
+            "geometry": multipolygon_to_geojson(&projection, &polygon),
+            "properties": {
+                "duration_seconds": duration_seconds,
+                "area_square_meters": polygon.unsigned_area().round(),
+                "nodes_count": nodes.iter().filter(|node| node.travel_time_seconds <= *duration_seconds).count()
+            }
+        })
+    }).collect();
+
+    Ok(json!({
+        "location": [longitude, latitude],
+        "nodes": nodes.iter().map(|node| json!({
+            "id": network.node_index().nodes()[node.node].uuid,
+            "travel_time_seconds": node.travel_time_seconds,
+            "departure_time_seconds": node.departure_time_seconds
+        })).collect::<Vec<serde_json::Value>>(),
+        "polygons": {
+            "type": "FeatureCollection",
+            "features": features
+        }
+    }))
+}
+
+/// Compute accessibility maps on the network of a cache: `scenario_id`, a
+/// `location` place or a `locations` array of places, either a
+/// `departure_time_seconds` or a `departure_time_range_seconds` [start, end],
+/// the `durations_seconds` of the polygons, by default the max total travel
+/// time, and the routing parameters. The network is loaded once for all the
+/// locations, which are returned in `maps`. See
+/// accessibility_map_query_with_networks to answer many queries.
+pub fn accessibility_map_query(cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    accessibility_map_query_with_networks(&TransitNetworkCache::new(0), cache_directory_path, query)
+}
+
+/// Compute accessibility maps like accessibility_map_query, on a network of
+/// the cache
+pub fn accessibility_map_query_with_networks(networks: &TransitNetworkCache, cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    let scenario_uuid = query["scenario_id"].as_str().ok_or_else(|| capnp::Error::failed(String::from("Missing scenario_id")))?;
+    let parameters = RoutingParameters::from_json(query)?;
+    let (start_time_seconds, end_time_seconds) = match (query["departure_time_seconds"].as_i64(), query["departure_time_range_seconds"].as_array()) {
+        (_, Some(range)) if range.len() == 2 && range.iter().all(|time| time.is_i64()) => (range[0].as_i64().unwrap(), range[1].as_i64().unwrap()),
+        (Some(departure_time_seconds), None) => (departure_time_seconds, departure_time_seconds),
+        _ => return Err(capnp::Error::failed(String::from("Missing departure_time_seconds or invalid departure_time_range_seconds")))
+    };
+    let durations_seconds : Vec<i64> = match query["durations_seconds"].as_array() {
+        Some(durations) => durations.iter().map(|duration| duration.as_i64().ok_or_else(|| capnp::Error::failed(format!("Invalid duration {}", duration)))).collect::<Result<Vec<i64>, capnp::Error>>()?,
+        None => vec![parameters.max_total_travel_time_seconds]
+    };
+    let locations = match query["locations"].as_array() {
+        Some(locations) => locations.iter().map(Place::from_json).collect::<Result<Vec<Place>, capnp::Error>>()?,
+        None => vec![Place::from_json(&query["location"])?]
+    };
+
+    let network = networks.get(cache_directory_path, scenario_uuid)?;
+    let maps = locations.iter()
+        .map(|location| accessibility_map(&network, location, start_time_seconds, end_time_seconds, &durations_seconds, &parameters))
+        .collect::<Result<Vec<serde_json::Value>, capnp::Error>>()?;
+    if query["locations"].is_array() {
+        Ok(json!({ "maps": maps }))
+    } else {
+        Ok(maps.into_iter().next().unwrap())
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Helpers to locate, read and write the capnp files of a cache directory. The
+ * cache directory can be any cache store location, see cache_store::open */
+
+use crate::cache_store;
+
+pub fn collection_file_path(cache_directory_path: &str, cache_file_name: &str) -> String {
+    format!("{}/{}.capnpbin", cache_directory_path, cache_file_name)
+}
+
+fn collection_file_name(cache_file_name: &str) -> String {
+    format!("{}.capnpbin", cache_file_name)
+}
+
+/// Read a collection file, None if the cache has no such collection
+pub fn read_optional_collection_file(
+    cache_directory_path: &str,
+    cache_file_name: &str,
+    read_fn: &dyn Fn(&mut dyn std::io::Read) -> Result<serde_json::Value, capnp::Error>,
+) -> Result<Option<serde_json::Value>, capnp::Error> {
+    let store = cache_store::open(cache_directory_path)?;
+    cache_store::read_message(store.as_ref(), &collection_file_name(cache_file_name), read_fn)
+}
+
+pub fn read_collection_file(
+    cache_directory_path: &str,
+    cache_file_name: &str,
+    read_fn: &dyn Fn(&mut dyn std::io::Read) -> Result<serde_json::Value, capnp::Error>,
+) -> Result<serde_json::Value, capnp::Error> {
+    read_optional_collection_file(cache_directory_path, cache_file_name, read_fn)?
+        .ok_or_else(|| capnp::Error::failed(format!("Cannot open {}: file not found", collection_file_path(cache_directory_path, cache_file_name))))
+}
+
+pub fn write_collection_file(
+    cache_directory_path: &str,
+    cache_file_name: &str,
+    json: &serde_json::Value,
+    write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>,
+) -> Result<(), capnp::Error> {
+    let store = cache_store::open(cache_directory_path)?;
+    cache_store::write_message(store.as_ref(), &collection_file_name(cache_file_name), json, write_fn)
+}
+
+/// Read a capnp file from its path, which can be in any cache store location
+pub fn read_file(
+    file_path: &str,
+    read_fn: &dyn Fn(&mut dyn std::io::Read) -> Result<serde_json::Value, capnp::Error>,
+) -> Result<serde_json::Value, capnp::Error> {
+    let (store, key) = cache_store::open_file(file_path)?;
+    cache_store::read_message(store.as_ref(), &key, read_fn)?
+        .ok_or_else(|| capnp::Error::failed(format!("Cannot open {}: file not found", file_path)))
+}
+
+/// Write a capnp file to its path, which can be in any cache store location
+pub fn write_file(
+    file_path: &str,
+    json: &serde_json::Value,
+    write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>,
+) -> Result<(), capnp::Error> {
+    let (store, key) = cache_store::open_file(file_path)?;
+    cache_store::write_message(store.as_ref(), &key, json, write_fn)
+}
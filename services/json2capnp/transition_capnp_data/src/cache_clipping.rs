@@ -0,0 +1,350 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Clipping of a cache to a study area: the nodes in the area, the paths cut
+ * to their nodes in the area and the trips trimmed to these nodes */
+
+use std::collections::{HashMap, HashSet};
+use geo::{Intersects, MultiPolygon, Point, Rect};
+use crate::cache::{read_collection_file, read_optional_collection_file, write_collection_file};
+use crate::cache_store;
+use crate::path_analytics::validate_segments;
+use crate::scenario_extraction::retain_transferable_nodes;
+use crate::serialization::{agency_collection, line, line_collection, node, node_collection, path_collection};
+use crate::zone_lookup::json_geometry_to_multipolygon;
+
+// Collections copied as is, when the source cache has them
+const COPIED_COLLECTIONS: [&str; 4] = ["services", "scenarios", "vehicleTypes", "fares"];
+
+// Path data attributes with one value per node or per segment
+const PATH_NODES_DATA: [&str; 4] = ["nodeTypes", "waypoints", "waypointTypes", "dwellTimeSeconds"];
+const PATH_SEGMENTS_DATA: [&str; 1] = ["segments"];
+// Path data attributes computed over the whole path, see the
+// PathGeographyGenerator of transition-common
+const PATH_TOTALS_DATA: [&str; 14] = [
+    "totalDistanceMeters", "totalDwellTimeSeconds", "travelTimeWithoutDwellTimesSeconds",
+    "operatingTimeWithoutLayoverTimeSeconds", "operatingTimeWithLayoverTimeSeconds", "layoverTimeSeconds",
+    "totalTravelTimeWithReturnBackSeconds", "averageSpeedWithoutDwellTimesMetersPerSecond", "operatingSpeedMetersPerSecond",
+    "operatingSpeedWithLayoverMetersPerSecond", "birdDistanceBetweenTerminals", "directRouteBetweenTerminalsDistanceMeters",
+    "directRouteBetweenTerminalsTravelTimeSeconds", "variables"
+];
+
+/// Area of a json bbox, `{ "bbox": [minLon, minLat, maxLon, maxLat] }`, or
+/// of a geojson Polygon or MultiPolygon geometry or feature
+pub fn clip_area_from_json(json: &serde_json::Value) -> Result<MultiPolygon<f64>, capnp::Error> {
+    if let Some(bbox) = json["bbox"].as_array() {
+        let bounds : Vec<f64> = bbox.iter().filter_map(|value| value.as_f64()).collect();
+        if bounds.len() != 4 {
+            return Err(capnp::Error::failed(format!("Invalid bbox, expected [minLon, minLat, maxLon, maxLat]: {}", json["bbox"])));
+        }
+        let rect = Rect::new((bounds[0], bounds[1]), (bounds[2], bounds[3]));
+        return Ok(MultiPolygon::new(vec![rect.to_polygon()]));
+    }
+    let geometry = if json["type"] == "Feature" { &json["geometry"] } else { json };
+    json_geometry_to_multipolygon(geometry).ok_or_else(|| capnp::Error::failed(String::from("The clip area must be a bbox or a Polygon or MultiPolygon geometry")))
+}
+
+// Longest run of consecutive nodes in the area, as the indices of its first
+// and last nodes, None if no two consecutive nodes are in the area, with the
+// number of runs of at least two nodes
+fn longest_run(nodes_in_area: &[bool]) -> (Option<(usize, usize)>, usize) {
+    let mut runs : Vec<(usize, usize)> = Vec::new();
+    let mut start : Option<usize> = None;
+    for (index, is_in_area) in nodes_in_area.iter().chain(std::iter::once(&false)).enumerate() {
+        match (start, *is_in_area) {
+            (None, true) => start = Some(index),
+            (Some(run_start), false) => {
+                if index - run_start >= 2 {
+                    runs.push((run_start, index - 1));
+                }
+                start = None;
+            },
+            _ => ()
+        }
+    }
+    let runs_count = runs.len();
+    // the first of the longest runs
+    let longest = runs.into_iter().fold(None, |longest : Option<(usize, usize)>, run| match longest {
+        Some(longest) if longest.1 - longest.0 >= run.1 - run.0 => Some(longest),
+        _ => Some(run)
+    });
+    (longest, runs_count)
+}
+
+fn slice_array(json: &serde_json::Value, start: usize, end: usize, expected_len: usize) -> Option<serde_json::Value> {
+    let values = json.as_array()?;
+    if values.len() != expected_len {
+        return None;
+    }
+    Some(json!(values[start..end]))
+}
+
+// Replace the totals of the path data with the ones of its segments and
+// dwell times, each segment with the dwell time at its end node like in
+// transition-common. The totals which need the routing or the layover cannot
+// be recomputed and are removed, like all of them when a segment has no
+// distance or travel time.
+fn recompute_path_totals(data: &mut serde_json::Value) {
+    let data = match data.as_object_mut() {
+        Some(data) => data,
+        None => return
+    };
+    for attribute in PATH_TOTALS_DATA.iter() {
+        data.remove(*attribute);
+    }
+    let segments = data.get("segments").and_then(|segments| segments.as_array()).cloned().unwrap_or_default();
+    let dwell_times : Option<Vec<f64>> = data.get("dwellTimeSeconds").and_then(|dwell_times| dwell_times.as_array())
+        .filter(|dwell_times| !segments.is_empty() && dwell_times.len() == segments.len() + 1)
+        .and_then(|dwell_times| dwell_times[1..].iter().map(|dwell_time| dwell_time.as_f64()).collect());
+    let distances : Option<Vec<f64>> = segments.iter().map(|segment| segment["distanceMeters"].as_f64()).collect();
+    let travel_times : Option<Vec<f64>> = segments.iter().map(|segment| segment["travelTimeSeconds"].as_f64()).collect();
+    if let (Some(dwell_times), Some(distances), Some(travel_times)) = (dwell_times, distances, travel_times) {
+        let total_distance_meters : f64 = distances.iter().sum();
+        let total_dwell_time_seconds : f64 = dwell_times.iter().sum();
+        let operating_time_seconds = travel_times.iter().sum::<f64>() + total_dwell_time_seconds;
+        data.insert(String::from("totalDistanceMeters"), json!(total_distance_meters));
+        data.insert(String::from("totalDwellTimeSeconds"), json!(total_dwell_time_seconds));
+        data.insert(String::from("operatingTimeWithoutLayoverTimeSeconds"), json!(operating_time_seconds));
+        if operating_time_seconds > 0.0 {
+            data.insert(String::from("operatingSpeedMetersPerSecond"), json!((total_distance_meters / operating_time_seconds * 100.0).round() / 100.0));
+        }
+    }
+}
+
+// Cut the path to its nodes first..=last, with the geometry between them
+fn clip_path(path: &mut serde_json::Value, first: usize, last: usize) {
+    let nodes_count = path["properties"]["nodes"].as_array().map_or(0, |nodes| nodes.len());
+    let segments : Vec<i64> = path["properties"]["segments"].as_array().map(|segments| segments.iter().filter_map(|segment| segment.as_i64()).collect()).unwrap_or_default();
+    let coordinates_count = path["geometry"]["coordinates"].as_array().map_or(0, |coordinates| coordinates.len());
+    let first_coordinate = segments[first] as usize;
+    // the last node is at the last coordinate
+    let last_coordinate = if last == nodes_count - 1 { coordinates_count - 1 } else { segments[last] as usize };
+
+    let properties = &mut path["properties"];
+    let nodes = slice_array(&properties["nodes"], first, last + 1, nodes_count).unwrap();
+    let node_uuids : HashSet<&str> = nodes.as_array().unwrap().iter().filter_map(|node_uuid| node_uuid.as_str()).collect();
+    properties["stops"] = json!(properties["stops"].as_array().map(|stops| stops.iter().filter(|stop| stop.as_str().is_some_and(|stop| node_uuids.contains(stop))).cloned().collect::<Vec<serde_json::Value>>()).unwrap_or_default());
+    properties["segments"] = json!(segments[first..last].iter().map(|segment| segment - first_coordinate as i64).collect::<Vec<i64>>());
+    properties["nodes"] = nodes;
+    for attribute in PATH_NODES_DATA.iter() {
+        if let Some(values) = slice_array(&properties["data"][*attribute], first, last + 1, nodes_count) {
+            properties["data"][*attribute] = values;
+        }
+    }
+    for attribute in PATH_SEGMENTS_DATA.iter() {
+        if let Some(values) = slice_array(&properties["data"][*attribute], first, last, nodes_count - 1) {
+            properties["data"][*attribute] = values;
+        }
+    }
+    recompute_path_totals(&mut properties["data"]);
+    path["geometry"]["coordinates"] = slice_array(&path["geometry"]["coordinates"], first_coordinate, last_coordinate + 1, coordinates_count).unwrap();
+}
+
+// Trim the trip to the nodes first..=last of its path, None if its node
+// times do not match the path nodes
+fn clip_trip(trip: &serde_json::Value, first: usize, last: usize, nodes_count: usize) -> Option<serde_json::Value> {
+    let mut clipped_trip = trip.clone();
+    let arrival_times = slice_array(&trip["node_arrival_times_seconds"], first, last + 1, nodes_count)?;
+    let departure_times = slice_array(&trip["node_departure_times_seconds"], first, last + 1, nodes_count)?;
+    let can_board = slice_array(&trip["nodes_can_board"], first, last + 1, nodes_count)?;
+    let can_unboard = slice_array(&trip["nodes_can_unboard"], first, last + 1, nodes_count)?;
+    let last_index = last - first;
+    clipped_trip["departure_time_seconds"] = departure_times[0].clone();
+    clipped_trip["arrival_time_seconds"] = arrival_times[last_index].clone();
+    clipped_trip["node_arrival_times_seconds"] = arrival_times;
+    clipped_trip["node_arrival_times_seconds"][0] = json!(null);
+    clipped_trip["node_departure_times_seconds"] = departure_times;
+    clipped_trip["node_departure_times_seconds"][last_index] = json!(null);
+    clipped_trip["nodes_can_board"] = can_board;
+    clipped_trip["nodes_can_board"][last_index] = json!(false);
+    clipped_trip["nodes_can_unboard"] = can_unboard;
+    clipped_trip["nodes_can_unboard"][0] = json!(false);
+    Some(clipped_trip)
+}
+
+/// Write to the destination cache the part of the source cache in the area:
+/// - the nodes in the area, with their transferable nodes in the area
+/// - the paths cut to their longest run of consecutive nodes in the area,
+///   with their segments, geometry and per node data, and their totals
+///   recomputed from the segments data or removed. A path leaving the
+///   area and coming back keeps only its longest part, it is reported in
+///   `paths.cut_parts`. The paths with less than two nodes in a row in the
+///   area are removed.
+/// - the trips trimmed to the nodes kept on their path, the trips on
+///   removed paths are removed
+/// - the lines with paths or trips left, with their agencies
+/// - the services, scenarios, vehicle types and fares as is
+///
+/// The integer ids of the nodes and paths are kept. Returns the report of
+/// what was clipped and removed.
+pub fn clip_cache(source_cache_directory_path: &str, destination_cache_directory_path: &str, area: &MultiPolygon<f64>) -> Result<serde_json::Value, capnp::Error> {
+
+    let nodes_json = read_collection_file(source_cache_directory_path, "nodes", &node_collection::read_collection)?;
+    let (nodes, removed_nodes) : (Vec<&serde_json::Value>, Vec<&serde_json::Value>) = nodes_json["nodes"]["features"].as_array().unwrap().iter().partition(|node| {
+        match (node["geometry"]["coordinates"][0].as_f64(), node["geometry"]["coordinates"][1].as_f64()) {
+            (Some(longitude), Some(latitude)) => area.intersects(&Point::new(longitude, latitude)),
+            _ => false
+        }
+    });
+    let node_uuids : HashSet<String> = nodes.iter().filter_map(|node| node["properties"]["id"].as_str().map(String::from)).collect();
+    write_collection_file(destination_cache_directory_path, "nodes", &json!({
+        "nodes": { "type": "FeatureCollection", "features": nodes }
+    }), &node_collection::write_collection)?;
+
+    // clipped paths with the indices of their first and last nodes kept and their nodes count
+    let mut paths_json = read_collection_file(source_cache_directory_path, "paths", &path_collection::read_collection)?;
+    let mut kept_paths : Vec<serde_json::Value> = Vec::new();
+    let mut path_runs : HashMap<String, (usize, usize, usize)> = HashMap::new();
+    let mut clipped_paths : Vec<String> = Vec::new();
+    let mut cut_parts_paths : Vec<String> = Vec::new();
+    let mut removed_paths : Vec<String> = Vec::new();
+    for mut path in paths_json["paths"]["features"].as_array_mut().unwrap().drain(..) {
+        let path_uuid = path["properties"]["id"].as_str().unwrap_or("").to_string();
+        let nodes_in_area : Vec<bool> = path["properties"]["nodes"].as_array().map(|path_nodes| {
+            path_nodes.iter().map(|node_uuid| node_uuid.as_str().is_some_and(|node_uuid| node_uuids.contains(node_uuid))).collect()
+        }).unwrap_or_default();
+        let nodes_count = nodes_in_area.len();
+        let coordinates_count = path["geometry"]["coordinates"].as_array().map_or(0, |coordinates| coordinates.len());
+        let segments : Vec<i64> = path["properties"]["segments"].as_array().map(|segments| segments.iter().filter_map(|segment| segment.as_i64()).collect()).unwrap_or_default();
+        let (run, runs_count) = longest_run(&nodes_in_area);
+        let (first, last) = match run {
+            Some(run) => run,
+            None => {
+                removed_paths.push(path_uuid);
+                continue;
+            }
+        };
+        if first > 0 || last < nodes_count - 1 {
+            // the geometry can only be cut at valid segments
+            if !validate_segments(&segments, nodes_count, coordinates_count).is_empty() {
+                removed_paths.push(path_uuid);
+                continue;
+            }
+            clip_path(&mut path, first, last);
+            clipped_paths.push(path_uuid.clone());
+        }
+        if runs_count > 1 {
+            cut_parts_paths.push(path_uuid.clone());
+        }
+        path_runs.insert(path_uuid, (first, last, nodes_count));
+        kept_paths.push(path);
+    }
+    let kept_paths_count = kept_paths.len();
+    let path_line_uuids : HashSet<String> = kept_paths.iter().filter_map(|path| path["properties"]["line_id"].as_str().map(String::from)).collect();
+    write_collection_file(destination_cache_directory_path, "paths", &json!({
+        "paths": { "type": "FeatureCollection", "features": kept_paths }
+    }), &path_collection::write_collection)?;
+
+    let source_lines_directory_path = format!("{}/lines", source_cache_directory_path);
+    let destination_lines_directory_path = format!("{}/lines", destination_cache_directory_path);
+    let lines_json = read_collection_file(source_cache_directory_path, "lines", &line_collection::read_collection)?;
+    let mut lines : Vec<&serde_json::Value> = Vec::new();
+    let mut removed_lines : Vec<String> = Vec::new();
+    let (mut kept_trips_count, mut clipped_trips_count, mut removed_trips_count) = (0, 0, 0);
+    for line_json in lines_json["lines"].as_array().unwrap() {
+        let line_uuid = line_json["id"].as_str().unwrap_or("").to_string();
+        let mut line_trips_count = 0;
+        // lines without object have no trips
+        let line_object = line::read_object(&line_uuid, &source_lines_directory_path).ok().map(|mut line_object| {
+            let schedules = line_object["line"]["scheduleByServiceId"].as_object_mut().map(|schedules| schedules.values_mut().collect()).unwrap_or_else(Vec::new);
+            for schedule in schedules {
+                for period in schedule["periods"].as_array_mut().map(|periods| periods.iter_mut().collect()).unwrap_or_else(Vec::new) {
+                    let trips = match period["trips"].as_array() {
+                        Some(trips) => trips,
+                        None => continue
+                    };
+                    let mut kept_trips : Vec<serde_json::Value> = Vec::with_capacity(trips.len());
+                    for trip in trips {
+                        let clipped_trip = trip["path_id"].as_str().and_then(|path_uuid| path_runs.get(path_uuid)).and_then(|(first, last, nodes_count)| {
+                            if *first == 0 && *last == nodes_count - 1 {
+                                return Some(trip.clone());
+                            }
+                            let clipped_trip = clip_trip(trip, *first, *last, *nodes_count);
+                            if clipped_trip.is_some() {
+                                clipped_trips_count += 1;
+                            }
+                            clipped_trip
+                        });
+                        match clipped_trip {
+                            Some(clipped_trip) => kept_trips.push(clipped_trip),
+                            None => removed_trips_count += 1
+                        }
+                    }
+                    line_trips_count += kept_trips.len();
+                    period["trips"] = json!(kept_trips);
+                }
+            }
+            line_object
+        });
+        if line_trips_count == 0 && !path_line_uuids.contains(&line_uuid) {
+            removed_lines.push(line_uuid);
+            continue;
+        }
+        kept_trips_count += line_trips_count;
+        // the clipped lines keep their layout in the source cache
+        if let Some(line_object) = line_object {
+            line::write_object_with_options(&destination_lines_directory_path, &line_object, &line::cached_write_options(&line_uuid, &source_lines_directory_path)?)?;
+        }
+        lines.push(line_json);
+    }
+    let agency_uuids : HashSet<&str> = lines.iter().filter_map(|line| line["agency_id"].as_str()).collect();
+    write_collection_file(destination_cache_directory_path, "lines", &json!({ "lines": lines }), &line_collection::write_collection)?;
+
+    if let Some(agencies_json) = read_optional_collection_file(source_cache_directory_path, "agencies", &agency_collection::read_collection)? {
+        let agencies : Vec<&serde_json::Value> = agencies_json["agencies"].as_array().unwrap().iter()
+            .filter(|agency| agency["id"].as_str().is_some_and(|agency_uuid| agency_uuids.contains(agency_uuid)))
+            .collect();
+        write_collection_file(destination_cache_directory_path, "agencies", &json!({ "agencies": agencies }), &agency_collection::write_collection)?;
+    }
+
+    let source_nodes_directory_path = format!("{}/nodes", source_cache_directory_path);
+    let destination_nodes_directory_path = format!("{}/nodes", destination_cache_directory_path);
+    let source_nodes_store = cache_store::open(&source_nodes_directory_path)?;
+    let mut sorted_node_uuids : Vec<&String> = node_uuids.iter().collect();
+    sorted_node_uuids.sort();
+    for node_uuid in sorted_node_uuids {
+        if source_nodes_store.get(&format!("node_{}.capnpbin", node_uuid))?.is_none() {
+            continue;
+        }
+        let mut node_json = node::read_object(node_uuid, &source_nodes_directory_path)?;
+        retain_transferable_nodes(&mut node_json, &node_uuids);
+        node::write_object(&destination_nodes_directory_path, &node_json)?;
+    }
+
+    let source_store = cache_store::open(source_cache_directory_path)?;
+    let destination_store = cache_store::open(destination_cache_directory_path)?;
+    for cache_file_name in COPIED_COLLECTIONS.iter() {
+        let key = format!("{}.capnpbin", cache_file_name);
+        if let Some(content) = source_store.get(&key)? {
+            destination_store.put(&key, &content)?;
+        }
+    }
+
+    Ok(json!({
+        "nodes": {
+            "kept_count": node_uuids.len(),
+            "removed_count": removed_nodes.len()
+        },
+        "paths": {
+            "kept_count": kept_paths_count,
+            "clipped": clipped_paths,
+            "cut_parts": cut_parts_paths,
+            "removed": removed_paths
+        },
+        "lines": {
+            "kept_count": lines.len(),
+            "removed": removed_lines
+        },
+        "trips": {
+            "kept_count": kept_trips_count,
+            "clipped_count": clipped_trips_count,
+            "removed_count": removed_trips_count
+        }
+    }))
+
+}
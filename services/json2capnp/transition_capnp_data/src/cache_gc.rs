@@ -0,0 +1,180 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Garbage collection of a cache: the line and node object files of the
+ * objects deleted from the lines and nodes collections, the stale custom
+ * subdirectories (like the simulation caches written with a custom
+ * cache_directory_path) and the transactions never committed nor rolled back */
+
+use std::collections::{BTreeMap, HashSet};
+use std::path::Path;
+use crate::cache::read_optional_collection_file;
+use crate::cache_store::{self, transaction, CacheChanges};
+use crate::serialization::{line_collection, node_collection};
+
+pub const DEFAULT_MAX_AGE_DAYS: f64 = 30.0;
+
+const SECONDS_PER_DAY: f64 = 86400.0;
+
+// Subdirectories of the object files of the cache with their entity type, the
+// other subdirectories are custom
+const OBJECT_DIRECTORIES: [(&str, &str); 2] = [("lines", "line"), ("nodes", "node")];
+
+#[derive(Debug, Clone)]
+pub struct CacheGcOptions {
+    // only report the files to remove
+    pub dry_run: bool,
+    // custom subdirectories and transactions unchanged for as long are stale
+    pub max_age_days: f64,
+    // custom subdirectories still used, never stale
+    pub keep_subdirectories: Vec<String>,
+    // remove the custom subdirectories of the stores where their age is unknown
+    pub remove_subdirectories_of_unknown_age: bool,
+}
+
+impl Default for CacheGcOptions {
+    fn default() -> Self {
+        CacheGcOptions {
+            dry_run: true,
+            max_age_days: DEFAULT_MAX_AGE_DAYS,
+            keep_subdirectories: Vec::new(),
+            remove_subdirectories_of_unknown_age: false,
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct CacheGcReport {
+    pub dry_run: bool,
+    pub orphan_files: Vec<String>,
+    pub stale_subdirectories: Vec<String>,
+    pub stale_transactions: Vec<String>,
+    // files removed, or to remove in a dry run
+    pub files_count: usize,
+}
+
+impl CacheGcReport {
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "dry_run": self.dry_run,
+            "orphan_files": self.orphan_files,
+            "stale_subdirectories": self.stale_subdirectories,
+            "stale_transactions": self.stale_transactions,
+            "files_count": self.files_count
+        })
+    }
+}
+
+// Uuids of the objects of a collection, None if the cache has no such
+// collection, so its object files are never orphans
+fn collection_uuids(cache_directory_path: &str, entity_type: &str) -> Result<Option<HashSet<String>>, capnp::Error> {
+    let uuids = match entity_type {
+        "line" => read_optional_collection_file(cache_directory_path, "lines", &line_collection::read_collection)?.map(|json| {
+            json["lines"].as_array().map_or(HashSet::new(), |lines| lines.iter().filter_map(|line| line["id"].as_str().map(String::from)).collect())
+        }),
+        _ => read_optional_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?.map(|json| {
+            json["nodes"]["features"].as_array().map_or(HashSet::new(), |nodes| nodes.iter().filter_map(|node| node["properties"]["id"].as_str().map(String::from)).collect())
+        })
+    };
+    Ok(uuids)
+}
+
+// Days since the last change of a file of the local directory, None if it has no file
+fn local_age_days(directory_path: &Path) -> Option<f64> {
+    fn last_modified(path: &Path) -> Option<std::time::SystemTime> {
+        if !path.is_dir() {
+            return path.metadata().and_then(|metadata| metadata.modified()).ok();
+        }
+        std::fs::read_dir(path).ok()?.filter_map(|entry| entry.ok()).filter_map(|entry| last_modified(&entry.path())).max()
+    }
+    let elapsed = last_modified(directory_path)?.elapsed().unwrap_or_default();
+    Some(elapsed.as_secs_f64() / SECONDS_PER_DAY)
+}
+
+/// Find the orphan object files, stale custom subdirectories and stale
+/// transactions of the cache, and remove them unless it is a dry run. The
+/// age of the subdirectories is only known in local directories: in the
+/// other stores, the subdirectories that are not kept are only stale with
+/// remove_subdirectories_of_unknown_age.
+pub fn gc_cache(cache_directory_path: &str, options: &CacheGcOptions) -> Result<CacheGcReport, capnp::Error> {
+    let store = cache_store::open(cache_directory_path)?;
+    let is_local = cache_store::is_local(cache_directory_path);
+    let mut report = CacheGcReport { dry_run: options.dry_run, ..Default::default() };
+    let mut changes = CacheChanges::default();
+    // directories left empty in a local cache once their files are removed
+    let mut removed_directories : Vec<String> = Vec::new();
+
+    for (directory, entity_type) in OBJECT_DIRECTORIES.iter() {
+        let uuids = match collection_uuids(cache_directory_path, entity_type)? {
+            Some(uuids) => uuids,
+            None => continue
+        };
+        let trips_entity_type = format!("{}_trips", entity_type);
+        for key in store.list(&format!("{}/", directory))? {
+            if !key.ends_with(".capnpbin") || key[directory.len() + 1..].contains('/') {
+                continue;
+            }
+            // the trips files by service of a line go with it
+            let (key_entity_type, uuid) = cache_store::entity_type_and_uuid(&key);
+            if (key_entity_type == *entity_type || key_entity_type == trips_entity_type) && !uuids.contains(&uuid) {
+                changes.delete(&key);
+                report.orphan_files.push(key);
+            }
+        }
+    }
+
+    let mut subdirectories_keys : BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for key in store.list("")? {
+        if let Some((subdirectory, _)) = key.split_once('/') {
+            subdirectories_keys.entry(subdirectory.to_string()).or_default().push(key.clone());
+        }
+    }
+    for (subdirectory, keys) in subdirectories_keys {
+        if subdirectory == transaction::TRANSACTIONS_DIRECTORY
+            || OBJECT_DIRECTORIES.iter().any(|(directory, _)| *directory == subdirectory)
+            || options.keep_subdirectories.contains(&subdirectory) {
+            continue;
+        }
+        let is_stale = if is_local {
+            local_age_days(&Path::new(cache_directory_path).join(&subdirectory)).is_none_or(|age_days| age_days >= options.max_age_days)
+        } else {
+            options.remove_subdirectories_of_unknown_age
+        };
+        if is_stale {
+            for key in keys.iter() {
+                changes.delete(key);
+            }
+            removed_directories.push(subdirectory.clone());
+            report.stale_subdirectories.push(subdirectory);
+        }
+    }
+
+    let now = chrono::Utc::now();
+    for (transaction_id, begun_at) in transaction::transactions(store.as_ref())? {
+        // staged files with no open transaction are always stale
+        let is_stale = begun_at.is_none_or(|begun_at| (now - begun_at.with_timezone(&chrono::Utc)).num_milliseconds() as f64 / 1000.0 / SECONDS_PER_DAY >= options.max_age_days);
+        if is_stale {
+            for key in transaction::transaction_keys(store.as_ref(), &transaction_id)? {
+                changes.delete(&key);
+            }
+            removed_directories.push(format!("{}/{}", transaction::TRANSACTIONS_DIRECTORY, transaction_id));
+            report.stale_transactions.push(transaction_id);
+        }
+    }
+
+    report.files_count = changes.deletes.len();
+    if !options.dry_run && !changes.is_empty() {
+        store.commit(changes)?;
+        if is_local {
+            for directory in removed_directories {
+                let _ = std::fs::remove_dir_all(Path::new(cache_directory_path).join(directory));
+            }
+        }
+    }
+    Ok(report)
+}
@@ -0,0 +1,247 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Cache files in a local directory, the layout shared with trRouting */
+
+use std::fs;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::{CacheChanges, CacheStore};
+
+// Files are written next to their final path, then renamed over it
+const TEMPORARY_FILE_SUFFIX: &str = ".tmp";
+
+// Copies of the directory made by this process, to make their names unique
+static DIRECTORY_COPIES_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+// Exchange two paths in a single rename, so a reader sees one or the other
+#[cfg(target_os = "linux")]
+fn exchange(first_path: &Path, second_path: &Path) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    let first_path = std::ffi::CString::new(first_path.as_os_str().as_bytes())?;
+    let second_path = std::ffi::CString::new(second_path.as_os_str().as_bytes())?;
+    match unsafe { libc::renameat2(libc::AT_FDCWD, first_path.as_ptr(), libc::AT_FDCWD, second_path.as_ptr(), libc::RENAME_EXCHANGE) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(target_os = "macos")]
+fn exchange(first_path: &Path, second_path: &Path) -> std::io::Result<()> {
+    use std::os::unix::ffi::OsStrExt;
+    let first_path = std::ffi::CString::new(first_path.as_os_str().as_bytes())?;
+    let second_path = std::ffi::CString::new(second_path.as_os_str().as_bytes())?;
+    match unsafe { libc::renamex_np(first_path.as_ptr(), second_path.as_ptr(), libc::RENAME_SWAP) } {
+        0 => Ok(()),
+        _ => Err(std::io::Error::last_os_error())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn exchange(_first_path: &Path, _second_path: &Path) -> std::io::Result<()> {
+    Err(std::io::Error::new(ErrorKind::Unsupported, "directories cannot be exchanged on this system"))
+}
+
+// Same directory tree, with hard links to the files, except the temporary ones
+fn link_directory(directory_path: &Path, copy_path: &Path) -> Result<(), capnp::Error> {
+    fs::create_dir(copy_path).map_err(|e| capnp::Error::failed(format!("Cannot create {}: {}", copy_path.display(), e)))?;
+    let entries = fs::read_dir(directory_path).map_err(|e| capnp::Error::failed(format!("Cannot list {}: {}", directory_path.display(), e)))?;
+    for entry in entries {
+        let entry = entry.map_err(|e| capnp::Error::failed(format!("Cannot list {}: {}", directory_path.display(), e)))?;
+        let path = entry.path();
+        let entry_copy_path = copy_path.join(entry.file_name());
+        if entry.file_type().is_ok_and(|file_type| file_type.is_dir()) {
+            link_directory(&path, &entry_copy_path)?;
+        } else if !entry.file_name().to_string_lossy().ends_with(TEMPORARY_FILE_SUFFIX) {
+            match fs::hard_link(&path, &entry_copy_path) {
+                // the file was removed since the directory was listed
+                Err(e) if e.kind() != ErrorKind::NotFound => return Err(capnp::Error::failed(format!("Cannot link {}: {}", path.display(), e))),
+                _ => ()
+            }
+        }
+    }
+    Ok(())
+}
+
+pub struct LocalDirectoryStore {
+    root: PathBuf,
+}
+
+impl LocalDirectoryStore {
+
+    pub fn new(directory_path: &str) -> Self {
+        LocalDirectoryStore { root: PathBuf::from(directory_path) }
+    }
+
+    pub fn file_path(&self, key: &str) -> PathBuf {
+        self.root.join(key)
+    }
+
+    fn temporary_file_path(&self, key: &str) -> PathBuf {
+        self.root.join(format!("{}{}", key, TEMPORARY_FILE_SUFFIX))
+    }
+
+    fn write_temporary_file(&self, key: &str, content: &[u8]) -> Result<PathBuf, capnp::Error> {
+        let file_path = self.temporary_file_path(key);
+        if let Some(directory_path) = file_path.parent() {
+            fs::create_dir_all(directory_path)
+                .map_err(|e| capnp::Error::failed(format!("Cannot create {}: {}", directory_path.display(), e)))?;
+        }
+        fs::write(&file_path, content)
+            .map_err(|e| capnp::Error::failed(format!("Cannot write {}: {}", file_path.display(), e)))?;
+        Ok(file_path)
+    }
+
+    fn rename(&self, temporary_file_path: &Path, key: &str) -> Result<(), capnp::Error> {
+        let file_path = self.file_path(key);
+        fs::rename(temporary_file_path, &file_path)
+            .map_err(|e| capnp::Error::failed(format!("Cannot write {}: {}", file_path.display(), e)))
+    }
+
+    /// Lock the directory, until the returned file is dropped: exclusive to
+    /// replace the whole directory with commit_by_exchange, shared for the
+    /// writes which would be lost if they happened meanwhile
+    #[cfg(unix)]
+    pub fn lock(&self, exclusive: bool) -> Result<fs::File, capnp::Error> {
+        use std::os::unix::fs::MetadataExt;
+        let lock_error = |e: std::io::Error| capnp::Error::failed(format!("Cannot lock {}: {}", self.root.display(), e));
+        fs::create_dir_all(&self.root).map_err(lock_error)?;
+        loop {
+            let directory = fs::File::open(&self.root).map_err(lock_error)?;
+            let locked = if exclusive { directory.lock() } else { directory.lock_shared() };
+            locked.map_err(lock_error)?;
+            // the directory may have been replaced while waiting for the lock
+            let (locked_metadata, metadata) = (directory.metadata().map_err(lock_error)?, fs::metadata(&self.root).map_err(lock_error)?);
+            if (locked_metadata.dev(), locked_metadata.ino()) == (metadata.dev(), metadata.ino()) {
+                return Ok(directory);
+            }
+        }
+    }
+
+    #[cfg(not(unix))]
+    pub fn lock(&self, _exclusive: bool) -> Result<fs::File, capnp::Error> {
+        Err(capnp::Error::failed(format!("Cannot lock {} on this system", self.root.display())))
+    }
+
+    /// Apply the changes by replacing the whole directory at once: the
+    /// changes are applied to a copy of the directory, with hard links to
+    /// its files, and the copy is exchanged with the directory in a single
+    /// rename. A reader opening the directory sees all the old files or all
+    /// the new ones, and a failed commit changes no file. Writes to the
+    /// directory during the commit are lost, see lock.
+    pub fn commit_by_exchange(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        fs::create_dir_all(&self.root).map_err(|e| capnp::Error::failed(format!("Cannot create {}: {}", self.root.display(), e)))?;
+        // a symbolic link to the directory is kept
+        let directory_path = fs::canonicalize(&self.root).map_err(|e| capnp::Error::failed(format!("Cannot open {}: {}", self.root.display(), e)))?;
+        let copy_path = match (directory_path.parent(), directory_path.file_name()) {
+            (Some(parent_path), Some(directory_name)) => parent_path.join(format!(
+                ".{}.commit-{}-{}", directory_name.to_string_lossy(), std::process::id(), DIRECTORY_COPIES_COUNT.fetch_add(1, Ordering::Relaxed)
+            )),
+            _ => return Err(capnp::Error::failed(format!("Cannot replace the directory {}", directory_path.display())))
+        };
+        let copied = link_directory(&directory_path, &copy_path)
+            .and_then(|_| LocalDirectoryStore { root: copy_path.clone() }.commit(changes))
+            .and_then(|_| exchange(&copy_path, &directory_path).map_err(|e| capnp::Error::failed(format!("Cannot replace {}: {}", directory_path.display(), e))));
+        // the copy is the previous directory once exchanged
+        let _ = fs::remove_dir_all(&copy_path);
+        copied
+    }
+
+    fn list_directory(&self, directory_path: &Path, keys: &mut Vec<String>) -> Result<(), capnp::Error> {
+        let entries = match fs::read_dir(directory_path) {
+            Ok(entries) => entries,
+            Err(e) if e.kind() == ErrorKind::NotFound => return Ok(()),
+            Err(e) => return Err(capnp::Error::failed(format!("Cannot list {}: {}", directory_path.display(), e)))
+        };
+        for entry in entries {
+            let path = entry.map_err(|e| capnp::Error::failed(format!("Cannot list {}: {}", directory_path.display(), e)))?.path();
+            if path.is_dir() {
+                self.list_directory(&path, keys)?;
+                continue;
+            }
+            let relative_path = path.strip_prefix(&self.root).unwrap_or(&path);
+            let key = relative_path.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+            if !key.ends_with(TEMPORARY_FILE_SUFFIX) {
+                keys.push(key);
+            }
+        }
+        Ok(())
+    }
+
+}
+
+impl CacheStore for LocalDirectoryStore {
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error> {
+        let file_path = self.file_path(key);
+        match fs::read(&file_path) {
+            Ok(content) => Ok(Some(content)),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(capnp::Error::failed(format!("Cannot open {}: {}", file_path.display(), e)))
+        }
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        let temporary_file_path = self.write_temporary_file(key, content)?;
+        self.rename(&temporary_file_path, key)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error> {
+        // only the directory of the prefix is walked
+        let directory = prefix.rfind('/').map(|index| &prefix[..index]).unwrap_or("");
+        let mut keys : Vec<String> = Vec::new();
+        self.list_directory(&self.root.join(directory), &mut keys)?;
+        keys.retain(|key| key.starts_with(prefix));
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), capnp::Error> {
+        let file_path = self.file_path(key);
+        match fs::remove_file(&file_path) {
+            Ok(()) => Ok(()),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(()),
+            Err(e) => Err(capnp::Error::failed(format!("Cannot remove {}: {}", file_path.display(), e)))
+        }
+    }
+
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        // all the files are written before any of them replaces a cache file
+        let mut temporary_file_paths : Vec<(PathBuf, &str)> = Vec::with_capacity(changes.puts.len());
+        for (key, content) in changes.puts.iter() {
+            match self.write_temporary_file(key, content) {
+                Ok(temporary_file_path) => temporary_file_paths.push((temporary_file_path, key)),
+                Err(error) => {
+                    for (temporary_file_path, _) in temporary_file_paths {
+                        let _ = fs::remove_file(temporary_file_path);
+                    }
+                    return Err(error);
+                }
+            }
+        }
+        for (i, (temporary_file_path, key)) in temporary_file_paths.iter().enumerate() {
+            if let Err(error) = self.rename(temporary_file_path, key) {
+                // the files already renamed stay, see CacheStore::commit
+                for (temporary_file_path, _) in temporary_file_paths[i..].iter() {
+                    let _ = fs::remove_file(temporary_file_path);
+                }
+                return Err(error);
+            }
+        }
+        for key in changes.deletes.iter() {
+            self.delete(key)?;
+        }
+        Ok(())
+    }
+
+    fn atomic_commit(&self) -> bool {
+        false
+    }
+
+}
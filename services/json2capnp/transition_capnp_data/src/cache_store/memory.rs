@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Cache files in memory, for tests and short lived caches */
+
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, OnceLock, RwLock};
+use super::{CacheChanges, CacheStore};
+
+type Files = Arc<RwLock<BTreeMap<String, Vec<u8>>>>;
+
+// Stores opened by name with `memory://<name>`, kept for the whole process
+static SHARED_STORES: OnceLock<Mutex<HashMap<String, Files>>> = OnceLock::new();
+
+#[derive(Clone, Default)]
+pub struct MemoryStore {
+    files: Files,
+    prefix: String,
+}
+
+impl MemoryStore {
+
+    pub fn new() -> Self {
+        MemoryStore::default()
+    }
+
+    /// Store shared by all the callers using the same name
+    pub fn shared(name: &str) -> Self {
+        let mut shared_stores = SHARED_STORES.get_or_init(|| Mutex::new(HashMap::new())).lock().unwrap();
+        MemoryStore {
+            files: shared_stores.entry(name.to_string()).or_default().clone(),
+            prefix: String::new(),
+        }
+    }
+
+    /// Same files, with the keys relative to a directory
+    pub fn with_prefix(&self, directory: &str) -> Self {
+        MemoryStore {
+            files: self.files.clone(),
+            prefix: super::key(&self.prefix, directory.trim_matches('/')),
+        }
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        super::key(&self.prefix, key)
+    }
+
+    fn read_files(&self) -> Result<std::sync::RwLockReadGuard<'_, BTreeMap<String, Vec<u8>>>, capnp::Error> {
+        self.files.read().map_err(|_| capnp::Error::failed(String::from("Memory store is poisoned")))
+    }
+
+    fn write_files(&self) -> Result<std::sync::RwLockWriteGuard<'_, BTreeMap<String, Vec<u8>>>, capnp::Error> {
+        self.files.write().map_err(|_| capnp::Error::failed(String::from("Memory store is poisoned")))
+    }
+
+}
+
+impl CacheStore for MemoryStore {
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error> {
+        Ok(self.read_files()?.get(&self.full_key(key)).cloned())
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        self.write_files()?.insert(self.full_key(key), content.to_vec());
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error> {
+        let directory_prefix = if self.prefix.is_empty() { String::new() } else { format!("{}/", self.prefix) };
+        let full_prefix = format!("{}{}", directory_prefix, prefix);
+        Ok(self.read_files()?.range(full_prefix.clone()..)
+            .take_while(|(key, _)| key.starts_with(&full_prefix))
+            .map(|(key, _)| key[directory_prefix.len()..].to_string())
+            .collect())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), capnp::Error> {
+        self.write_files()?.remove(&self.full_key(key));
+        Ok(())
+    }
+
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        // readers wait for the lock, so they see all the changes or none
+        let mut files = self.write_files()?;
+        for (key, content) in changes.puts {
+            files.insert(self.full_key(&key), content);
+        }
+        for key in changes.deletes.iter() {
+            files.remove(&self.full_key(key));
+        }
+        Ok(())
+    }
+
+    fn atomic_commit(&self) -> bool {
+        true
+    }
+
+}
@@ -0,0 +1,227 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Storage of the cache files. The files are read and written by key, a path
+ * relative to the cache location like `lines/line_<uuid>.capnpbin`, so the
+ * cache can be in a local directory, in memory, in a SQLite database or in an
+ * S3 compatible object storage. A cache location is a directory path,
+ * `memory://<name>/<path>`, `sqlite://<file>.sqlite/<path>` or
+ * `s3://<bucket>/<prefix>`, see open. Files can also be written in a
+ * transaction, see transaction. */
+
+mod local_directory;
+mod memory;
+mod s3;
+mod sqlite;
+pub mod transaction;
+
+pub use local_directory::LocalDirectoryStore;
+pub use memory::MemoryStore;
+pub use s3::{S3Store, S3StoreConfig};
+pub use sqlite::{SqliteStore, entity_type_and_uuid};
+pub use transaction::TransactionStore;
+
+pub const MEMORY_SCHEME: &str = "memory://";
+pub const S3_SCHEME: &str = "s3://";
+pub const SQLITE_SCHEME: &str = "sqlite://";
+
+// Files copied per commit when converting a cache, to bound the memory used
+const COPY_BATCH_FILES_COUNT: usize = 1000;
+
+pub trait CacheStore: Send + Sync {
+
+    /// Content of the file, None if there is no file with this key
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error>;
+
+    /// Create or replace the file, a reader never sees a partially written file
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error>;
+
+    /// Keys of the files starting with the prefix, sorted
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error>;
+
+    /// Remove the file, removing a missing file is not an error
+    fn delete(&self, key: &str) -> Result<(), capnp::Error>;
+
+    /// Apply the changes, so the files of a cache object are replaced
+    /// together. The memory and SQLite stores replace all the files at once.
+    /// The local directory store writes all the files before replacing any,
+    /// so a file that cannot be written changes no file, but then renames
+    /// them one at a time: a reader can see some of the new files with the
+    /// old ones, and a failed rename leaves the files already renamed, see
+    /// LocalDirectoryStore::commit_by_exchange to replace them all at once.
+    /// The S3 store replaces the objects one at a time, see atomic_commit.
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error>;
+
+    /// Whether commit replaces all the files at once, so a reader never sees
+    /// some of the changes without the others and a failed commit changes
+    /// no file
+    fn atomic_commit(&self) -> bool;
+
+}
+
+/// Files to write and remove together with CacheStore::commit
+#[derive(Debug, Default)]
+pub struct CacheChanges {
+    pub puts: Vec<(String, Vec<u8>)>,
+    pub deletes: Vec<String>,
+}
+
+impl CacheChanges {
+
+    pub fn put(&mut self, key: &str, content: Vec<u8>) {
+        self.deletes.retain(|deleted_key| deleted_key != key);
+        self.puts.push((key.to_string(), content));
+    }
+
+    pub fn delete(&mut self, key: &str) {
+        if !self.puts.iter().any(|(put_key, _)| put_key == key) {
+            self.deletes.push(key.to_string());
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puts.is_empty() && self.deletes.is_empty()
+    }
+
+}
+
+/// Key of a file in a directory of the store, the directory can be empty
+pub fn key(directory: &str, file_name: &str) -> String {
+    let directory = directory.trim_matches('/');
+    if directory.is_empty() {
+        file_name.to_string()
+    } else {
+        format!("{}/{}", directory, file_name)
+    }
+}
+
+/// Whether the cache location is a local directory
+pub fn is_local(location: &str) -> bool {
+    ![MEMORY_SCHEME, S3_SCHEME, SQLITE_SCHEME].iter().any(|scheme| location.starts_with(scheme))
+}
+
+/// Store of the files of a cache location:
+/// - a directory path for a LocalDirectoryStore
+/// - `memory://<name>/<path>` for the files under path in the MemoryStore
+///   shared by all the locations with the same name
+/// - `sqlite://<file>.sqlite/<path>` for the files under path in the
+///   SqliteStore of the database file, created if needed
+/// - `s3://<bucket>/<prefix>` for an S3Store, configured with the
+///   AWS_ENDPOINT_URL, AWS_REGION, AWS_ACCESS_KEY_ID and
+///   AWS_SECRET_ACCESS_KEY environment variables
+///
+/// A location in the shadow directory of a transaction, like
+/// `<cache location>/.transactions/<id>/lines`, is a TransactionStore
+pub fn open(location: &str) -> Result<Box<dyn CacheStore>, capnp::Error> {
+    match transaction::split_transaction_location(location) {
+        Some((cache_location, transaction_id, path)) => Ok(Box::new(TransactionStore::open(&cache_location, &transaction_id, &path)?)),
+        None => open_location(location)
+    }
+}
+
+// Database file path and prefix of the files in it of a sqlite location without its scheme
+fn sqlite_database_path_and_prefix(path: &str) -> (&str, &str) {
+    let database_path_end = path.find(&format!("{}/", sqlite::SQLITE_FILE_EXTENSION)).map_or(path.len(), |index| index + sqlite::SQLITE_FILE_EXTENSION.len());
+    (&path[..database_path_end], path[database_path_end..].trim_start_matches('/'))
+}
+
+// Store of a location, without the transactions
+fn open_location(location: &str) -> Result<Box<dyn CacheStore>, capnp::Error> {
+    if let Some(path) = location.strip_prefix(MEMORY_SCHEME) {
+        let (name, prefix) = path.split_once('/').unwrap_or((path, ""));
+        return Ok(Box::new(MemoryStore::shared(name).with_prefix(prefix)));
+    }
+    if let Some(path) = location.strip_prefix(SQLITE_SCHEME) {
+        let (database_path, prefix) = sqlite_database_path_and_prefix(path);
+        return Ok(Box::new(SqliteStore::open(database_path, prefix)?));
+    }
+    if let Some(path) = location.strip_prefix(S3_SCHEME) {
+        let (bucket, prefix) = path.split_once('/').unwrap_or((path, ""));
+        return Ok(Box::new(S3Store::new(S3StoreConfig::from_env(bucket, prefix)?)));
+    }
+    Ok(Box::new(LocalDirectoryStore::new(location)))
+}
+
+/// Store of the directory of a file path, with the key of the file in it
+pub fn open_file(file_path: &str) -> Result<(Box<dyn CacheStore>, String), capnp::Error> {
+    let (location, file_name) = match file_path.rsplit_once('/') {
+        Some(("", file_name)) => ("/", file_name),
+        Some((location, file_name)) => (location, file_name),
+        None => (".", file_path)
+    };
+    Ok((open(location)?, file_name.to_string()))
+}
+
+/// Write a capnp message with the write function of a serialization module
+pub fn write_message(
+    store: &dyn CacheStore,
+    key: &str,
+    json: &serde_json::Value,
+    write_fn: &dyn Fn(&serde_json::Value, &mut dyn std::io::Write) -> Result<(), capnp::Error>,
+) -> Result<(), capnp::Error> {
+    let mut content : Vec<u8> = Vec::new();
+    write_fn(json, &mut content)?;
+    store.put(key, &content)
+}
+
+/// Read a capnp message with the read function of a serialization module,
+/// None if there is no file with this key
+pub fn read_message(
+    store: &dyn CacheStore,
+    key: &str,
+    read_fn: &dyn Fn(&mut dyn std::io::Read) -> Result<serde_json::Value, capnp::Error>,
+) -> Result<Option<serde_json::Value>, capnp::Error> {
+    match store.get(key)? {
+        Some(content) => Ok(Some(read_fn(&mut content.as_slice())?)),
+        None => Ok(None)
+    }
+}
+
+/// Copy all the files of a store to another, to convert a cache between
+/// backends. The files staged in transactions are not copied. The files are
+/// committed in batches, so a large cache is not held in memory. Returns the
+/// number of files copied.
+pub fn copy(source: &dyn CacheStore, destination: &dyn CacheStore) -> Result<usize, capnp::Error> {
+    copy_excluding(source, destination, &[])
+}
+
+fn copy_excluding(source: &dyn CacheStore, destination: &dyn CacheStore, excluded_keys: &[String]) -> Result<usize, capnp::Error> {
+    let transactions_prefix = format!("{}/", transaction::TRANSACTIONS_DIRECTORY);
+    let keys : Vec<String> = source.list("")?.into_iter()
+        .filter(|key| !key.starts_with(&transactions_prefix) && !excluded_keys.contains(key))
+        .collect();
+    for batch_keys in keys.chunks(COPY_BATCH_FILES_COUNT) {
+        let mut changes = CacheChanges::default();
+        for key in batch_keys {
+            let content = source.get(key)?.ok_or_else(|| capnp::Error::failed(format!("Cache file {} was removed during the copy", key)))?;
+            changes.put(key, content);
+        }
+        destination.commit(changes)?;
+    }
+    Ok(keys.len())
+}
+
+/// Copy a cache to another location, like a cache directory to a single
+/// SQLite file or back
+pub fn convert(source_location: &str, destination_location: &str) -> Result<usize, capnp::Error> {
+    let destination = open(destination_location)?;
+    // a database file in the source directory is not a file of the cache
+    let mut excluded_keys : Vec<String> = Vec::new();
+    if let (true, Some(path)) = (is_local(source_location), destination_location.strip_prefix(SQLITE_SCHEME)) {
+        let (database_path, _) = sqlite_database_path_and_prefix(path);
+        if let (Ok(database_path), Ok(source_path)) = (std::fs::canonicalize(database_path), std::fs::canonicalize(source_location)) {
+            if let Ok(relative_path) = database_path.strip_prefix(&source_path) {
+                let database_key = relative_path.components().map(|component| component.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+                for suffix in ["", "-journal", "-wal", "-shm"] {
+                    excluded_keys.push(format!("{}{}", database_key, suffix));
+                }
+            }
+        }
+    }
+    copy_excluding(open(source_location)?.as_ref(), destination.as_ref(), &excluded_keys)
+}
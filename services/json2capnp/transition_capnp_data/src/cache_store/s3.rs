@@ -0,0 +1,242 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Cache files in an S3 compatible object storage (AWS S3, MinIO, ...), with
+ * path style urls and requests signed with AWS signature version 4 */
+
+use hmac::{Hmac, Mac};
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use super::{CacheChanges, CacheStore};
+
+const DEFAULT_REGION: &str = "us-east-1";
+
+#[derive(Debug, Clone)]
+pub struct S3StoreConfig {
+    // scheme and host of the storage, like http://localhost:9000
+    pub endpoint: String,
+    pub region: String,
+    pub bucket: String,
+    // keys are relative to this prefix in the bucket
+    pub prefix: String,
+    pub access_key_id: String,
+    pub secret_access_key: String,
+}
+
+impl S3StoreConfig {
+
+    /// Configuration from the usual AWS environment variables. The endpoint
+    /// defaults to AWS S3 in the region when AWS_ENDPOINT_URL is not set.
+    pub fn from_env(bucket: &str, prefix: &str) -> Result<Self, capnp::Error> {
+        let env = |name: &str| std::env::var(name).ok().filter(|value| !value.is_empty());
+        let required_env = |name: &str| env(name).ok_or_else(|| capnp::Error::failed(format!("{} must be set to use the S3 cache store", name)));
+        let region = env("AWS_REGION").unwrap_or_else(|| DEFAULT_REGION.to_string());
+        Ok(S3StoreConfig {
+            endpoint: env("AWS_ENDPOINT_URL").unwrap_or_else(|| format!("https://s3.{}.amazonaws.com", region)),
+            region,
+            bucket: bucket.to_string(),
+            prefix: prefix.trim_matches('/').to_string(),
+            access_key_id: required_env("AWS_ACCESS_KEY_ID")?,
+            secret_access_key: required_env("AWS_SECRET_ACCESS_KEY")?,
+        })
+    }
+
+}
+
+pub struct S3Store {
+    config: S3StoreConfig,
+    agent: ureq::Agent,
+}
+
+// Percent encoding of AWS signatures: everything but the unreserved characters
+fn uri_encode(value: &str, encode_slash: bool) -> String {
+    let mut encoded = String::with_capacity(value.len());
+    for byte in value.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => encoded.push(byte as char),
+            b'/' if !encode_slash => encoded.push('/'),
+            _ => encoded.push_str(&format!("%{:02X}", byte))
+        }
+    }
+    encoded
+}
+
+fn hmac_sha256(key: &[u8], data: &str) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts keys of any length");
+    mac.update(data.as_bytes());
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn xml_unescape(value: &str) -> String {
+    value.replace("&lt;", "<").replace("&gt;", ">").replace("&quot;", "\"").replace("&apos;", "'").replace("&amp;", "&")
+}
+
+// Text of the elements with this tag name, enough for the ListObjectsV2 response
+fn xml_elements_text(xml: &str, tag_name: &str) -> Vec<String> {
+    let start_tag = format!("<{}>", tag_name);
+    let end_tag = format!("</{}>", tag_name);
+    let mut texts : Vec<String> = Vec::new();
+    let mut rest = xml;
+    while let Some(start) = rest.find(&start_tag) {
+        rest = &rest[start + start_tag.len()..];
+        match rest.find(&end_tag) {
+            Some(end) => {
+                texts.push(xml_unescape(&rest[..end]));
+                rest = &rest[end + end_tag.len()..];
+            },
+            None => break
+        }
+    }
+    texts
+}
+
+impl S3Store {
+
+    pub fn new(config: S3StoreConfig) -> Self {
+        S3Store { config, agent: ureq::AgentBuilder::new().build() }
+    }
+
+    fn object_key(&self, key: &str) -> String {
+        super::key(&self.config.prefix, key)
+    }
+
+    fn host(&self) -> &str {
+        let endpoint = self.config.endpoint.split_once("://").map_or(self.config.endpoint.as_str(), |(_, host)| host);
+        endpoint.split('/').next().unwrap_or(endpoint)
+    }
+
+    /// Authorization header of a request signed with AWS signature version 4,
+    /// with the host, x-amz-content-sha256 and x-amz-date headers. The uri
+    /// and query must be encoded, with the query parameters sorted.
+    pub fn authorization(&self, method: &str, canonical_uri: &str, canonical_query: &str, content_sha256: &str, now: chrono::DateTime<chrono::Utc>) -> String {
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let date = now.format("%Y%m%d").to_string();
+        let canonical_request = format!(
+            "{}\n{}\n{}\nhost:{}\nx-amz-content-sha256:{}\nx-amz-date:{}\n\nhost;x-amz-content-sha256;x-amz-date\n{}",
+            method, canonical_uri, canonical_query, self.host(), content_sha256, amz_date, content_sha256
+        );
+        let scope = format!("{}/{}/s3/aws4_request", date, self.config.region);
+        let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", amz_date, scope, hex::encode(Sha256::digest(canonical_request.as_bytes())));
+        let signing_key = ["s3", "aws4_request"].iter().fold(
+            hmac_sha256(&hmac_sha256(format!("AWS4{}", self.config.secret_access_key).as_bytes(), &date), &self.config.region),
+            |key, data| hmac_sha256(&key, data)
+        );
+        format!(
+            "AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders=host;x-amz-content-sha256;x-amz-date, Signature={}",
+            self.config.access_key_id, scope, hex::encode(hmac_sha256(&signing_key, &string_to_sign))
+        )
+    }
+
+    // Signed request on an object, or on the bucket when object_key is None
+    fn request(&self, method: &str, object_key: Option<&str>, query: &[(&str, &str)], content: &[u8]) -> Result<Option<ureq::Response>, capnp::Error> {
+        let canonical_uri = match object_key {
+            Some(object_key) => format!("/{}/{}", uri_encode(&self.config.bucket, true), uri_encode(object_key, false)),
+            None => format!("/{}", uri_encode(&self.config.bucket, true))
+        };
+        let mut sorted_query : Vec<(String, String)> = query.iter().map(|(name, value)| (uri_encode(name, true), uri_encode(value, true))).collect();
+        sorted_query.sort();
+        let canonical_query = sorted_query.iter().map(|(name, value)| format!("{}={}", name, value)).collect::<Vec<String>>().join("&");
+
+        let now = chrono::Utc::now();
+        let amz_date = now.format("%Y%m%dT%H%M%SZ").to_string();
+        let content_sha256 = hex::encode(Sha256::digest(content));
+        let authorization = self.authorization(method, &canonical_uri, &canonical_query, &content_sha256, now);
+
+        let url = if canonical_query.is_empty() {
+            format!("{}{}", self.config.endpoint.trim_end_matches('/'), canonical_uri)
+        } else {
+            format!("{}{}?{}", self.config.endpoint.trim_end_matches('/'), canonical_uri, canonical_query)
+        };
+        let response = self.agent.request(method, &url)
+            .set("x-amz-content-sha256", &content_sha256)
+            .set("x-amz-date", &amz_date)
+            .set("authorization", &authorization)
+            .send_bytes(content);
+        match response {
+            Ok(response) => Ok(Some(response)),
+            Err(ureq::Error::Status(404, _)) => Ok(None),
+            Err(ureq::Error::Status(status, response)) => Err(capnp::Error::failed(format!(
+                "S3 {} {} failed with status {}: {}", method, url, status, response.into_string().unwrap_or_default()
+            ))),
+            Err(error) => Err(capnp::Error::failed(format!("S3 {} {} failed: {}", method, url, error)))
+        }
+    }
+
+}
+
+impl CacheStore for S3Store {
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error> {
+        let response = match self.request("GET", Some(&self.object_key(key)), &[], &[])? {
+            Some(response) => response,
+            None => return Ok(None)
+        };
+        let mut content : Vec<u8> = Vec::new();
+        response.into_reader().read_to_end(&mut content)
+            .map_err(|e| capnp::Error::failed(format!("Cannot read S3 object {}: {}", key, e)))?;
+        Ok(Some(content))
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        self.request("PUT", Some(&self.object_key(key)), &[], content)?;
+        Ok(())
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error> {
+        let bucket_prefix = if self.config.prefix.is_empty() { String::new() } else { format!("{}/", self.config.prefix) };
+        let full_prefix = format!("{}{}", bucket_prefix, prefix);
+        let mut keys : Vec<String> = Vec::new();
+        let mut continuation_token : Option<String> = None;
+        loop {
+            let mut query : Vec<(&str, &str)> = vec![("list-type", "2"), ("prefix", &full_prefix)];
+            if let Some(continuation_token) = &continuation_token {
+                query.push(("continuation-token", continuation_token));
+            }
+            let response = match self.request("GET", None, &query, &[])? {
+                Some(response) => response,
+                None => return Err(capnp::Error::failed(format!("S3 bucket {} does not exist", self.config.bucket)))
+            };
+            let xml = response.into_string()
+                .map_err(|e| capnp::Error::failed(format!("Cannot read S3 bucket {} list: {}", self.config.bucket, e)))?;
+            keys.extend(xml_elements_text(&xml, "Key").into_iter().filter_map(|key| key.strip_prefix(&bucket_prefix).map(String::from)));
+            continuation_token = match xml_elements_text(&xml, "IsTruncated").first().map(String::as_str) {
+                Some("true") => xml_elements_text(&xml, "NextContinuationToken").into_iter().next(),
+                _ => None
+            };
+            if continuation_token.is_none() {
+                break;
+            }
+        }
+        keys.sort();
+        Ok(keys)
+    }
+
+    fn delete(&self, key: &str) -> Result<(), capnp::Error> {
+        self.request("DELETE", Some(&self.object_key(key)), &[], &[])?;
+        Ok(())
+    }
+
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        // S3 only replaces one object at a time: a reader can see some of
+        // the new objects with the old ones, and a failed commit leaves the
+        // objects already replaced. The removed objects only go once all the
+        // new ones are written.
+        for (key, content) in changes.puts.iter() {
+            self.put(key, content)?;
+        }
+        for key in changes.deletes.iter() {
+            self.delete(key)?;
+        }
+        Ok(())
+    }
+
+    fn atomic_commit(&self) -> bool {
+        false
+    }
+
+}
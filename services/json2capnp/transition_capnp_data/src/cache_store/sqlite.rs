@@ -0,0 +1,150 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Cache files as blobs in a single SQLite database, so a whole cache can be
+ * copied, backed up and versioned as one file. Each row keeps the key of the
+ * file, with the entity type and uuid of the object it contains. */
+
+use rusqlite::{params, Connection, OptionalExtension};
+use std::sync::Mutex;
+use super::{CacheChanges, CacheStore};
+
+pub const SQLITE_FILE_EXTENSION: &str = ".sqlite";
+
+const BUSY_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+const CREATE_TABLE_SQL: &str = "
+    CREATE TABLE IF NOT EXISTS cache_files (
+        key TEXT PRIMARY KEY NOT NULL,
+        entity_type TEXT NOT NULL,
+        uuid TEXT NOT NULL,
+        content BLOB NOT NULL
+    );
+    CREATE INDEX IF NOT EXISTS cache_files_entity_idx ON cache_files (entity_type, uuid);
+";
+
+fn sqlite_error(database_path: &str, error: rusqlite::Error) -> capnp::Error {
+    capnp::Error::failed(format!("SQLite cache {}: {}", database_path, error))
+}
+
+/// Entity type and uuid of the object in a cache file, from its name:
+/// `agencies.capnpbin` is the agencies collection with no uuid,
+/// `line_<uuid>.capnpbin` the line object and
+/// `line_<uuid>_trips_<service uuid>.capnpbin` the line_trips of the line
+pub fn entity_type_and_uuid(key: &str) -> (String, String) {
+    let file_name = key.rsplit('/').next().unwrap_or(key);
+    let stem = file_name.strip_suffix(".capnpbin").unwrap_or(file_name);
+    match stem.split_once('_') {
+        Some((entity_type, uuid)) => match uuid.split_once("_trips_") {
+            Some((uuid, _service_uuid)) => (format!("{}_trips", entity_type), uuid.to_string()),
+            None => (entity_type.to_string(), uuid.to_string())
+        },
+        None => (stem.to_string(), String::new())
+    }
+}
+
+pub struct SqliteStore {
+    database_path: String,
+    connection: Mutex<Connection>,
+    prefix: String,
+}
+
+impl SqliteStore {
+
+    /// Open or create the database, the keys being relative to the prefix
+    pub fn open(database_path: &str, prefix: &str) -> Result<Self, capnp::Error> {
+        if let Some(directory_path) = std::path::Path::new(database_path).parent() {
+            std::fs::create_dir_all(directory_path)
+                .map_err(|e| capnp::Error::failed(format!("Cannot create {}: {}", directory_path.display(), e)))?;
+        }
+        let connection = Connection::open(database_path).map_err(|e| sqlite_error(database_path, e))?;
+        // other processes and server threads can write to the same database
+        connection.busy_timeout(BUSY_TIMEOUT).map_err(|e| sqlite_error(database_path, e))?;
+        connection.execute_batch(CREATE_TABLE_SQL).map_err(|e| sqlite_error(database_path, e))?;
+        Ok(SqliteStore {
+            database_path: database_path.to_string(),
+            connection: Mutex::new(connection),
+            prefix: prefix.trim_matches('/').to_string(),
+        })
+    }
+
+    fn full_key(&self, key: &str) -> String {
+        super::key(&self.prefix, key)
+    }
+
+    fn connection(&self) -> Result<std::sync::MutexGuard<'_, Connection>, capnp::Error> {
+        self.connection.lock().map_err(|_| capnp::Error::failed(format!("SQLite cache {} is poisoned", self.database_path)))
+    }
+
+    fn put_row(&self, connection: &Connection, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        let full_key = self.full_key(key);
+        let (entity_type, uuid) = entity_type_and_uuid(&full_key);
+        connection.execute(
+            "INSERT OR REPLACE INTO cache_files (key, entity_type, uuid, content) VALUES (?1, ?2, ?3, ?4)",
+            params![full_key, entity_type, uuid, content],
+        ).map_err(|e| sqlite_error(&self.database_path, e))?;
+        Ok(())
+    }
+
+    fn delete_row(&self, connection: &Connection, key: &str) -> Result<(), capnp::Error> {
+        connection.execute("DELETE FROM cache_files WHERE key = ?1", params![self.full_key(key)])
+            .map_err(|e| sqlite_error(&self.database_path, e))?;
+        Ok(())
+    }
+
+}
+
+impl CacheStore for SqliteStore {
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error> {
+        self.connection()?.query_row(
+            "SELECT content FROM cache_files WHERE key = ?1",
+            params![self.full_key(key)],
+            |row| row.get(0),
+        ).optional().map_err(|e| sqlite_error(&self.database_path, e))
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        self.put_row(&*self.connection()?, key, content)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error> {
+        let directory_prefix = if self.prefix.is_empty() { String::new() } else { format!("{}/", self.prefix) };
+        let full_prefix = format!("{}{}", directory_prefix, prefix);
+        let connection = self.connection()?;
+        let mut statement = connection.prepare("SELECT key FROM cache_files WHERE substr(key, 1, length(?1)) = ?1 ORDER BY key")
+            .map_err(|e| sqlite_error(&self.database_path, e))?;
+        let keys = statement.query_map(params![full_prefix], |row| row.get::<_, String>(0))
+            .map_err(|e| sqlite_error(&self.database_path, e))?
+            .collect::<Result<Vec<String>, rusqlite::Error>>()
+            .map_err(|e| sqlite_error(&self.database_path, e))?;
+        Ok(keys.into_iter().map(|key| key[directory_prefix.len()..].to_string()).collect())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), capnp::Error> {
+        self.delete_row(&*self.connection()?, key)
+    }
+
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        // one SQLite transaction for all the changes, rolled back on error
+        let mut connection = self.connection()?;
+        let transaction = connection.transaction().map_err(|e| sqlite_error(&self.database_path, e))?;
+        for (key, content) in changes.puts.iter() {
+            self.put_row(&transaction, key, content)?;
+        }
+        for key in changes.deletes.iter() {
+            self.delete_row(&transaction, key)?;
+        }
+        transaction.commit().map_err(|e| sqlite_error(&self.database_path, e))
+    }
+
+    fn atomic_commit(&self) -> bool {
+        true
+    }
+
+}
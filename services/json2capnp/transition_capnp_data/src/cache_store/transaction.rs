@@ -0,0 +1,302 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Transactions on a cache. The files written in a transaction are staged in
+ * a shadow directory of the cache, `.transactions/<id>/`, with the layout of
+ * the cache, and only replace the cache files when the transaction is
+ * committed, all at once. The memory and SQLite stores replace them in a
+ * single CacheStore::commit. A local directory is replaced as a whole, see
+ * LocalDirectoryStore::commit_by_exchange, so a reader like trRouting
+ * opening the cache sees all the old files or all the new ones. The commit
+ * locks the directory, and the other transactions wait for it to stage
+ * their files, but the files written directly to the cache during a commit
+ * are lost. The S3 store replaces its objects one at a time, so it cannot
+ * have transactions. A failed commit changes no cache file and leaves the
+ * transaction open, so it can be committed again.
+ * The shadow directory is itself a cache location: its store reads the
+ * staged files over the cache files, so all the cache writers can write in a
+ * transaction. */
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use super::{CacheChanges, CacheStore, LocalDirectoryStore};
+
+pub const TRANSACTIONS_DIRECTORY: &str = ".transactions";
+
+pub type BegunAt = chrono::DateTime<chrono::FixedOffset>;
+
+// Empty file staged for a cache file removed in the transaction
+const DELETED_FILE_SUFFIX: &str = ".deleted";
+
+// Transactions begun by this process, to make their ids unique
+static TRANSACTIONS_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+fn validate_transaction_id(transaction_id: &str) -> Result<(), capnp::Error> {
+    // the id is a directory name, it must not escape the transactions directory
+    if transaction_id.is_empty() || !transaction_id.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_') {
+        return Err(capnp::Error::failed(format!("Invalid transaction id {}", transaction_id)));
+    }
+    Ok(())
+}
+
+fn staging_directory(transaction_id: &str) -> String {
+    format!("{}/{}", TRANSACTIONS_DIRECTORY, transaction_id)
+}
+
+// The transaction file marks an open transaction, it is next to the shadow
+// directory so it is not committed with the staged files
+fn transaction_file_key(transaction_id: &str) -> String {
+    format!("{}/{}.json", TRANSACTIONS_DIRECTORY, transaction_id)
+}
+
+fn deleted_file_key(key: &str) -> String {
+    format!("{}{}", key, DELETED_FILE_SUFFIX)
+}
+
+/// Location of the shadow directory of the transaction, to use instead of
+/// the cache location to write in the transaction
+pub fn transaction_location(location: &str, transaction_id: &str) -> Result<String, capnp::Error> {
+    validate_transaction_id(transaction_id)?;
+    Ok(format!("{}/{}", location.trim_end_matches('/'), staging_directory(transaction_id)))
+}
+
+/// Cache location, transaction id and path in the cache of a location in the
+/// shadow directory of a transaction, None for other locations
+pub fn split_transaction_location(location: &str) -> Option<(String, String, String)> {
+    let separator = format!("/{}/", TRANSACTIONS_DIRECTORY);
+    let index = location.find(&separator)?;
+    let (transaction_id, path) = location[index + separator.len()..].split_once('/').unwrap_or((&location[index + separator.len()..], ""));
+    Some((location[..index].to_string(), transaction_id.to_string(), path.trim_matches('/').to_string()))
+}
+
+fn check_open(store: &dyn CacheStore, transaction_id: &str) -> Result<(), capnp::Error> {
+    validate_transaction_id(transaction_id)?;
+    match store.get(&transaction_file_key(transaction_id))? {
+        Some(_) => Ok(()),
+        None => Err(capnp::Error::failed(format!("Transaction {} is not open", transaction_id)))
+    }
+}
+
+// Only the stores replacing all the files at once can commit a transaction,
+// and the local directories, replaced as a whole
+fn check_commit(store: &dyn CacheStore, location: &str) -> Result<(), capnp::Error> {
+    if store.atomic_commit() || super::is_local(location) {
+        return Ok(());
+    }
+    Err(capnp::Error::failed(format!("The cache store of {} cannot commit a transaction at once, its files are replaced one at a time", location)))
+}
+
+// Lock of a local cache directory, see LocalDirectoryStore::lock: exclusive
+// to commit, shared to write in the directory. None for the other stores.
+fn lock_local(location: &str, exclusive: bool) -> Result<Option<std::fs::File>, capnp::Error> {
+    if !super::is_local(location) {
+        return Ok(None);
+    }
+    Ok(Some(LocalDirectoryStore::new(location).lock(exclusive)?))
+}
+
+/// Begin a transaction on the cache, returns its id. The S3 store cannot
+/// have transactions, see CacheStore::atomic_commit.
+pub fn begin(location: &str) -> Result<String, capnp::Error> {
+    let store = super::open_location(location)?;
+    check_commit(store.as_ref(), location)?;
+    let _lock = lock_local(location, false)?;
+    let now = chrono::Utc::now();
+    let transaction_id = format!(
+        "{}-{}-{}",
+        now.format("%Y%m%d%H%M%S%f"), std::process::id(), TRANSACTIONS_COUNT.fetch_add(1, Ordering::Relaxed)
+    );
+    let transaction_json = serde_json::json!({ "id": transaction_id, "begun_at": now.to_rfc3339() });
+    store.put(&transaction_file_key(&transaction_id), transaction_json.to_string().as_bytes())?;
+    Ok(transaction_id)
+}
+
+// Remove the staged files and the transaction file with the changes
+fn close_changes(changes: &mut CacheChanges, transaction_id: &str, staged_keys: &[String]) {
+    for key in staged_keys.iter() {
+        changes.delete(key);
+    }
+    changes.delete(&transaction_file_key(transaction_id));
+}
+
+// Remove the shadow directory of a local cache, the directories of the
+// staged files are left empty otherwise
+fn remove_staging_directory(location: &str, transaction_id: &str) {
+    if super::is_local(location) {
+        let _ = std::fs::remove_dir_all(std::path::Path::new(location).join(staging_directory(transaction_id)));
+    }
+}
+
+/// Replace the cache files with the files staged in the transaction, all at
+/// once, and close the transaction: in a single commit of the memory and
+/// SQLite stores, see CacheStore::commit, and by replacing a local directory
+/// as a whole, see LocalDirectoryStore::commit_by_exchange. The transaction
+/// stays open if the commit fails, to commit it again. Returns the number of
+/// files written or removed.
+pub fn commit(location: &str, transaction_id: &str) -> Result<usize, capnp::Error> {
+    let store = super::open_location(location)?;
+    check_commit(store.as_ref(), location)?;
+    let _lock = lock_local(location, true)?;
+    check_open(store.as_ref(), transaction_id)?;
+    let staging_prefix = format!("{}/", staging_directory(transaction_id));
+    let staged_keys = store.list(&staging_prefix)?;
+    let mut changes = CacheChanges::default();
+    for staged_key in staged_keys.iter() {
+        let key = &staged_key[staging_prefix.len()..];
+        match key.strip_suffix(DELETED_FILE_SUFFIX) {
+            Some(deleted_key) => changes.delete(deleted_key),
+            None => {
+                let content = store.get(staged_key)?.ok_or_else(|| capnp::Error::failed(format!("Staged file {} was removed during the commit", staged_key)))?;
+                changes.put(key, content);
+            }
+        }
+    }
+    let files_count = changes.puts.len() + changes.deletes.len();
+    close_changes(&mut changes, transaction_id, &staged_keys);
+    if super::is_local(location) {
+        LocalDirectoryStore::new(location).commit_by_exchange(changes)?;
+    } else {
+        store.commit(changes)?;
+    }
+    remove_staging_directory(location, transaction_id);
+    Ok(files_count)
+}
+
+/// Discard the files staged in the transaction, the cache is unchanged
+pub fn rollback(location: &str, transaction_id: &str) -> Result<(), capnp::Error> {
+    let store = super::open_location(location)?;
+    let _lock = lock_local(location, false)?;
+    check_open(store.as_ref(), transaction_id)?;
+    let staged_keys = store.list(&format!("{}/", staging_directory(transaction_id)))?;
+    let mut changes = CacheChanges::default();
+    close_changes(&mut changes, transaction_id, &staged_keys);
+    store.commit(changes)?;
+    remove_staging_directory(location, transaction_id);
+    Ok(())
+}
+
+/// Transactions of the cache, with the time they were begun at. The
+/// transactions with no time are not open, their staged files were left by
+/// an interrupted commit or rollback.
+pub fn transactions(store: &dyn CacheStore) -> Result<Vec<(String, Option<BegunAt>)>, capnp::Error> {
+    let transactions_prefix = format!("{}/", TRANSACTIONS_DIRECTORY);
+    let mut transaction_ids : BTreeSet<String> = BTreeSet::new();
+    for key in store.list(&transactions_prefix)? {
+        let name = &key[transactions_prefix.len()..];
+        match name.split_once('/') {
+            Some((transaction_id, _)) => transaction_ids.insert(transaction_id.to_string()),
+            None => transaction_ids.insert(name.strip_suffix(".json").unwrap_or(name).to_string())
+        };
+    }
+    let mut transactions = Vec::with_capacity(transaction_ids.len());
+    for transaction_id in transaction_ids {
+        let begun_at = store.get(&transaction_file_key(&transaction_id))?
+            .and_then(|content| serde_json::from_slice::<serde_json::Value>(&content).ok())
+            .and_then(|transaction_json| transaction_json["begun_at"].as_str().and_then(|begun_at| chrono::DateTime::parse_from_rfc3339(begun_at).ok()));
+        transactions.push((transaction_id, begun_at));
+    }
+    Ok(transactions)
+}
+
+/// Keys of the staged files and of the transaction file of a transaction
+pub fn transaction_keys(store: &dyn CacheStore, transaction_id: &str) -> Result<Vec<String>, capnp::Error> {
+    let mut keys = store.list(&format!("{}/", staging_directory(transaction_id)))?;
+    if store.get(&transaction_file_key(transaction_id))?.is_some() {
+        keys.push(transaction_file_key(transaction_id));
+    }
+    Ok(keys)
+}
+
+/// Store of a location in the shadow directory of a transaction: the files
+/// are written to the shadow directory and read from it first, then from the
+/// cache
+pub struct TransactionStore {
+    // cache location, locked to stage the files
+    location: String,
+    cache: Box<dyn CacheStore>,
+    staging: Box<dyn CacheStore>,
+}
+
+impl TransactionStore {
+
+    /// Store of the path in the cache, for an open transaction
+    pub fn open(location: &str, transaction_id: &str, path: &str) -> Result<Self, capnp::Error> {
+        let cache_root = super::open_location(location)?;
+        check_open(cache_root.as_ref(), transaction_id)?;
+        let cache_location = if path.is_empty() { location.to_string() } else { format!("{}/{}", location.trim_end_matches('/'), path) };
+        let staging_location = format!("{}/{}", transaction_location(location, transaction_id)?, path);
+        Ok(TransactionStore {
+            location: location.to_string(),
+            cache: super::open_location(&cache_location)?,
+            staging: super::open_location(staging_location.trim_end_matches('/'))?,
+        })
+    }
+
+    fn staged_changes(changes: CacheChanges) -> CacheChanges {
+        let mut staged_changes = CacheChanges::default();
+        for (key, content) in changes.puts {
+            staged_changes.delete(&deleted_file_key(&key));
+            staged_changes.put(&key, content);
+        }
+        for key in changes.deletes.iter() {
+            staged_changes.delete(key);
+            staged_changes.put(&deleted_file_key(key), Vec::new());
+        }
+        staged_changes
+    }
+
+}
+
+impl CacheStore for TransactionStore {
+
+    fn get(&self, key: &str) -> Result<Option<Vec<u8>>, capnp::Error> {
+        if let Some(content) = self.staging.get(key)? {
+            return Ok(Some(content));
+        }
+        if self.staging.get(&deleted_file_key(key))?.is_some() {
+            return Ok(None);
+        }
+        self.cache.get(key)
+    }
+
+    fn put(&self, key: &str, content: &[u8]) -> Result<(), capnp::Error> {
+        let mut changes = CacheChanges::default();
+        changes.put(key, content.to_vec());
+        self.commit(changes)
+    }
+
+    fn list(&self, prefix: &str) -> Result<Vec<String>, capnp::Error> {
+        let transactions_prefix = format!("{}/", TRANSACTIONS_DIRECTORY);
+        let mut keys : BTreeSet<String> = self.cache.list(prefix)?.into_iter().filter(|key| !key.starts_with(&transactions_prefix)).collect();
+        for staged_key in self.staging.list(prefix)? {
+            match staged_key.strip_suffix(DELETED_FILE_SUFFIX) {
+                Some(deleted_key) => { keys.remove(deleted_key); },
+                None => { keys.insert(staged_key); }
+            }
+        }
+        Ok(keys.into_iter().collect())
+    }
+
+    fn delete(&self, key: &str) -> Result<(), capnp::Error> {
+        let mut changes = CacheChanges::default();
+        changes.delete(key);
+        self.commit(changes)
+    }
+
+    fn commit(&self, changes: CacheChanges) -> Result<(), capnp::Error> {
+        // the cache is only changed when the transaction is committed, the
+        // files staged while another transaction is committed would be lost
+        let _lock = lock_local(&self.location, false)?;
+        self.staging.commit(TransactionStore::staged_changes(changes))
+    }
+
+    fn atomic_commit(&self) -> bool {
+        self.staging.atomic_commit()
+    }
+
+}
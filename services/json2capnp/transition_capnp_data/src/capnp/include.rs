@@ -38,3 +38,38 @@ pub mod scenarioCollection_capnp {
 pub mod agencyCollection_capnp {
   include!("./agencyCollection_capnp.rs");
 }
+
+#[allow(non_snake_case)]
+pub mod householdCollection_capnp {
+  include!("./householdCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod personCollection_capnp {
+  include!("./personCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod odTripCollection_capnp {
+  include!("./odTripCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod stationCollection_capnp {
+  include!("./stationCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod zoneCollection_capnp {
+  include!("./zoneCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod fareCollection_capnp {
+  include!("./fareCollection_capnp.rs");
+}
+
+#[allow(non_snake_case)]
+pub mod vehicleTypeCollection_capnp {
+  include!("./vehicleTypeCollection_capnp.rs");
+}
@@ -0,0 +1,287 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Price of journeys with the fare collection (fares.capnpbin) */
+
+use std::collections::{HashMap, HashSet};
+use crate::cache::read_collection_file;
+use crate::serialization::{fare_collection, line_collection};
+
+/// Transit leg of a journey, from the boarding to the alighting node of a
+/// line. The agency is found from the line when it is not set.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FareLeg {
+    pub line_uuid: String,
+    pub agency_uuid: Option<String>,
+    pub boarding_node_uuid: String,
+    pub alighting_node_uuid: String,
+    pub boarding_time_seconds: i64,
+    pub alighting_time_seconds: i64,
+}
+
+impl FareLeg {
+
+    /// Parse a leg from { line_id, agency_id, boarding_node_id,
+    /// alighting_node_id, boarding_time_seconds, alighting_time_seconds }
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        let string = |attribute: &str| json[attribute].as_str().map(|value| value.to_string()).ok_or_else(|| capnp::Error::failed(format!("Missing {} in fare leg", attribute)));
+        let time = |attribute: &str| json[attribute].as_i64().ok_or_else(|| capnp::Error::failed(format!("Missing {} in fare leg", attribute)));
+        Ok(FareLeg {
+            line_uuid: string("line_id")?,
+            agency_uuid: json["agency_id"].as_str().map(|agency_uuid| agency_uuid.to_string()),
+            boarding_node_uuid: string("boarding_node_id")?,
+            alighting_node_uuid: string("alighting_node_id")?,
+            boarding_time_seconds: time("boarding_time_seconds")?,
+            alighting_time_seconds: time("alighting_time_seconds")?,
+        })
+    }
+}
+
+/// Legs of a journey returned by the transit routing, from its boarding and
+/// unboarding steps
+pub fn journey_fare_legs(journey: &serde_json::Value) -> Vec<FareLeg> {
+    let steps = journey["steps"].as_array().map(|steps| steps.as_slice()).unwrap_or_default();
+    let mut legs : Vec<FareLeg> = Vec::new();
+    let mut boarding : Option<&serde_json::Value> = None;
+    for step in steps {
+        match step["action"].as_str() {
+            Some("boarding") => boarding = Some(step),
+            Some("unboarding") => if let Some(boarding_step) = boarding.take() {
+                legs.push(FareLeg {
+                    line_uuid: boarding_step["line_id"].as_str().unwrap_or("").to_string(),
+                    agency_uuid: None,
+                    boarding_node_uuid: boarding_step["node_id"].as_str().unwrap_or("").to_string(),
+                    alighting_node_uuid: step["node_id"].as_str().unwrap_or("").to_string(),
+                    boarding_time_seconds: boarding_step["departure_time_seconds"].as_i64().unwrap_or(0),
+                    alighting_time_seconds: step["arrival_time_seconds"].as_i64().unwrap_or(0),
+                });
+            },
+            _ => ()
+        }
+    }
+    legs
+}
+
+struct FareProduct {
+    uuid: String,
+    amount_cents: i64,
+    currency: Option<String>,
+}
+
+// Empty fields of the rules match any value
+struct LegRule {
+    leg_group_id: Option<String>,
+    agency_uuid: Option<String>,
+    line_uuid: Option<String>,
+    from_area_uuid: Option<String>,
+    to_area_uuid: Option<String>,
+    product: usize,
+    priority: i64,
+}
+
+impl LegRule {
+
+    // A line is more specific than areas, which are more specific than an agency
+    fn specificity(&self) -> (bool, usize, bool) {
+        (self.line_uuid.is_some(), [&self.from_area_uuid, &self.to_area_uuid].iter().filter(|area| area.is_some()).count(), self.agency_uuid.is_some())
+    }
+}
+
+struct TransferRule {
+    from_leg_group_id: Option<String>,
+    to_leg_group_id: Option<String>,
+    transfer_count: Option<i64>,
+    duration_limit_seconds: Option<i64>,
+    duration_limit_type: i64,
+    fare_transfer_type: i64,
+    product: Option<usize>,
+}
+
+pub struct FareCalculator {
+    products: Vec<FareProduct>,
+    area_nodes: HashMap<String, HashSet<String>>,
+    leg_rules: Vec<LegRule>,
+    transfer_rules: Vec<TransferRule>,
+    line_agencies: HashMap<String, String>,
+}
+
+fn optional(value: &serde_json::Value) -> Option<String> {
+    value.as_str().filter(|value| !value.is_empty()).map(|value| value.to_string())
+}
+
+fn matches(rule_value: &Option<String>, value: Option<&str>) -> bool {
+    rule_value.as_deref().is_none_or(|rule_value| Some(rule_value) == value)
+}
+
+impl FareCalculator {
+
+    /// Build the calculator from the json returned by
+    /// fare_collection::read_collection, and optionally the one of
+    /// line_collection::read_collection to find the agency of the legs
+    pub fn from_collection_json(fares_json: &serde_json::Value, lines_json: Option<&serde_json::Value>) -> Result<Self, capnp::Error> {
+        let fares = &fares_json["fares"];
+        let array = |attribute: &str| fares[attribute].as_array().map(|array| array.as_slice()).unwrap_or_default();
+
+        let products : Vec<FareProduct> = array("fare_products").iter().map(|product| FareProduct {
+            uuid: product["id"].as_str().unwrap_or("").to_string(),
+            amount_cents: (product["amount"].as_f64().unwrap_or(0.0) * 100.0).round() as i64,
+            currency: optional(&product["currency"]),
+        }).collect();
+        let product_index = |product_uuid: &str| products.iter().position(|product| product.uuid == product_uuid).ok_or_else(|| capnp::Error::failed(format!("Unknown fare product {}", product_uuid)));
+
+        let area_nodes = array("fare_areas").iter().map(|area| (
+            area["id"].as_str().unwrap_or("").to_string(),
+            area["nodes"].as_array().map(|nodes| nodes.iter().filter_map(|node_uuid| node_uuid.as_str().map(|node_uuid| node_uuid.to_string())).collect()).unwrap_or_default()
+        )).collect();
+
+        let leg_rules = array("fare_leg_rules").iter().map(|rule| Ok(LegRule {
+            leg_group_id: optional(&rule["leg_group_id"]),
+            agency_uuid: optional(&rule["agency_id"]),
+            line_uuid: optional(&rule["line_id"]),
+            from_area_uuid: optional(&rule["from_area_id"]),
+            to_area_uuid: optional(&rule["to_area_id"]),
+            product: product_index(rule["fare_product_id"].as_str().unwrap_or(""))?,
+            priority: rule["rule_priority"].as_i64().unwrap_or(0),
+        })).collect::<Result<Vec<LegRule>, capnp::Error>>()?;
+
+        let transfer_rules = array("fare_transfer_rules").iter().map(|rule| Ok(TransferRule {
+            from_leg_group_id: optional(&rule["from_leg_group_id"]),
+            to_leg_group_id: optional(&rule["to_leg_group_id"]),
+            transfer_count: rule["transfer_count"].as_i64().filter(|count| *count >= 0),
+            duration_limit_seconds: rule["duration_limit_seconds"].as_i64().filter(|duration| *duration >= 0),
+            duration_limit_type: rule["duration_limit_type"].as_i64().unwrap_or(0),
+            fare_transfer_type: rule["fare_transfer_type"].as_i64().unwrap_or(0),
+            product: match optional(&rule["fare_product_id"]) {
+                Some(product_uuid) => Some(product_index(&product_uuid)?),
+                None => None
+            },
+        })).collect::<Result<Vec<TransferRule>, capnp::Error>>()?;
+
+        let line_agencies = lines_json.and_then(|lines_json| lines_json["lines"].as_array()).map(|lines| lines.iter().map(|line| (
+            line["id"].as_str().unwrap_or("").to_string(),
+            line["agency_id"].as_str().unwrap_or("").to_string()
+        )).collect()).unwrap_or_default();
+
+        Ok(FareCalculator { products, area_nodes, leg_rules, transfer_rules, line_agencies })
+    }
+
+    /// Load the fares and lines of a cache directory
+    pub fn load(cache_directory_path: &str) -> Result<Self, capnp::Error> {
+        let fares_json = read_collection_file(cache_directory_path, "fares", &fare_collection::read_collection)?;
+        let lines_json = read_collection_file(cache_directory_path, "lines", &line_collection::read_collection).ok();
+        FareCalculator::from_collection_json(&fares_json, lines_json.as_ref())
+    }
+
+    fn in_area(&self, area_uuid: &Option<String>, node_uuid: &str) -> bool {
+        area_uuid.as_ref().is_none_or(|area_uuid| self.area_nodes.get(area_uuid).is_some_and(|nodes| nodes.contains(node_uuid)))
+    }
+
+    // Matching rule with the highest priority, then the most specific, then the cheapest
+    fn leg_rule(&self, leg: &FareLeg) -> Option<&LegRule> {
+        let agency_uuid = leg.agency_uuid.as_deref().or_else(|| self.line_agencies.get(&leg.line_uuid).map(|agency_uuid| agency_uuid.as_str()));
+        self.leg_rules.iter()
+            .filter(|rule| matches(&rule.line_uuid, Some(&leg.line_uuid)) && matches(&rule.agency_uuid, agency_uuid))
+            .filter(|rule| self.in_area(&rule.from_area_uuid, &leg.boarding_node_uuid) && self.in_area(&rule.to_area_uuid, &leg.alighting_node_uuid))
+            .min_by_key(|rule| (-rule.priority, std::cmp::Reverse(rule.specificity()), self.products[rule.product].amount_cents))
+    }
+
+    /// Price the legs of a journey. Each leg is priced by its leg rule,
+    /// unless a transfer rule applies from the previous leg: the transfer
+    /// fee is then charged instead (0: A + AB), in addition to the leg price
+    /// (1: A + AB + B), or instead of the current leg price and of all the
+    /// amounts charged since the first leg of the fare (2: AB, then ABC).
+    /// The transfer count and duration limits are counted from the first
+    /// leg of the fare.
+    pub fn price(&self, legs: &[FareLeg]) -> Result<serde_json::Value, capnp::Error> {
+        let mut total_amount_cents : i64 = 0;
+        let mut currencies : HashSet<&str> = HashSet::new();
+        let mut legs_json : Vec<serde_json::Value> = Vec::with_capacity(legs.len());
+        // first leg, leg group of the previous leg, amount charged since the first leg and transfers count of the current fare
+        let mut fare_start : Option<(usize, Option<&str>, i64, i64)> = None;
+
+        for (i, leg) in legs.iter().enumerate() {
+            let rule = self.leg_rule(leg).ok_or_else(|| capnp::Error::failed(format!("No fare leg rule matches leg {} on line {}", i, leg.line_uuid)))?;
+            let product = &self.products[rule.product];
+            if let Some(currency) = &product.currency {
+                currencies.insert(currency);
+            }
+            let leg_group_id = rule.leg_group_id.as_deref();
+
+            let transfer = fare_start.and_then(|(first_leg, previous_leg_group_id, fare_amount_cents, transfers_count)| {
+                let first_leg = &legs[first_leg];
+                self.transfer_rules.iter().find(|transfer_rule| {
+                    let duration_seconds = match transfer_rule.duration_limit_type {
+                        1 => leg.boarding_time_seconds - first_leg.boarding_time_seconds,
+                        2 => leg.boarding_time_seconds - first_leg.alighting_time_seconds,
+                        3 => leg.alighting_time_seconds - first_leg.alighting_time_seconds,
+                        _ => leg.alighting_time_seconds - first_leg.boarding_time_seconds
+                    };
+                    matches(&transfer_rule.from_leg_group_id, previous_leg_group_id)
+                        && matches(&transfer_rule.to_leg_group_id, leg_group_id)
+                        && transfer_rule.transfer_count.is_none_or(|count| transfers_count < count)
+                        && transfer_rule.duration_limit_seconds.is_none_or(|limit| duration_seconds <= limit)
+                }).map(|transfer_rule| (transfer_rule, fare_amount_cents))
+            });
+
+            let amount_cents = match transfer {
+                Some((transfer_rule, fare_amount_cents)) => {
+                    let fee_cents = transfer_rule.product.map(|product| self.products[product].amount_cents).unwrap_or(0);
+                    if let Some(currency) = transfer_rule.product.and_then(|product| self.products[product].currency.as_deref()) {
+                        currencies.insert(currency);
+                    }
+                    let amount_cents = match transfer_rule.fare_transfer_type {
+                        1 => fee_cents + product.amount_cents,
+                        2 => fee_cents - fare_amount_cents,
+                        _ => fee_cents
+                    };
+                    fare_start = fare_start.map(|(first_leg, _, _, transfers_count)| (first_leg, leg_group_id, fare_amount_cents + amount_cents, transfers_count + 1));
+                    amount_cents
+                },
+                None => {
+                    fare_start = Some((i, leg_group_id, product.amount_cents, 0));
+                    product.amount_cents
+                }
+            };
+            total_amount_cents += amount_cents;
+            legs_json.push(json!({
+                "line_id": leg.line_uuid,
+                "fare_product_id": product.uuid,
+                "leg_group_id": leg_group_id,
+                "transfer": transfer.is_some(),
+                "amount": amount_cents as f64 / 100.0
+            }));
+        }
+
+        if currencies.len() > 1 {
+            let mut currencies : Vec<&str> = currencies.into_iter().collect();
+            currencies.sort_unstable();
+            return Err(capnp::Error::failed(format!("Fare products have different currencies: {}", currencies.join(", "))));
+        }
+        Ok(json!({
+            "total_amount": total_amount_cents as f64 / 100.0,
+            "currency": currencies.into_iter().next(),
+            "legs": legs_json
+        }))
+    }
+}
+
+/// Price journeys with the fares of a cache: the query has the `legs` of a
+/// journey (see FareLeg::from_json), or `journeys` [{ legs }] to price many
+/// journeys at once, returned in `fares`.
+pub fn fare_query(cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    let journey_legs = |journey: &serde_json::Value| -> Result<Vec<FareLeg>, capnp::Error> {
+        journey["legs"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Missing legs")))?.iter().map(FareLeg::from_json).collect()
+    };
+    let calculator = FareCalculator::load(cache_directory_path)?;
+    match query["journeys"].as_array() {
+        Some(journeys) => Ok(json!({
+            "fares": journeys.iter().map(|journey| calculator.price(&journey_legs(journey)?)).collect::<Result<Vec<serde_json::Value>, capnp::Error>>()?
+        })),
+        None => calculator.price(&journey_legs(query)?)
+    }
+}
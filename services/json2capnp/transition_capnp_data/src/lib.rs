@@ -8,6 +8,26 @@
 
 mod utils;
 pub mod serialization;
+pub mod cache;
+pub mod cache_store;
+pub mod cache_gc;
+pub mod node_spatial_index;
+pub mod transferable_nodes;
+pub mod path_analytics;
+pub mod service_calendar;
+pub mod scenario_resolution;
+pub mod scenario_extraction;
+pub mod cache_clipping;
+pub mod timetable_validation;
+pub mod service_kpis;
+pub mod vehicle_blocking;
+pub mod transit_routing;
+pub mod accessibility_map;
+pub mod od_matrix;
+pub mod station_validation;
+pub mod zone_lookup;
+pub mod fare_calculator;
+pub mod vehicle_types;
 
 #[macro_use]
 extern crate serde_json;
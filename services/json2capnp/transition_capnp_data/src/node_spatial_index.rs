@@ -0,0 +1,200 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* R-tree spatial index over the node collection (nodes.capnpbin) */
+
+use rstar::primitives::GeomWithData;
+use rstar::{RTree, AABB};
+use crate::serialization::node_collection;
+use crate::utils::{haversine_distance_meters, EARTH_RADIUS_METERS};
+
+#[derive(Debug, Clone)]
+pub struct IndexedNode {
+    pub uuid: String,
+    pub integer_id: u32,
+    pub longitude: f64,
+    pub latitude: f64,
+    pub routing_radius_meters: f64, // 0 when not set
+    pub is_enabled: bool,           // null is_enabled is considered enabled
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeDistance<'a> {
+    pub node: &'a IndexedNode,
+    // distance from the query point to the edge of the node's routing radius (0 if inside it)
+    pub distance_meters: f64,
+    // distance from the query point to the node coordinates
+    pub center_distance_meters: f64,
+}
+
+// Nodes are indexed twice: on the unit sphere, where the euclidean (chord)
+// distance orders nodes like the haversine distance does, for nearest and
+// radius queries, and in longitude/latitude for bounding box queries.
+pub struct NodeSpatialIndex {
+    nodes: Vec<IndexedNode>,
+    sphere_tree: RTree<GeomWithData<[f64; 3], usize>>,
+    lon_lat_tree: RTree<GeomWithData<[f64; 2], usize>>,
+    max_routing_radius_meters: f64,
+}
+
+fn to_unit_sphere(longitude: f64, latitude: f64) -> [f64; 3] {
+    let (lon, lat) = (longitude.to_radians(), latitude.to_radians());
+    [lat.cos() * lon.cos(), lat.cos() * lon.sin(), lat.sin()]
+}
+
+// Squared chord length on the unit sphere for a great circle distance in meters
+fn squared_chord_for_distance(distance_meters: f64) -> f64 {
+    let angle = (distance_meters / EARTH_RADIUS_METERS).min(std::f64::consts::PI);
+    let chord = 2.0 * (angle / 2.0).sin();
+    chord * chord
+}
+
+impl NodeSpatialIndex {
+
+    pub fn new(nodes: Vec<IndexedNode>) -> Self {
+        let sphere_points = nodes.iter().enumerate().map(|(i, node)| GeomWithData::new(to_unit_sphere(node.longitude, node.latitude), i)).collect();
+        let lon_lat_points = nodes.iter().enumerate().map(|(i, node)| GeomWithData::new([node.longitude, node.latitude], i)).collect();
+        let max_routing_radius_meters = nodes.iter().fold(0.0, |max: f64, node| max.max(node.routing_radius_meters));
+        NodeSpatialIndex {
+            nodes,
+            sphere_tree: RTree::bulk_load(sphere_points),
+            lon_lat_tree: RTree::bulk_load(lon_lat_points),
+            max_routing_radius_meters,
+        }
+    }
+
+    /// Build the index from the json returned by node_collection::read_collection
+    pub fn from_collection_json(json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        let features = json["nodes"]["features"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Nodes json is not a FeatureCollection")))?;
+        let mut nodes : Vec<IndexedNode> = Vec::with_capacity(features.len());
+        for feature in features {
+            let properties = &feature["properties"];
+            let coordinates = &feature["geometry"]["coordinates"];
+            nodes.push(IndexedNode {
+                uuid: properties["id"].as_str().unwrap_or("").to_string(),
+                integer_id: properties["integer_id"].as_u64().unwrap_or(0) as u32,
+                longitude: coordinates[0].as_f64().unwrap_or(0.0),
+                latitude: coordinates[1].as_f64().unwrap_or(0.0),
+                routing_radius_meters: properties["routing_radius_meters"].as_f64().unwrap_or(0.0).max(0.0),
+                is_enabled: properties["is_enabled"].as_bool().unwrap_or(true),
+            });
+        }
+        Ok(NodeSpatialIndex::new(nodes))
+    }
+
+    pub fn read_collection(file: &mut dyn std::io::Read) -> Result<Self, capnp::Error> {
+        NodeSpatialIndex::from_collection_json(&node_collection::read_collection(file)?)
+    }
+
+    pub fn nodes(&self) -> &Vec<IndexedNode> {
+        &self.nodes
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    fn node_distance(&self, index: usize, longitude: f64, latitude: f64) -> NodeDistance<'_> {
+        let node = &self.nodes[index];
+        let center_distance_meters = haversine_distance_meters(longitude, latitude, node.longitude, node.latitude);
+        NodeDistance {
+            node,
+            distance_meters: (center_distance_meters - node.routing_radius_meters).max(0.0),
+            center_distance_meters,
+        }
+    }
+
+    /// The `count` enabled nodes closest to a point, sorted by distance
+    pub fn nearest(&self, longitude: f64, latitude: f64, count: usize) -> Vec<NodeDistance<'_>> {
+        // the count comes from requests, there are never more than the nodes
+        let count = count.min(self.nodes.len());
+        let mut result : Vec<NodeDistance> = Vec::new();
+        if count == 0 {
+            return result;
+        }
+        let query_point = to_unit_sphere(longitude, latitude);
+        for point in self.sphere_tree.nearest_neighbor_iter(&query_point) {
+            let node_distance = self.node_distance(point.data, longitude, latitude);
+            // The routing radius can bring a farther node closer than the
+            // current candidates, so stop only once no remaining node can.
+            if result.len() == count && node_distance.center_distance_meters - self.max_routing_radius_meters > result[count - 1].distance_meters {
+                break;
+            }
+            if !node_distance.node.is_enabled {
+                continue;
+            }
+            let position = result.partition_point(|candidate| candidate.distance_meters <= node_distance.distance_meters);
+            if position < count {
+                result.insert(position, node_distance);
+                result.truncate(count);
+            }
+        }
+        result
+    }
+
+    /// All enabled nodes within `radius_meters` of a point, sorted by distance
+    pub fn within_radius(&self, longitude: f64, latitude: f64, radius_meters: f64) -> Vec<NodeDistance<'_>> {
+        let query_point = to_unit_sphere(longitude, latitude);
+        let max_squared_chord = squared_chord_for_distance(radius_meters + self.max_routing_radius_meters);
+        let mut result : Vec<NodeDistance> = self.sphere_tree.locate_within_distance(query_point, max_squared_chord)
+            .map(|point| self.node_distance(point.data, longitude, latitude))
+            .filter(|node_distance| node_distance.node.is_enabled && node_distance.distance_meters <= radius_meters)
+            .collect();
+        result.sort_by(|a, b| a.distance_meters.partial_cmp(&b.distance_meters).unwrap());
+        result
+    }
+
+    /// All enabled nodes whose coordinates are within `distance_meters` of a
+    /// point, ignoring routing radiuses, sorted by distance
+    pub fn within_center_distance(&self, longitude: f64, latitude: f64, distance_meters: f64) -> Vec<NodeDistance<'_>> {
+        let query_point = to_unit_sphere(longitude, latitude);
+        let mut result : Vec<NodeDistance> = self.sphere_tree.locate_within_distance(query_point, squared_chord_for_distance(distance_meters))
+            .map(|point| self.node_distance(point.data, longitude, latitude))
+            .filter(|node_distance| node_distance.node.is_enabled && node_distance.center_distance_meters <= distance_meters)
+            .collect();
+        result.sort_by(|a, b| a.center_distance_meters.partial_cmp(&b.center_distance_meters).unwrap());
+        result
+    }
+
+    /// All enabled nodes inside a longitude/latitude bounding box
+    pub fn within_bbox(&self, min_longitude: f64, min_latitude: f64, max_longitude: f64, max_latitude: f64) -> Vec<&IndexedNode> {
+        let envelope = AABB::from_corners([min_longitude, min_latitude], [max_longitude, max_latitude]);
+        self.lon_lat_tree.locate_in_envelope(&envelope)
+            .map(|point| &self.nodes[point.data])
+            .filter(|node| node.is_enabled)
+            .collect()
+    }
+}
+
+pub fn node_distances_to_json(node_distances: &[NodeDistance]) -> serde_json::Value {
+    let nodes_json : Vec<serde_json::Value> = node_distances.iter().map(|node_distance| json!({
+        "id": node_distance.node.uuid,
+        "integer_id": node_distance.node.integer_id,
+        "coordinates": [node_distance.node.longitude, node_distance.node.latitude],
+        "distance_meters": node_distance.distance_meters,
+        "center_distance_meters": node_distance.center_distance_meters
+    })).collect();
+    json!({
+        "nodes": nodes_json
+    })
+}
+
+pub fn nodes_to_json(nodes: &[&IndexedNode]) -> serde_json::Value {
+    let nodes_json : Vec<serde_json::Value> = nodes.iter().map(|node| json!({
+        "id": node.uuid,
+        "integer_id": node.integer_id,
+        "coordinates": [node.longitude, node.latitude]
+    })).collect();
+    json!({
+        "nodes": nodes_json
+    })
+}
@@ -0,0 +1,263 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Batch routing of origin-destination trips, in parallel, streaming the results */
+
+use std::fs::File;
+use std::io::{Read, Write};
+use rayon::prelude::*;
+use crate::transit_routing::{Place, RoutingParameters, TransitNetwork};
+
+// Trips routed in parallel before their results are written
+const CHUNK_SIZE: usize = 1000;
+
+pub const RESULT_COLUMNS: [&str; 14] = [
+    "id",
+    "status",
+    "departure_time_seconds",
+    "arrival_time_seconds",
+    "total_travel_time_seconds",
+    "in_vehicle_time_seconds",
+    "access_travel_time_seconds",
+    "egress_travel_time_seconds",
+    "transfer_walking_time_seconds",
+    "waiting_time_seconds",
+    "first_waiting_time_seconds",
+    "number_of_transfers",
+    "walking_only_travel_time_seconds",
+    "error",
+];
+
+#[derive(Debug, Clone)]
+pub struct OdTrip {
+    pub id: String,
+    pub origin: Place,
+    pub destination: Place,
+    pub departure_time_seconds: i64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OutputFormat {
+    Csv,
+    Json,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = capnp::Error;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format {
+            "csv" => Ok(OutputFormat::Csv),
+            "json" => Ok(OutputFormat::Json),
+            _ => Err(capnp::Error::failed(format!("Invalid format {}, expected csv or json", format)))
+        }
+    }
+}
+
+/// Parse a departure time in seconds since midnight or as HH:MM[:SS]
+pub fn parse_departure_time(time_str: &str) -> Result<i64, capnp::Error> {
+    let time_str = time_str.trim();
+    if let Ok(seconds) = time_str.parse::<i64>() {
+        return Ok(seconds);
+    }
+    let parts : Vec<Option<i64>> = time_str.split(':').map(|part| part.parse::<i64>().ok().filter(|_| part.len() == 2 || part.len() == 1)).collect();
+    match parts.as_slice() {
+        [Some(hours), Some(minutes)] if *minutes < 60 => Ok(hours * 3600 + minutes * 60),
+        [Some(hours), Some(minutes), Some(seconds)] if *minutes < 60 && *seconds < 60 => Ok(hours * 3600 + minutes * 60 + seconds),
+        _ => Err(capnp::Error::failed(format!("Invalid departure time {}, expected seconds since midnight or HH:MM[:SS]", time_str)))
+    }
+}
+
+/// One trip per origin and destination pair, with the "<origin index>_<destination index>" id
+pub fn od_trips_from_pairs(origins: &[Place], destinations: &[Place], departure_time_seconds: i64) -> Vec<OdTrip> {
+    origins.iter().enumerate().flat_map(|(origin_index, origin)| {
+        destinations.iter().enumerate().map(move |(destination_index, destination)| OdTrip {
+            id: format!("{}_{}", origin_index, destination_index),
+            origin: origin.clone(),
+            destination: destination.clone(),
+            departure_time_seconds,
+        })
+    }).collect()
+}
+
+/// Read OD trips from a csv with the id, origin_longitude, origin_latitude,
+/// destination_longitude, destination_latitude and departure_time columns.
+/// Rows are numbered from 1 in the errors, which do not repeat the values.
+pub fn read_od_trips_csv<R: Read>(reader: R) -> Result<Vec<OdTrip>, capnp::Error> {
+    let mut csv_reader = csv::Reader::from_reader(reader);
+    let headers = csv_reader.headers().map_err(|e| capnp::Error::failed(format!("Cannot read the OD trips csv headers: {}", e)))?.clone();
+    let column = |name: &str| headers.iter().position(|header| header.trim() == name).ok_or_else(|| capnp::Error::failed(format!("Missing {} column in the OD trips csv", name)));
+    let id_column = column("id")?;
+    let coordinate_columns = [column("origin_longitude")?, column("origin_latitude")?, column("destination_longitude")?, column("destination_latitude")?];
+    let departure_time_column = column("departure_time")?;
+
+    let mut od_trips : Vec<OdTrip> = Vec::new();
+    for (i, record) in csv_reader.records().enumerate() {
+        let row_error = |error: String| capnp::Error::failed(format!("OD trips csv row {}: {}", i + 1, error));
+        let record = record.map_err(|e| row_error(e.to_string()))?;
+        let mut coordinates = [0.0; 4];
+        for (coordinate, column) in coordinates.iter_mut().zip(coordinate_columns.iter()) {
+            *coordinate = record.get(*column).unwrap_or("").trim().parse::<f64>().map_err(|_| row_error(format!("Invalid {}", &headers[*column])))?;
+        }
+        od_trips.push(OdTrip {
+            id: record.get(id_column).unwrap_or("").to_string(),
+            origin: Place::Coordinates(coordinates[0], coordinates[1]),
+            destination: Place::Coordinates(coordinates[2], coordinates[3]),
+            departure_time_seconds: parse_departure_time(record.get(departure_time_column).unwrap_or("")).map_err(|_| row_error(String::from("Invalid departure_time, expected seconds since midnight or HH:MM[:SS]")))?,
+        });
+    }
+    Ok(od_trips)
+}
+
+// Flat result of an OD trip, with the RESULT_COLUMNS attributes
+fn od_trip_result(network: &TransitNetwork, od_trip: &OdTrip, parameters: &RoutingParameters) -> serde_json::Value {
+    let mut result = json!({});
+    match network.earliest_arrival(&od_trip.origin, &od_trip.destination, od_trip.departure_time_seconds, parameters) {
+        Ok(journey) => {
+            for column in RESULT_COLUMNS.iter().skip(1) {
+                result[*column] = journey[*column].clone();
+            }
+            result["departure_time_seconds"] = json!(od_trip.departure_time_seconds);
+        },
+        Err(error) => {
+            result["status"] = json!("error");
+            result["departure_time_seconds"] = json!(od_trip.departure_time_seconds);
+            result["error"] = json!(error.extra);
+        }
+    }
+    result["id"] = json!(od_trip.id);
+    result
+}
+
+fn csv_value(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(value) => value.clone(),
+        value => value.to_string()
+    }
+}
+
+fn csv_bytes<I: IntoIterator<Item = Vec<String>>>(records: I) -> Result<Vec<u8>, capnp::Error> {
+    let mut csv_writer = csv::Writer::from_writer(Vec::new());
+    for record in records {
+        csv_writer.write_record(&record).map_err(|e| capnp::Error::failed(e.to_string()))?;
+    }
+    csv_writer.into_inner().map_err(|e| capnp::Error::failed(e.to_string()))
+}
+
+/// Route every OD trip on the network, in parallel by chunks of trips, and
+/// write the results of each chunk in the order of the trips, as csv rows
+/// or as the elements of a json array. Return the count of trips by status.
+pub fn compute_od_matrix<W: Write>(
+    network: &TransitNetwork,
+    od_trips: &[OdTrip],
+    parameters: &RoutingParameters,
+    format: OutputFormat,
+    writer: &mut W,
+) -> Result<serde_json::Value, capnp::Error> {
+    parameters.validate()?;
+    let mut write = |bytes: &[u8]| writer.write_all(bytes).and_then(|_| writer.flush()).map_err(|e| capnp::Error::failed(format!("Cannot write the OD matrix results: {}", e)));
+    match format {
+        OutputFormat::Csv => write(&csv_bytes([RESULT_COLUMNS.iter().map(|column| column.to_string()).collect()])?)?,
+        OutputFormat::Json => write(b"[")?
+    }
+
+    let mut counts = [0; 3];
+    for (chunk_index, chunk) in od_trips.chunks(CHUNK_SIZE).enumerate() {
+        let results : Vec<serde_json::Value> = chunk.par_iter().map(|od_trip| od_trip_result(network, od_trip, parameters)).collect();
+        for result in results.iter() {
+            match result["status"].as_str() {
+                Some("success") => counts[0] += 1,
+                Some("no_routing_found") => counts[1] += 1,
+                _ => counts[2] += 1
+            }
+        }
+        let bytes = match format {
+            OutputFormat::Csv => csv_bytes(results.iter().map(|result| RESULT_COLUMNS.iter().map(|column| csv_value(&result[*column])).collect()))?,
+            OutputFormat::Json => {
+                let separator = if chunk_index == 0 { "\n" } else { ",\n" };
+                format!("{}{}", separator, results.iter().map(|result| result.to_string()).collect::<Vec<String>>().join(",\n")).into_bytes()
+            }
+        };
+        write(&bytes)?;
+    }
+    if format == OutputFormat::Json {
+        write(if od_trips.is_empty() { b"]\n" } else { b"\n]\n" })?;
+    }
+
+    Ok(json!({
+        "trips_count": od_trips.len(),
+        "success_count": counts[0],
+        "no_routing_found_count": counts[1],
+        "error_count": counts[2]
+    }))
+}
+
+/// Trips, routing parameters and output format of an OD matrix query
+pub struct OdMatrixQuery {
+    pub scenario_uuid: String,
+    pub od_trips: Vec<OdTrip>,
+    pub parameters: RoutingParameters,
+    pub format: OutputFormat,
+}
+
+impl OdMatrixQuery {
+
+    /// Parse a query with the `scenario_id`, the trips as `od_trips` [{ id,
+    /// origin, destination, departure_time_seconds }], as `od_trips_csv`
+    /// content (see read_od_trips_csv), or as all the pairs of `origins` and
+    /// `destinations` leaving at `departure_time_seconds`, the output
+    /// `format`, csv by default, and the routing parameters. With
+    /// allow_file_path, the trips can also be read from an
+    /// `od_trips_csv_file_path`: only for the queries of local callers, never
+    /// for the requests of a server.
+    pub fn from_json(query: &serde_json::Value, allow_file_path: bool) -> Result<Self, capnp::Error> {
+        let scenario_uuid = query["scenario_id"].as_str().ok_or_else(|| capnp::Error::failed(String::from("Missing scenario_id")))?;
+        let parameters = RoutingParameters::from_json(query)?;
+        parameters.validate()?;
+        let format : OutputFormat = query["format"].as_str().unwrap_or("csv").parse()?;
+        let places = |attribute: &str| -> Result<Vec<Place>, capnp::Error> {
+            query[attribute].as_array().ok_or_else(|| capnp::Error::failed(format!("Missing {}", attribute)))?.iter().map(Place::from_json).collect()
+        };
+
+        let od_trips = if let Some(od_trips) = query["od_trips"].as_array() {
+            od_trips.iter().enumerate().map(|(i, od_trip)| Ok(OdTrip {
+                id: if od_trip["id"].is_null() { i.to_string() } else { csv_value(&od_trip["id"]) },
+                origin: Place::from_json(&od_trip["origin"])?,
+                destination: Place::from_json(&od_trip["destination"])?,
+                departure_time_seconds: od_trip["departure_time_seconds"].as_i64().ok_or_else(|| capnp::Error::failed(format!("Missing departure_time_seconds for OD trip {}", i)))?,
+            })).collect::<Result<Vec<OdTrip>, capnp::Error>>()?
+        } else if let Some(od_trips_csv) = query["od_trips_csv"].as_str() {
+            read_od_trips_csv(od_trips_csv.as_bytes())?
+        } else if !query["od_trips_csv_file_path"].is_null() {
+            let file_path = query["od_trips_csv_file_path"].as_str().filter(|_| allow_file_path)
+                .ok_or_else(|| capnp::Error::failed(String::from("od_trips_csv_file_path is not allowed, send the od_trips_csv content")))?;
+            let file = File::open(file_path).map_err(|e| capnp::Error::failed(format!("Cannot open {}: {}", file_path, e)))?;
+            read_od_trips_csv(file)?
+        } else {
+            let departure_time_seconds = query["departure_time_seconds"].as_i64().ok_or_else(|| capnp::Error::failed(String::from("Missing departure_time_seconds")))?;
+            od_trips_from_pairs(&places("origins")?, &places("destinations")?, departure_time_seconds)
+        };
+
+        Ok(OdMatrixQuery { scenario_uuid: scenario_uuid.to_string(), od_trips, parameters, format })
+    }
+
+    /// Route the trips on the network of the scenario, see compute_od_matrix
+    pub fn compute<W: Write>(&self, network: &TransitNetwork, writer: &mut W) -> Result<serde_json::Value, capnp::Error> {
+        compute_od_matrix(network, &self.od_trips, &self.parameters, self.format, writer)
+    }
+
+}
+
+/// Compute the OD matrix of a query (see OdMatrixQuery::from_json, the trips
+/// csv file path is allowed) on the network of a cache and write it.
+pub fn od_matrix_query<W: Write>(cache_directory_path: &str, query: &serde_json::Value, writer: &mut W) -> Result<serde_json::Value, capnp::Error> {
+    let od_matrix_query = OdMatrixQuery::from_json(query, true)?;
+    let network = TransitNetwork::load(cache_directory_path, &od_matrix_query.scenario_uuid)?;
+    od_matrix_query.compute(&network, writer)
+}
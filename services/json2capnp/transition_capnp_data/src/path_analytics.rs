@@ -0,0 +1,121 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Segment indices validation and lengths of the path geographies */
+
+use std::collections::HashMap;
+use crate::cache::read_collection_file;
+use crate::serialization::{node_collection, path_collection};
+use crate::utils::haversine_distance_meters;
+
+/// Check the path segments against its nodes and geography: there must be
+/// one segment per pair of consecutive nodes, starting at the first
+/// coordinate, with indices that never decrease and stay in the geography.
+pub fn validate_segments(segments: &[i64], nodes_count: usize, coordinates_count: usize) -> Vec<String> {
+    let mut errors : Vec<String> = Vec::new();
+    let expected_segments_count = if nodes_count > 0 { nodes_count - 1 } else { 0 };
+    if segments.len() != expected_segments_count {
+        errors.push(format!("Path has {} segments for {} nodes, expected {}", segments.len(), nodes_count, expected_segments_count));
+    }
+    if let Some(first_segment) = segments.first() {
+        if *first_segment != 0 {
+            errors.push(format!("First segment starts at coordinate {} instead of 0", first_segment));
+        }
+    }
+    for (i, segment) in segments.iter().enumerate() {
+        if *segment < 0 || *segment as usize >= coordinates_count {
+            errors.push(format!("Segment {} index {} is out of the geography's {} coordinates", i, segment, coordinates_count));
+        }
+        if i > 0 && *segment < segments[i - 1] {
+            errors.push(format!("Segment {} index {} is before the previous segment index {}", i, segment, segments[i - 1]));
+        }
+    }
+    errors
+}
+
+pub fn line_length_meters(coordinates: &[[f64; 2]]) -> f64 {
+    coordinates.windows(2).map(|pair| haversine_distance_meters(pair[0][0], pair[0][1], pair[1][0], pair[1][1])).sum()
+}
+
+/// Analyze a path geography and segments. `node_coordinates` contains the
+/// coordinates of the path's nodes, in order, or None when a node is unknown.
+/// Segment lengths and node distances are only computed if the segments are valid.
+pub fn analyze_path(coordinates: &[[f64; 2]], segments: &[i64], node_coordinates: &[Option<[f64; 2]>]) -> serde_json::Value {
+    let errors = validate_segments(segments, node_coordinates.len(), coordinates.len());
+
+    let mut segment_lengths_meters : Vec<f64> = Vec::new();
+    let mut nodes_distances_to_geometry_meters : Vec<serde_json::Value> = Vec::new();
+    if errors.is_empty() && !coordinates.is_empty() {
+        for (i, segment) in segments.iter().enumerate() {
+            // segments share their boundary coordinate, the last one ends with the geography
+            let end = segments.get(i + 1).map(|next_segment| *next_segment as usize).unwrap_or(coordinates.len() - 1);
+            segment_lengths_meters.push(line_length_meters(&coordinates[*segment as usize..=end]));
+        }
+        for (i, node_coordinate) in node_coordinates.iter().enumerate() {
+            // the last node is snapped to the last coordinate
+            let snapped = segments.get(i).map(|segment| coordinates[*segment as usize]).unwrap_or(coordinates[coordinates.len() - 1]);
+            nodes_distances_to_geometry_meters.push(match node_coordinate {
+                Some(node_coordinate) => json!(haversine_distance_meters(node_coordinate[0], node_coordinate[1], snapped[0], snapped[1])),
+                None => json!(null)
+            });
+        }
+    }
+
+    json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+        "total_length_meters": line_length_meters(coordinates),
+        "segment_lengths_meters": segment_lengths_meters,
+        "nodes_distances_to_geometry_meters": nodes_distances_to_geometry_meters
+    })
+}
+
+pub fn json_coordinates(geometry: &serde_json::Value) -> Vec<[f64; 2]> {
+    geometry["coordinates"].as_array().map(|coordinates| coordinates.iter().map(|coordinate| {
+        [coordinate[0].as_f64().unwrap_or(0.0), coordinate[1].as_f64().unwrap_or(0.0)]
+    }).collect()).unwrap_or_default()
+}
+
+/// Analyze a path feature, in the format of the path collection, using the
+/// node coordinates by node uuid.
+pub fn analyze_path_feature(path_feature: &serde_json::Value, nodes_coordinates_by_uuid: &HashMap<String, [f64; 2]>) -> serde_json::Value {
+    let properties = &path_feature["properties"];
+    let segments : Vec<i64> = properties["segments"].as_array().map(|segments| segments.iter().map(|segment| segment.as_i64().unwrap_or(-1)).collect()).unwrap_or_default();
+    let node_coordinates : Vec<Option<[f64; 2]>> = properties["nodes"].as_array().map(|nodes| nodes.iter().map(|node_uuid| {
+        node_uuid.as_str().and_then(|node_uuid| nodes_coordinates_by_uuid.get(node_uuid).copied())
+    }).collect()).unwrap_or_default();
+
+    let mut analytics = analyze_path(&json_coordinates(&path_feature["geometry"]), &segments, &node_coordinates);
+    analytics["id"] = properties["id"].clone();
+    analytics
+}
+
+/// Analyze and validate all the paths of a cache directory
+pub fn analyze_path_collection(cache_directory_path: &str) -> Result<serde_json::Value, capnp::Error> {
+    let nodes_json = read_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?;
+    let paths_json = read_collection_file(cache_directory_path, "paths", &path_collection::read_collection)?;
+
+    let mut nodes_coordinates_by_uuid : HashMap<String, [f64; 2]> = HashMap::new();
+    for node_feature in nodes_json["nodes"]["features"].as_array().unwrap() {
+        let coordinates = &node_feature["geometry"]["coordinates"];
+        nodes_coordinates_by_uuid.insert(
+            node_feature["properties"]["id"].as_str().unwrap_or("").to_string(),
+            [coordinates[0].as_f64().unwrap_or(0.0), coordinates[1].as_f64().unwrap_or(0.0)]
+        );
+    }
+
+    let paths_analytics : Vec<serde_json::Value> = paths_json["paths"]["features"].as_array().unwrap().iter()
+        .map(|path_feature| analyze_path_feature(path_feature, &nodes_coordinates_by_uuid))
+        .collect();
+    let invalid_paths_count = paths_analytics.iter().filter(|analytics| analytics["valid"] == json!(false)).count();
+
+    Ok(json!({
+        "invalid_paths_count": invalid_paths_count,
+        "paths": paths_analytics
+    }))
+}
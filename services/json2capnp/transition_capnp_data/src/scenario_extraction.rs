@@ -0,0 +1,146 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Extraction of the subset of a cache used by a scenario, as a smaller cache
+ * for trRouting instances and batch runs */
+
+use std::collections::HashSet;
+use crate::cache::{read_collection_file, read_optional_collection_file, write_collection_file};
+use crate::cache_store;
+use crate::scenario_resolution::{line_trips_count, resolve_scenario};
+use crate::serialization::{
+    agency_collection, line, line_collection, node, node_collection, path_collection, scenario_collection, service_collection,
+};
+
+// Collections copied as is, when the source cache has them
+const COPIED_COLLECTIONS: [&str; 1] = ["vehicleTypes"];
+
+fn string_set(json: &serde_json::Value) -> HashSet<String> {
+    json.as_array().map(|uuids| uuids.iter().filter_map(|uuid| uuid.as_str().map(String::from)).collect()).unwrap_or_default()
+}
+
+// Keep only the transferable nodes in the extracted nodes
+pub(crate) fn retain_transferable_nodes(node_json: &mut serde_json::Value, node_uuids: &HashSet<String>) {
+    let transferable_nodes = &mut node_json["node"]["data"]["transferableNodes"];
+    let nodes_ids = match transferable_nodes["nodesIds"].as_array() {
+        Some(nodes_ids) => nodes_ids.clone(),
+        None => return
+    };
+    let kept : Vec<bool> = nodes_ids.iter().map(|node_uuid| node_uuid.as_str().is_some_and(|node_uuid| node_uuids.contains(node_uuid))).collect();
+    for attribute in ["nodesIds", "walkingTravelTimesSeconds", "walkingDistancesMeters"] {
+        if let Some(values) = transferable_nodes[attribute].as_array_mut() {
+            let mut index = 0;
+            values.retain(|_| {
+                index += 1;
+                kept.get(index - 1).copied().unwrap_or(false)
+            });
+        }
+    }
+}
+
+/// Write to the destination cache only what the scenario uses: the scenario,
+/// its services, the lines with trips in the scenario and their agencies,
+/// the line objects with only the schedules and trips of the scenario (see
+/// resolve_scenario), the paths of these trips and their nodes, with the
+/// transferable nodes among them. The integer ids of the nodes and paths are
+/// kept, so the extracted objects have the same ids as in the source cache.
+/// The destination can be any cache location and should be empty.
+pub fn extract_scenario_cache(source_cache_directory_path: &str, scenario_uuid: &str, destination_cache_directory_path: &str) -> Result<serde_json::Value, capnp::Error> {
+
+    let resolved_scenario = resolve_scenario(source_cache_directory_path, scenario_uuid)?;
+    let lines : Vec<&serde_json::Value> = resolved_scenario.lines.iter().filter(|line_object| line_trips_count(line_object) > 0).collect();
+    let line_uuids : HashSet<&str> = lines.iter().filter_map(|line_object| line_object["id"].as_str()).collect();
+    let node_uuids : HashSet<String> = resolved_scenario.paths.iter().flat_map(|path| string_set(&path["properties"]["nodes"])).collect();
+    let service_uuids = string_set(&resolved_scenario.scenario["services"]);
+
+    write_collection_file(destination_cache_directory_path, "scenarios", &json!({ "scenarios": [resolved_scenario.scenario] }), &scenario_collection::write_collection)?;
+
+    let services_json = read_collection_file(source_cache_directory_path, "services", &service_collection::read_collection)?;
+    let services : Vec<&serde_json::Value> = services_json["services"].as_array().unwrap().iter()
+        .filter(|service| service["id"].as_str().is_some_and(|service_uuid| service_uuids.contains(service_uuid)))
+        .collect();
+    write_collection_file(destination_cache_directory_path, "services", &json!({ "services": services }), &service_collection::write_collection)?;
+
+    let lines_json = read_collection_file(source_cache_directory_path, "lines", &line_collection::read_collection)?;
+    let extracted_lines : Vec<&serde_json::Value> = lines_json["lines"].as_array().unwrap().iter()
+        .filter(|line| line["id"].as_str().is_some_and(|line_uuid| line_uuids.contains(line_uuid)))
+        .collect();
+    let agency_uuids : HashSet<&str> = extracted_lines.iter().filter_map(|line| line["agency_id"].as_str()).collect();
+    write_collection_file(destination_cache_directory_path, "lines", &json!({ "lines": extracted_lines }), &line_collection::write_collection)?;
+
+    // the extracted lines keep their layout in the source cache
+    let source_lines_directory_path = format!("{}/lines", source_cache_directory_path);
+    let destination_lines_directory_path = format!("{}/lines", destination_cache_directory_path);
+    for line_object in lines.iter() {
+        let options = line::cached_write_options(line_object["id"].as_str().unwrap_or(""), &source_lines_directory_path)?;
+        line::write_object_with_options(&destination_lines_directory_path, &json!({ "line": line_object }), &options)?;
+    }
+
+    write_collection_file(destination_cache_directory_path, "paths", &json!({
+        "paths": { "type": "FeatureCollection", "features": resolved_scenario.paths }
+    }), &path_collection::write_collection)?;
+
+    let mut agencies_count = 0;
+    if let Some(agencies_json) = read_optional_collection_file(source_cache_directory_path, "agencies", &agency_collection::read_collection)? {
+        let agencies : Vec<&serde_json::Value> = agencies_json["agencies"].as_array().unwrap().iter()
+            .filter(|agency| agency["id"].as_str().is_some_and(|agency_uuid| agency_uuids.contains(agency_uuid)))
+            .collect();
+        agencies_count = agencies.len();
+        write_collection_file(destination_cache_directory_path, "agencies", &json!({ "agencies": agencies }), &agency_collection::write_collection)?;
+    }
+
+    let mut nodes_count = 0;
+    if let Some(nodes_json) = read_optional_collection_file(source_cache_directory_path, "nodes", &node_collection::read_collection)? {
+        let nodes : Vec<&serde_json::Value> = nodes_json["nodes"]["features"].as_array().unwrap().iter()
+            .filter(|node| node["properties"]["id"].as_str().is_some_and(|node_uuid| node_uuids.contains(node_uuid)))
+            .collect();
+        nodes_count = nodes.len();
+        write_collection_file(destination_cache_directory_path, "nodes", &json!({
+            "nodes": { "type": "FeatureCollection", "features": nodes }
+        }), &node_collection::write_collection)?;
+    }
+
+    // node objects are optional, the transferable nodes may not be computed yet
+    let source_nodes_directory_path = format!("{}/nodes", source_cache_directory_path);
+    let destination_nodes_directory_path = format!("{}/nodes", destination_cache_directory_path);
+    let source_nodes_store = cache_store::open(&source_nodes_directory_path)?;
+    let mut node_objects_count = 0;
+    let mut sorted_node_uuids : Vec<&String> = node_uuids.iter().collect();
+    sorted_node_uuids.sort();
+    for node_uuid in sorted_node_uuids {
+        if source_nodes_store.get(&format!("node_{}.capnpbin", node_uuid))?.is_none() {
+            continue;
+        }
+        let mut node_json = node::read_object(node_uuid, &source_nodes_directory_path)?;
+        retain_transferable_nodes(&mut node_json, &node_uuids);
+        node::write_object(&destination_nodes_directory_path, &node_json)?;
+        node_objects_count += 1;
+    }
+
+    let source_store = cache_store::open(source_cache_directory_path)?;
+    let destination_store = cache_store::open(destination_cache_directory_path)?;
+    for cache_file_name in COPIED_COLLECTIONS.iter() {
+        let key = format!("{}.capnpbin", cache_file_name);
+        if let Some(content) = source_store.get(&key)? {
+            destination_store.put(&key, &content)?;
+        }
+    }
+
+    Ok(json!({
+        "scenario_id": scenario_uuid,
+        "services_count": services.len(),
+        "lines_count": lines.len(),
+        "agencies_count": agencies_count,
+        "paths_count": resolved_scenario.paths.len(),
+        "nodes_count": nodes_count,
+        "node_objects_count": node_objects_count,
+        "trips_count": lines.iter().map(|line_object| line_trips_count(line_object)).sum::<usize>(),
+        "summary": resolved_scenario.summary
+    }))
+
+}
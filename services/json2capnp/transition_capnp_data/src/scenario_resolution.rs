@@ -0,0 +1,256 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Apply the services and the only/except rules of a scenario to the lines of a cache */
+
+use std::collections::{HashMap, HashSet};
+use crate::cache::read_collection_file;
+use crate::serialization::{line, line_collection, path_collection, scenario_collection, service_collection};
+
+// Rules removing whole lines, in the order they are applied. A line removed
+// by a rule is not counted again by the following ones.
+const LINE_RULES: [(&str, &str, bool); 6] = [
+    ("only_agencies", "agency_id", true),
+    ("except_agencies", "agency_id", false),
+    ("only_lines", "id", true),
+    ("except_lines", "id", false),
+    ("only_modes", "mode", true),
+    ("except_modes", "mode", false),
+];
+
+pub struct ResolvedScenario {
+    pub scenario: serde_json::Value,
+    // line objects, as returned by line::read_object, keeping only the
+    // schedules and trips of the scenario
+    pub lines: Vec<serde_json::Value>,
+    // path features used by the trips of the scenario
+    pub paths: Vec<serde_json::Value>,
+    pub summary: serde_json::Value,
+}
+
+fn uuid_set(json: &serde_json::Value) -> HashSet<&str> {
+    json.as_array().map(|uuids| uuids.iter().filter_map(|uuid| uuid.as_str()).collect()).unwrap_or_default()
+}
+
+pub(crate) fn line_trips_count(line_json: &serde_json::Value) -> usize {
+    line_json["scheduleByServiceId"].as_object().map(|schedules| schedules.values().map(schedule_trips_count).sum()).unwrap_or(0)
+}
+
+fn schedule_trips_count(schedule_json: &serde_json::Value) -> usize {
+    schedule_json["periods"].as_array().map(|periods| periods.iter().map(|period| {
+        period["trips"].as_array().map(|trips| trips.len()).unwrap_or(0)
+    }).sum()).unwrap_or(0)
+}
+
+/// Load the scenario, services, lines, paths and line objects of a cache and
+/// keep only the lines, schedules and trips effective in the scenario.
+/// Line objects are read from the `lines` subdirectory of the cache.
+///
+/// The line rules (agencies, lines and modes) are applied first, then the
+/// scenario services select the schedules. Finally, trips serving a node
+/// excluded by `except_nodes`, or a node not in `only_nodes` when it is not
+/// empty, are removed, as are trips on paths missing from the cache. The
+/// lines with no trips left are removed too, counted in the summary of the
+/// services, or of the rule which removed their last trip.
+pub fn resolve_scenario(cache_directory_path: &str, scenario_uuid: &str) -> Result<ResolvedScenario, capnp::Error> {
+
+    let scenarios_json = read_collection_file(cache_directory_path, "scenarios", &scenario_collection::read_collection)?;
+    let scenario = scenarios_json["scenarios"].as_array().unwrap().iter()
+        .find(|scenario| scenario["id"] == json!(scenario_uuid))
+        .cloned()
+        .ok_or_else(|| capnp::Error::failed(format!("Scenario {} not found", scenario_uuid)))?;
+
+    let services_json = read_collection_file(cache_directory_path, "services", &service_collection::read_collection)?;
+    let lines_json = read_collection_file(cache_directory_path, "lines", &line_collection::read_collection)?;
+    let paths_json = read_collection_file(cache_directory_path, "paths", &path_collection::read_collection)?;
+    let lines_directory_path = format!("{}/lines", cache_directory_path);
+
+    let existing_services : HashSet<&str> = services_json["services"].as_array().unwrap().iter().filter_map(|service| service["id"].as_str()).collect();
+    let scenario_services = uuid_set(&scenario["services"]);
+    let mut unknown_services : Vec<&str> = scenario_services.iter().filter(|service_uuid| !existing_services.contains(*service_uuid)).copied().collect();
+    unknown_services.sort();
+
+    let paths_by_uuid : HashMap<&str, &serde_json::Value> = paths_json["paths"]["features"].as_array().unwrap().iter()
+        .filter_map(|path| path["properties"]["id"].as_str().map(|path_uuid| (path_uuid, path)))
+        .collect();
+
+    let mut summary = json!({});
+    let mut removed_lines_by_rule : HashMap<&str, (usize, usize)> = HashMap::new();
+    let mut missing_line_objects : Vec<String> = Vec::new();
+    let mut lines : Vec<serde_json::Value> = Vec::new();
+
+    // line rules
+    for line_json in lines_json["lines"].as_array().unwrap() {
+        let removing_rule = LINE_RULES.iter().find(|(rule, attribute, is_only)| {
+            let rule_uuids = uuid_set(&scenario[*rule]);
+            if *is_only {
+                !rule_uuids.is_empty() && !rule_uuids.contains(line_json[*attribute].as_str().unwrap_or(""))
+            } else {
+                rule_uuids.contains(line_json[*attribute].as_str().unwrap_or(""))
+            }
+        });
+
+        let line_uuid = line_json["id"].as_str().unwrap_or("").to_string();
+        let line_object = line::read_object(&line_uuid, &lines_directory_path).map(|line_object| line_object["line"].clone());
+
+        match (removing_rule, line_object) {
+            (Some((rule, _, _)), line_object) => {
+                let removed = removed_lines_by_rule.entry(rule).or_insert((0, 0));
+                removed.0 += 1;
+                removed.1 += line_object.map(|line_object| line_trips_count(&line_object)).unwrap_or(0);
+            },
+            (None, Ok(line_object)) => lines.push(line_object),
+            (None, Err(_)) => missing_line_objects.push(line_uuid)
+        }
+    }
+    for (rule, _, _) in LINE_RULES.iter() {
+        let (removed_lines, removed_trips) = removed_lines_by_rule.get(rule).copied().unwrap_or((0, 0));
+        summary[*rule] = json!({ "removed_lines": removed_lines, "removed_trips": removed_trips });
+    }
+
+    // services
+    let mut removed_schedules = 0;
+    let mut removed_trips = 0;
+    for line_object in lines.iter_mut() {
+        if let Some(schedules) = line_object["scheduleByServiceId"].as_object_mut() {
+            let removed_services : Vec<String> = schedules.keys().filter(|service_uuid| !scenario_services.contains(service_uuid.as_str())).cloned().collect();
+            for service_uuid in removed_services {
+                let schedule = schedules.remove(&service_uuid).unwrap();
+                removed_schedules += 1;
+                removed_trips += schedule_trips_count(&schedule);
+            }
+        }
+    }
+    let lines_count = lines.len();
+    lines.retain(|line_object| line_trips_count(line_object) > 0);
+    summary["services"] = json!({
+        "removed_lines": lines_count - lines.len(),
+        "removed_schedules": removed_schedules,
+        "removed_trips": removed_trips,
+        "unknown_services": unknown_services
+    });
+
+    // nodes and paths
+    let only_nodes = uuid_set(&scenario["only_nodes"]);
+    let except_nodes = uuid_set(&scenario["except_nodes"]);
+    let mut removed_trips_by_rule : HashMap<&str, usize> = HashMap::new();
+    let mut removed_lines_by_rule : HashMap<&str, usize> = HashMap::new();
+    let mut used_paths : Vec<&str> = Vec::new();
+    let mut effective_lines : Vec<serde_json::Value> = Vec::with_capacity(lines.len());
+    for mut line_object in lines {
+        // rule which removed the last trip of the line
+        let mut last_removing_rule : Option<&str> = None;
+        let schedules = match line_object["scheduleByServiceId"].as_object_mut() {
+            Some(schedules) => schedules,
+            None => continue
+        };
+        for schedule in schedules.values_mut() {
+            let periods = match schedule["periods"].as_array_mut() {
+                Some(periods) => periods,
+                None => continue
+            };
+            for period in periods.iter_mut() {
+                let trips = match period["trips"].as_array_mut() {
+                    Some(trips) => trips,
+                    None => continue
+                };
+                trips.retain(|trip| {
+                    let path = match trip["path_id"].as_str().and_then(|path_uuid| paths_by_uuid.get(path_uuid)) {
+                        Some(path) => path,
+                        None => {
+                            *removed_trips_by_rule.entry("missing_paths").or_insert(0) += 1;
+                            last_removing_rule = Some("missing_paths");
+                            return false;
+                        }
+                    };
+                    let path_nodes = uuid_set(&path["properties"]["nodes"]);
+                    let removing_rule = if !except_nodes.is_empty() && path_nodes.iter().any(|node_uuid| except_nodes.contains(node_uuid)) {
+                        Some("except_nodes")
+                    } else if !only_nodes.is_empty() && path_nodes.iter().any(|node_uuid| !only_nodes.contains(node_uuid)) {
+                        Some("only_nodes")
+                    } else {
+                        None
+                    };
+                    match removing_rule {
+                        Some(rule) => {
+                            *removed_trips_by_rule.entry(rule).or_insert(0) += 1;
+                            last_removing_rule = Some(rule);
+                            false
+                        },
+                        None => {
+                            let path_uuid = path["properties"]["id"].as_str().unwrap();
+                            if !used_paths.contains(&path_uuid) {
+                                used_paths.push(path_uuid);
+                            }
+                            true
+                        }
+                    }
+                });
+            }
+        }
+        match last_removing_rule {
+            Some(rule) if line_trips_count(&line_object) == 0 => *removed_lines_by_rule.entry(rule).or_insert(0) += 1,
+            _ => effective_lines.push(line_object)
+        }
+    }
+    let lines = effective_lines;
+    for rule in ["only_nodes", "except_nodes", "missing_paths"] {
+        summary[rule] = json!({
+            "removed_lines": removed_lines_by_rule.get(rule).copied().unwrap_or(0),
+            "removed_trips": removed_trips_by_rule.get(rule).copied().unwrap_or(0)
+        });
+    }
+    summary["missing_line_objects"] = json!(missing_line_objects);
+
+    let paths = used_paths.iter().map(|path_uuid| (*paths_by_uuid[path_uuid]).clone()).collect();
+
+    Ok(ResolvedScenario { scenario, lines, paths, summary })
+
+}
+
+impl ResolvedScenario {
+
+    /// Effective lines, paths, schedules and trips of the scenario, by uuid
+    pub fn to_json(&self) -> serde_json::Value {
+        let mut schedules : Vec<serde_json::Value> = Vec::new();
+        let mut trips : Vec<serde_json::Value> = Vec::new();
+        for line_object in self.lines.iter() {
+            for schedule in line_object["scheduleByServiceId"].as_object().map(|schedules| schedules.values().collect()).unwrap_or_else(Vec::new) {
+                let schedule_trips_count = schedule_trips_count(schedule);
+                schedules.push(json!({
+                    "id": schedule["id"],
+                    "line_id": line_object["id"],
+                    "service_id": schedule["service_id"],
+                    "trips_count": schedule_trips_count
+                }));
+                for period in schedule["periods"].as_array().unwrap_or(&Vec::new()) {
+                    for trip in period["trips"].as_array().unwrap_or(&Vec::new()) {
+                        trips.push(json!({
+                            "id": trip["id"],
+                            "line_id": line_object["id"],
+                            "path_id": trip["path_id"],
+                            "service_id": schedule["service_id"],
+                            "schedule_id": schedule["id"],
+                            "period_shortname": period["period_shortname"],
+                            "departure_time_seconds": trip["departure_time_seconds"],
+                            "arrival_time_seconds": trip["arrival_time_seconds"]
+                        }));
+                    }
+                }
+            }
+        }
+        json!({
+            "scenario_id": self.scenario["id"],
+            "lines_ids": self.lines.iter().map(|line_object| line_object["id"].clone()).collect::<Vec<serde_json::Value>>(),
+            "paths_ids": self.paths.iter().map(|path| path["properties"]["id"].clone()).collect::<Vec<serde_json::Value>>(),
+            "schedules": schedules,
+            "trips": trips,
+            "summary": self.summary
+        })
+    }
+}
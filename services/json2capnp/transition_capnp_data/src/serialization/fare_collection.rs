@@ -0,0 +1,164 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Fare products, areas, leg rules and transfer rules, roughly following GTFS-Fares v2 */
+
+use crate::fareCollection_capnp::fare_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    empty_str_to_json_null,
+    json_value_or_null_to_i64_or_minus_one,
+    minus_one_i64_to_null
+};
+
+fn json_array<'a>(json: &'a serde_json::Value, attribute: &str) -> &'a [serde_json::Value] {
+    json[attribute].as_array().map(|array| array.as_slice()).unwrap_or_default()
+}
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    let fares = &json["fares"];
+    if !fares.is_object() {
+        return Err(capnp::Error::failed(String::from("Fares are missing or not an object")));
+    }
+    let mut collection_capnp = message.init_root::<collection::Builder>();
+
+    let fare_products = json_array(fares, "fare_products");
+    let mut capnp = collection_capnp.reborrow().init_fare_products(fare_products.len() as u32);
+    for (i, json_data) in fare_products.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        let amount = json_data["amount"].as_f64().ok_or_else(|| capnp::Error::failed(format!("Fare product {} has no amount", json_data["id"])))?;
+        capnp_data.set_uuid(required_string(json_data.get("id")));
+        capnp_data.set_name(optional_string(json_data.get("name")));
+        capnp_data.set_amount_cents((amount * 100.0).round() as i32);
+        capnp_data.set_currency(optional_string(json_data.get("currency")));
+        capnp_data.set_rider_category(optional_string(json_data.get("rider_category")));
+        capnp_data.set_data(json_data.get("data").unwrap_or(&json!({})).to_string().as_str());
+    }
+
+    let fare_areas = json_array(fares, "fare_areas");
+    let mut capnp = collection_capnp.reborrow().init_fare_areas(fare_areas.len() as u32);
+    for (i, json_data) in fare_areas.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        capnp_data.set_uuid(required_string(json_data.get("id")));
+        capnp_data.set_name(optional_string(json_data.get("name")));
+        let nodes_uuids : Vec<&str> = json_array(json_data, "nodes").iter().filter_map(|node_uuid| node_uuid.as_str()).collect();
+        let mut capnp_nodes_uuids = capnp_data.init_nodes_uuids(nodes_uuids.len() as u32);
+        for (j, node_uuid) in nodes_uuids.iter().enumerate() {
+            capnp_nodes_uuids.set(j as u32, *node_uuid);
+        }
+    }
+
+    let fare_leg_rules = json_array(fares, "fare_leg_rules");
+    let mut capnp = collection_capnp.reborrow().init_fare_leg_rules(fare_leg_rules.len() as u32);
+    for (i, json_data) in fare_leg_rules.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        capnp_data.set_uuid(optional_string(json_data.get("id")));
+        capnp_data.set_leg_group_id(optional_string(json_data.get("leg_group_id")));
+        capnp_data.set_agency_uuid(optional_string(json_data.get("agency_id")));
+        capnp_data.set_line_uuid(optional_string(json_data.get("line_id")));
+        capnp_data.set_from_area_uuid(optional_string(json_data.get("from_area_id")));
+        capnp_data.set_to_area_uuid(optional_string(json_data.get("to_area_id")));
+        capnp_data.set_fare_product_uuid(required_string(json_data.get("fare_product_id")));
+        capnp_data.set_rule_priority(json_data["rule_priority"].as_i64().unwrap_or(0) as i16);
+    }
+
+    let fare_transfer_rules = json_array(fares, "fare_transfer_rules");
+    let mut capnp = collection_capnp.init_fare_transfer_rules(fare_transfer_rules.len() as u32);
+    for (i, json_data) in fare_transfer_rules.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        capnp_data.set_from_leg_group_id(optional_string(json_data.get("from_leg_group_id")));
+        capnp_data.set_to_leg_group_id(optional_string(json_data.get("to_leg_group_id")));
+        capnp_data.set_transfer_count(json_value_or_null_to_i64_or_minus_one(&json_data["transfer_count"]) as i16);
+        capnp_data.set_duration_limit_seconds(json_value_or_null_to_i64_or_minus_one(&json_data["duration_limit_seconds"]) as i32);
+        capnp_data.set_duration_limit_type(json_data["duration_limit_type"].as_i64().unwrap_or(0) as i8);
+        capnp_data.set_fare_transfer_type(json_data["fare_transfer_type"].as_i64().unwrap_or(0) as i8);
+        capnp_data.set_fare_product_uuid(optional_string(json_data.get("fare_product_id")));
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut fare_products : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_fare_products()?.len() as usize);
+    for capnp_object in capnp_collection.get_fare_products()?.iter() {
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        fare_products.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "name": empty_str_to_json_null(capnp_object.get_name()?.to_str()?),
+            "amount": capnp_object.get_amount_cents() as f64 / 100.0,
+            "currency": empty_str_to_json_null(capnp_object.get_currency()?.to_str()?),
+            "rider_category": empty_str_to_json_null(capnp_object.get_rider_category()?.to_str()?),
+            "data": data_attributes
+        }));
+    }
+
+    let mut fare_areas : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_fare_areas()?.len() as usize);
+    for capnp_object in capnp_collection.get_fare_areas()?.iter() {
+        let mut nodes_uuids : Vec<serde_json::Value> = Vec::with_capacity(capnp_object.get_nodes_uuids()?.len() as usize);
+        for node_uuid in capnp_object.get_nodes_uuids()?.iter() {
+            nodes_uuids.push(json!(node_uuid?.to_str()?));
+        }
+        fare_areas.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "name": empty_str_to_json_null(capnp_object.get_name()?.to_str()?),
+            "nodes": nodes_uuids
+        }));
+    }
+
+    let mut fare_leg_rules : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_fare_leg_rules()?.len() as usize);
+    for capnp_object in capnp_collection.get_fare_leg_rules()?.iter() {
+        fare_leg_rules.push(json!({
+            "id": empty_str_to_json_null(capnp_object.get_uuid()?.to_str()?),
+            "leg_group_id": empty_str_to_json_null(capnp_object.get_leg_group_id()?.to_str()?),
+            "agency_id": empty_str_to_json_null(capnp_object.get_agency_uuid()?.to_str()?),
+            "line_id": empty_str_to_json_null(capnp_object.get_line_uuid()?.to_str()?),
+            "from_area_id": empty_str_to_json_null(capnp_object.get_from_area_uuid()?.to_str()?),
+            "to_area_id": empty_str_to_json_null(capnp_object.get_to_area_uuid()?.to_str()?),
+            "fare_product_id": capnp_object.get_fare_product_uuid()?.to_str()?,
+            "rule_priority": capnp_object.get_rule_priority()
+        }));
+    }
+
+    let mut fare_transfer_rules : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_fare_transfer_rules()?.len() as usize);
+    for capnp_object in capnp_collection.get_fare_transfer_rules()?.iter() {
+        fare_transfer_rules.push(json!({
+            "from_leg_group_id": empty_str_to_json_null(capnp_object.get_from_leg_group_id()?.to_str()?),
+            "to_leg_group_id": empty_str_to_json_null(capnp_object.get_to_leg_group_id()?.to_str()?),
+            "transfer_count": minus_one_i64_to_null(capnp_object.get_transfer_count() as i64),
+            "duration_limit_seconds": minus_one_i64_to_null(capnp_object.get_duration_limit_seconds() as i64),
+            "duration_limit_type": capnp_object.get_duration_limit_type(),
+            "fare_transfer_type": capnp_object.get_fare_transfer_type(),
+            "fare_product_id": empty_str_to_json_null(capnp_object.get_fare_product_uuid()?.to_str()?)
+        }));
+    }
+
+    Ok(json!({
+        "fares": {
+            "fare_products": fare_products,
+            "fare_areas": fare_areas,
+            "fare_leg_rules": fare_leg_rules,
+            "fare_transfer_rules": fare_transfer_rules
+        }
+    }))
+
+}
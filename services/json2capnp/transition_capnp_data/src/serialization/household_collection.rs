@@ -0,0 +1,96 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Households of the demand data, with their home location and expansion factor */
+
+use crate::householdCollection_capnp::household_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    empty_str_to_json_null,
+    json_value_or_null_to_i64_or_minus_one,
+    minus_one_i64_to_null,
+    json_value_or_null_to_thousandths_or_minus_one,
+    minus_one_thousandths_to_null,
+    json_point_to_i32_coordinates,
+    i32_coordinates_to_json_point
+};
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    let json_objects = json["households"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Households are missing or not an array")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_households(json_objects.len() as u32);
+
+    for (i, json_data) in json_objects.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        // households without a home location are stored with -1 coordinates
+        let (home_longitude, home_latitude) = json_point_to_i32_coordinates(&json_data["home_geography"]).unwrap_or((-1, -1));
+        capnp_data.set_uuid(required_string(json_data.get("id")));
+        capnp_data.set_id(json_data["integer_id"].as_u64().unwrap_or(0) as u32);
+        capnp_data.set_internal_id(optional_string(json_data.get("internal_id")));
+        capnp_data.set_data_source_uuid(optional_string(json_data.get("data_source_id")));
+        capnp_data.set_size(json_value_or_null_to_i64_or_minus_one(&json_data["size"]) as i16);
+        capnp_data.set_car_number(json_value_or_null_to_i64_or_minus_one(&json_data["car_number"]) as i16);
+        capnp_data.set_income_level(optional_string(json_data.get("income_level")));
+        capnp_data.set_category(optional_string(json_data.get("category")));
+        capnp_data.set_home_latitude(home_latitude);
+        capnp_data.set_home_longitude(home_longitude);
+        capnp_data.set_expansion_factor(json_value_or_null_to_thousandths_or_minus_one(&json_data["expansion_factor"]));
+        capnp_data.set_data(json_data.get("data").unwrap_or(&json!({})).to_string().as_str());
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_households()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_households()?.iter() {
+
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        let home_geography = if capnp_object.get_home_longitude() == -1 && capnp_object.get_home_latitude() == -1 {
+            json!(null)
+        } else {
+            i32_coordinates_to_json_point(capnp_object.get_home_longitude(), capnp_object.get_home_latitude())
+        };
+        collection_json_vec.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "integer_id": capnp_object.get_id(),
+            "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+            "data_source_id": empty_str_to_json_null(capnp_object.get_data_source_uuid()?.to_str()?),
+            "size": minus_one_i64_to_null(capnp_object.get_size() as i64),
+            "car_number": minus_one_i64_to_null(capnp_object.get_car_number() as i64),
+            "income_level": empty_str_to_json_null(capnp_object.get_income_level()?.to_str()?),
+            "category": empty_str_to_json_null(capnp_object.get_category()?.to_str()?),
+            "home_geography": home_geography,
+            "expansion_factor": minus_one_thousandths_to_null(capnp_object.get_expansion_factor()),
+            "data": data_attributes
+        }));
+
+    }
+
+    Ok(json!({
+        "households": serde_json::Value::Array(collection_json_vec)
+    }))
+
+}
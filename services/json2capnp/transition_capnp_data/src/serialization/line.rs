@@ -6,12 +6,11 @@
  *
  */
 
-use crate::line_capnp::{line};
-use std::fs::File;
-use std::path::Path;
+use crate::line_capnp::{line, period, service_trips, trip};
+use crate::serialization::trip_pattern::{TripPattern, TripPatterns};
+use crate::cache_store::{self, CacheChanges, CacheStore};
 use capnp::serialize_packed;
 use serde_json;
-use std::io::BufReader;
 use crate::utils::{ 
     required_string, 
     optional_string_json_null_to_empty_str as optional_string, 
@@ -26,10 +25,53 @@ use crate::utils::{
     seconds_since_midnight_to_time_str
 };
 
+fn object_file_name(object_uuid: &str) -> String {
+    format!("line_{}.capnpbin", object_uuid)
+}
+
+fn service_trips_file_name(object_uuid: &str, service_uuid: &str) -> String {
+    format!("line_{}_trips_{}.capnpbin", object_uuid, service_uuid)
+}
+
+/// Layout of the line object files. trRouting only reads the trips with their
+/// node times in `line_<uuid>.capnpbin`: a line written by service has no
+/// trips for trRouting and the trips of a line written with trip patterns
+/// have no node times, so these layouts are only for the caches read by this
+/// crate, like the caches of the backend and of the simulations.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LineWriteOptions {
+    // write the trips of each schedule in a separate file, see write_object_by_service
+    pub trips_by_service: bool,
+    // write the node times and flags shared by many trips once in trip patterns,
+    // the trips keeping only their departure time and pattern id
+    pub trip_patterns: bool,
+}
+
 pub fn write_object(
     cache_directory_path: &str,
     json: &serde_json::Value,
 ) -> ::std::result::Result<(), capnp::Error> {
+    write_object_with_options(cache_directory_path, json, &LineWriteOptions::default())
+}
+
+/// Write a line object with the trips of each schedule in a separate
+/// `line_<uuid>_trips_<service uuid>.capnpbin` file, so they can be read by
+/// service or by period. read_object still returns the complete line, but
+/// trRouting does not read these files, see LineWriteOptions.
+pub fn write_object_by_service(
+    cache_directory_path: &str,
+    json: &serde_json::Value,
+) -> ::std::result::Result<(), capnp::Error> {
+    write_object_with_options(cache_directory_path, json, &LineWriteOptions { trips_by_service: true, ..Default::default() })
+}
+
+/// Write a line object with the layout of the options. The line object read
+/// back is the same whatever the options.
+pub fn write_object_with_options(
+    cache_directory_path: &str,
+    json: &serde_json::Value,
+    options: &LineWriteOptions,
+) -> ::std::result::Result<(), capnp::Error> {
 
     let mut message = ::capnp::message::Builder::new_default();
 
@@ -50,15 +92,9 @@ pub fn write_object(
         Err(MyError::new("line uuid is invalid or empty"))
     }*/
 
-    let object_file_path_name = format!("{}/line_{}.capnpbin", cache_directory_path, object_uuid);
-    let path = Path::new(&object_file_path_name);
-
-    let file_check = File::create(&path);
-
-    let file = file_check.unwrap();
+    let store = cache_store::open(cache_directory_path)?;
+    let mut changes = CacheChanges::default();
 
-    /* TODO: deal with error when creating object file using uuid */
-    
     let mut capnp_data = message.init_root::<line::Builder>();
 
     capnp_data.set_uuid(&required_string(json_object.get("id")));
@@ -75,11 +111,13 @@ pub fn write_object(
     capnp_data.set_is_enabled(json_boolean_to_i8(json_object.get("is_enabled").unwrap_or(&json!(null))));
     capnp_data.set_is_autonomous(json_boolean_to_i8(json_object.get("is_autonomous").unwrap_or(&json!(null))));
     capnp_data.set_allow_same_line_transfers(json_boolean_to_i8(json_object.get("allow_same_line_transfers").unwrap_or(&json!(null))));
+    capnp_data.set_trips_by_service(if options.trips_by_service { 1 } else { 0 });
 
     let schedules_json : std::collections::HashMap<String, serde_json::Value> = serde_json::from_str(&json_object.get("scheduleByServiceId").unwrap_or(&json!({})).to_string().as_str()).unwrap();
     let count_schedules : usize = schedules_json.keys().len();
 
     let mut capnp_schedules = capnp_data.reborrow().init_schedules(count_schedules as u32);
+    let mut trip_patterns = TripPatterns::default();
 
     let mut i : u32 = 0;
     for (_ /* service_id */, json_data) in &schedules_json
@@ -131,131 +169,348 @@ pub fn write_object(
             capnp_period_data.set_number_of_units(json_value_or_null_to_i64_or_minus_one(&json_data.get("number_of_units").unwrap_or(&json!(null))) as i16);
             capnp_period_data.set_is_frozen(json_boolean_to_i8(json_data.get("is_frozen").unwrap_or(&json!(null))));
             capnp_period_data.set_uuid(&optional_string(json_data.get("id"))); // period.id is required in the db. However, in the genetic algorithm, we don't need it.
+            capnp_period_data.set_vehicle_type_uuid(optional_string(json_data.get("vehicle_type_id")));
+
+            if !options.trips_by_service
+            {
+                let count_trips: usize = json_data.get("trips").unwrap_or(&json!([])).as_array().unwrap().len();
+                write_trips(&json_data["trips"], capnp_period_data.init_trips(count_trips as u32), options.trip_patterns.then_some(&mut trip_patterns))?;
+            }
+
+        }
+        i += 1;
+
+    }
+
+    if !trip_patterns.is_empty()
+    {
+        trip_patterns.write(capnp_data.init_trip_patterns(trip_patterns.len() as u32))?;
+    }
+
+    let mut content : Vec<u8> = Vec::new();
+    serialize_packed::write_message(&mut content, &message)?;
+    changes.put(&object_file_name(object_uuid), content);
+
+    if options.trips_by_service
+    {
+        for json_data in schedules_json.values()
+        {
+            let service_uuid = required_string(json_data.get("service_id"));
+            changes.put(&service_trips_file_name(object_uuid, service_uuid), write_service_trips(object_uuid, json_data, options)?);
+        }
+    }
 
-            let count_trips: usize = json_data.get("trips").unwrap_or(&json!([])).as_array().unwrap().len();
-            let mut capnp_trips    = capnp_period_data.init_trips(count_trips as u32);
-            let trips_json         = &json_data["trips"];
+    // trips of the previous version of the line must not be read with the new one
+    for file_name in service_trips_file_names(store.as_ref(), object_uuid)?
+    {
+        changes.delete(&file_name);
+    }
+
+    // the line and its trips files are replaced together
+    store.commit(changes)
+
+}
+
+/// Layout of the line in the cache, the default layout if there is no such
+/// line, to rewrite the line with the files of the same readers
+pub fn cached_write_options(
+    object_uuid: &str,
+    cache_directory_path: &str,
+) -> ::std::result::Result<LineWriteOptions, capnp::Error> {
 
-            for k in 0..count_trips
+    let store = cache_store::open(cache_directory_path)?;
+    if store.get(&object_file_name(object_uuid))?.is_none()
+    {
+        return Ok(LineWriteOptions::default());
+    }
+    let message_reader = read_message(store.as_ref(), &object_file_name(object_uuid))?;
+    let capnp_object = message_reader.get_root::<line::Reader>()?;
+    let trips_by_service = capnp_object.get_trips_by_service() == 1;
+    let mut trip_patterns = capnp_object.has_trip_patterns() && !capnp_object.get_trip_patterns()?.is_empty();
+    // the trip patterns of a line written by service are in its trips files
+    if trips_by_service && !trip_patterns
+    {
+        for file_name in service_trips_file_names(store.as_ref(), object_uuid)?
+        {
+            let service_message_reader = read_message(store.as_ref(), &file_name)?;
+            let capnp_service_trips = service_message_reader.get_root::<service_trips::Reader>()?;
+            if capnp_service_trips.has_trip_patterns() && !capnp_service_trips.get_trip_patterns()?.is_empty()
             {
-                let json_data           = &trips_json[k];
-                let mut capnp_trip_data = capnp_trips.reborrow().get(k as u32);
-
-                capnp_trip_data.set_uuid(&required_string(json_data.get("id")));
-                capnp_trip_data.set_path_uuid(&required_string(json_data.get("path_id")));
-                capnp_trip_data.set_departure_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data.get("departure_time_seconds").unwrap_or(&json!(null))) as i32);
-                capnp_trip_data.set_arrival_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data.get("arrival_time_seconds").unwrap_or(&json!(null))) as i32);
-                capnp_trip_data.set_block_uuid(&optional_string(json_data.get("block_id")));
-                capnp_trip_data.set_total_capacity(json_value_or_null_to_i64_or_minus_one(&json_data.get("total_capacity").unwrap_or(&json!(null))) as i16);
-                capnp_trip_data.set_seated_capacity(json_value_or_null_to_i64_or_minus_one(&json_data.get("seated_capacity").unwrap_or(&json!(null))) as i16);
-                capnp_trip_data.set_is_frozen(json_boolean_to_i8(json_data.get("is_frozen").unwrap_or(&json!(null))));
-
-                let nodes_arrival_time_seconds = json_data.get("node_arrival_times_seconds").unwrap().as_array();
-                if nodes_arrival_time_seconds != None {
-                    let nodes_arrival_time_seconds = nodes_arrival_time_seconds.unwrap();
-                    let nodes_count: usize = nodes_arrival_time_seconds.len();
-
-                    capnp_trip_data.reborrow().init_node_arrival_times_seconds(nodes_count as u32);
-                    for l in 0..nodes_count
-                    {
-                        capnp_trip_data.reborrow().get_node_arrival_times_seconds().unwrap().set(l as u32, json_value_or_null_to_i64_or_minus_one(&nodes_arrival_time_seconds[l]) as i32);
-                    }
-                }
-                else
-                {
-                    capnp_trip_data.reborrow().init_node_arrival_times_seconds(0);
-                }
+                trip_patterns = true;
+                break;
+            }
+        }
+    }
+    Ok(LineWriteOptions { trips_by_service, trip_patterns })
 
-                let nodes_departure_time_seconds = json_data.get("node_departure_times_seconds").unwrap().as_array();
-                if nodes_departure_time_seconds != None {
-                    let nodes_departure_time_seconds = nodes_departure_time_seconds.unwrap();
-                    let nodes_count: usize = nodes_departure_time_seconds.len();
+}
 
-                    capnp_trip_data.reborrow().init_node_departure_times_seconds(nodes_count as u32);
-                    for l in 0..nodes_count
-                    {
-                        capnp_trip_data.reborrow().get_node_departure_times_seconds().unwrap().set(l as u32, json_value_or_null_to_i64_or_minus_one(&nodes_departure_time_seconds[l]) as i32);
-                    }
-                }
-                else
-                {
-                    capnp_trip_data.reborrow().init_node_departure_times_seconds(0);
-                }
+/// Write a line object with the layout of the line it replaces in the cache,
+/// see cached_write_options
+pub fn rewrite_object(
+    cache_directory_path: &str,
+    json: &serde_json::Value,
+) -> ::std::result::Result<(), capnp::Error> {
+    let options = cached_write_options(json["line"]["id"].as_str().unwrap_or(""), cache_directory_path)?;
+    write_object_with_options(cache_directory_path, json, &options)
+}
 
-                let nodes_can_board = json_data.get("nodes_can_board").unwrap().as_array();
-                if nodes_can_board != None {
-                    let nodes_can_board = nodes_can_board.unwrap();
-                    let nodes_count: usize = nodes_can_board.len();
+fn write_service_trips(
+    object_uuid: &str,
+    schedule_json: &serde_json::Value,
+    options: &LineWriteOptions,
+) -> ::std::result::Result<Vec<u8>, capnp::Error> {
 
-                    capnp_trip_data.reborrow().init_nodes_can_board(nodes_count as u32);
-                    for l in 0..nodes_count
-                    {
-                        capnp_trip_data.reborrow().get_nodes_can_board().unwrap().set(l as u32, crate::utils::json_boolean_to_i8(&nodes_can_board[l]));
-                    }
-                }
-                else
-                {
-                    capnp_trip_data.reborrow().init_nodes_can_board(0);
-                }
+    let mut message = ::capnp::message::Builder::new_default();
+    let service_uuid = required_string(schedule_json.get("service_id"));
 
-                let nodes_can_unboard = json_data.get("nodes_can_unboard").unwrap().as_array();
-                if nodes_can_unboard != None {
-                    let nodes_can_unboard = nodes_can_unboard.unwrap();
-                    let nodes_count: usize = nodes_can_unboard.len();
+    let mut capnp_data = message.init_root::<service_trips::Builder>();
+    capnp_data.set_line_uuid(object_uuid);
+    capnp_data.set_service_uuid(service_uuid);
 
-                    capnp_trip_data.reborrow().init_nodes_can_unboard(nodes_count as u32);
-                    for l in 0..nodes_count
-                    {
-                        capnp_trip_data.reborrow().get_nodes_can_unboard().unwrap().set(l as u32, crate::utils::json_boolean_to_i8(&nodes_can_unboard[l]));
-                    }
-                }
-                else
-                {
-                    capnp_trip_data.reborrow().init_nodes_can_unboard(0);
-                }
+    let periods_json = schedule_json["periods"].as_array().map(|periods| periods.as_slice()).unwrap_or_default();
+    let mut capnp_periods = capnp_data.reborrow().init_periods(periods_json.len() as u32);
+    let mut trip_patterns = TripPatterns::default();
+    for (j, json_data) in periods_json.iter().enumerate()
+    {
+        let mut capnp_period_data = capnp_periods.reborrow().get(j as u32);
+        capnp_period_data.set_period_uuid(optional_string(json_data.get("id")));
+        let count_trips: usize = json_data["trips"].as_array().map_or(0, |trips| trips.len());
+        write_trips(&json_data["trips"], capnp_period_data.init_trips(count_trips as u32), options.trip_patterns.then_some(&mut trip_patterns))?;
+    }
+    if !trip_patterns.is_empty()
+    {
+        trip_patterns.write(capnp_data.init_trip_patterns(trip_patterns.len() as u32))?;
+    }
+
+    let mut content : Vec<u8> = Vec::new();
+    serialize_packed::write_message(&mut content, &message)?;
+    Ok(content)
+
+}
+
+// Trips files of the line currently in the cache, if it was written by service
+fn service_trips_file_names(
+    store: &dyn CacheStore,
+    object_uuid: &str,
+) -> ::std::result::Result<Vec<String>, capnp::Error> {
+
+    let content = match store.get(&object_file_name(object_uuid))? {
+        Some(content) => content,
+        None => return Ok(Vec::new())
+    };
+    let message_reader = serialize_packed::read_message(content.as_slice(), ::capnp::message::ReaderOptions::new())?;
+    let capnp_object = message_reader.get_root::<line::Reader>()?;
+    if capnp_object.get_trips_by_service() != 1
+    {
+        return Ok(Vec::new());
+    }
+    let mut file_names : Vec<String> = Vec::new();
+    for schedule in capnp_object.get_schedules()?.iter() {
+        file_names.push(service_trips_file_name(object_uuid, schedule.get_service_uuid()?.to_str()?));
+    }
+    Ok(file_names)
+
+}
+
+fn write_trips(
+    trips_json: &serde_json::Value,
+    mut capnp_trips: ::capnp::struct_list::Builder<'_, trip::Owned>,
+    mut trip_patterns: Option<&mut TripPatterns>,
+) -> ::std::result::Result<(), capnp::Error> {
+
+    let count_trips: usize = capnp_trips.len() as usize;
+
+    for k in 0..count_trips
+    {
+        let json_data           = &trips_json[k];
+        let mut capnp_trip_data = capnp_trips.reborrow().get(k as u32);
+
+        capnp_trip_data.set_uuid(&required_string(json_data.get("id")));
+        capnp_trip_data.set_path_uuid(&required_string(json_data.get("path_id")));
+        capnp_trip_data.set_departure_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data.get("departure_time_seconds").unwrap_or(&json!(null))) as i32);
+        capnp_trip_data.set_arrival_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data.get("arrival_time_seconds").unwrap_or(&json!(null))) as i32);
+        capnp_trip_data.set_block_uuid(&optional_string(json_data.get("block_id")));
+        capnp_trip_data.set_total_capacity(json_value_or_null_to_i64_or_minus_one(&json_data.get("total_capacity").unwrap_or(&json!(null))) as i16);
+        capnp_trip_data.set_seated_capacity(json_value_or_null_to_i64_or_minus_one(&json_data.get("seated_capacity").unwrap_or(&json!(null))) as i16);
+        capnp_trip_data.set_is_frozen(json_boolean_to_i8(json_data.get("is_frozen").unwrap_or(&json!(null))));
+        capnp_trip_data.set_vehicle_type_uuid(optional_string(json_data.get("vehicle_type_id")));
+
+        // with a pattern, the node times and flags are not repeated in the trip
+        if let Some(trip_patterns) = trip_patterns.as_deref_mut()
+        {
+            let pattern_id = trip_patterns.pattern_id(json_data);
+            if pattern_id > 0
+            {
+                capnp_trip_data.set_pattern_id(pattern_id);
+                continue;
+            }
+        }
+
+        let nodes_arrival_time_seconds = json_data.get("node_arrival_times_seconds").unwrap().as_array();
+        if nodes_arrival_time_seconds != None {
+            let nodes_arrival_time_seconds = nodes_arrival_time_seconds.unwrap();
+            let nodes_count: usize = nodes_arrival_time_seconds.len();
 
+            capnp_trip_data.reborrow().init_node_arrival_times_seconds(nodes_count as u32);
+            for l in 0..nodes_count
+            {
+                capnp_trip_data.reborrow().get_node_arrival_times_seconds().unwrap().set(l as u32, json_value_or_null_to_i64_or_minus_one(&nodes_arrival_time_seconds[l]) as i32);
             }
+        }
+        else
+        {
+            capnp_trip_data.reborrow().init_node_arrival_times_seconds(0);
+        }
+
+        let nodes_departure_time_seconds = json_data.get("node_departure_times_seconds").unwrap().as_array();
+        if nodes_departure_time_seconds != None {
+            let nodes_departure_time_seconds = nodes_departure_time_seconds.unwrap();
+            let nodes_count: usize = nodes_departure_time_seconds.len();
 
+            capnp_trip_data.reborrow().init_node_departure_times_seconds(nodes_count as u32);
+            for l in 0..nodes_count
+            {
+                capnp_trip_data.reborrow().get_node_departure_times_seconds().unwrap().set(l as u32, json_value_or_null_to_i64_or_minus_one(&nodes_departure_time_seconds[l]) as i32);
+            }
+        }
+        else
+        {
+            capnp_trip_data.reborrow().init_node_departure_times_seconds(0);
+        }
+
+        let nodes_can_board = json_data.get("nodes_can_board").unwrap().as_array();
+        if nodes_can_board != None {
+            let nodes_can_board = nodes_can_board.unwrap();
+            let nodes_count: usize = nodes_can_board.len();
+
+            capnp_trip_data.reborrow().init_nodes_can_board(nodes_count as u32);
+            for l in 0..nodes_count
+            {
+                capnp_trip_data.reborrow().get_nodes_can_board().unwrap().set(l as u32, crate::utils::json_boolean_to_i8(&nodes_can_board[l]));
+            }
+        }
+        else
+        {
+            capnp_trip_data.reborrow().init_nodes_can_board(0);
+        }
+
+        let nodes_can_unboard = json_data.get("nodes_can_unboard").unwrap().as_array();
+        if nodes_can_unboard != None {
+            let nodes_can_unboard = nodes_can_unboard.unwrap();
+            let nodes_count: usize = nodes_can_unboard.len();
+
+            capnp_trip_data.reborrow().init_nodes_can_unboard(nodes_count as u32);
+            for l in 0..nodes_count
+            {
+                capnp_trip_data.reborrow().get_nodes_can_unboard().unwrap().set(l as u32, crate::utils::json_boolean_to_i8(&nodes_can_unboard[l]));
+            }
+        }
+        else
+        {
+            capnp_trip_data.reborrow().init_nodes_can_unboard(0);
         }
-        i += 1;
 
     }
 
-    serialize_packed::write_message(file, &message)
+    Ok(())
 
 }
 
 
+
+/// Part of a line object to read. The trips files of a line written by
+/// service are only read for the services and periods requested.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LineSlice<'a> {
+    Full,
+    // schedules and periods, without their trips
+    Headers,
+    Service(&'a str),
+    Period(&'a str),
+}
+
 pub fn read_object(
     object_uuid: &String,
     cache_directory_path: &str,
 ) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+    read_object_slice(object_uuid, cache_directory_path, LineSlice::Full)
+}
 
-    let object_file_path_name = format!("{}/line_{}.capnpbin", cache_directory_path, object_uuid);
-    let path = Path::new(&object_file_path_name);
-    let file = File::open(&path);
-    
-    let file = match file {
-        Ok(f) => f,
+/// Line object with its schedules and periods, without the trips
+pub fn read_object_headers(
+    object_uuid: &str,
+    cache_directory_path: &str,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+    read_object_slice(object_uuid, cache_directory_path, LineSlice::Headers)
+}
+
+/// Line object with only the schedule of a service, and its trips
+pub fn read_service_object(
+    object_uuid: &str,
+    service_uuid: &str,
+    cache_directory_path: &str,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+    read_object_slice(object_uuid, cache_directory_path, LineSlice::Service(service_uuid))
+}
+
+/// Line object with only a period, its trips and the schedule containing it
+pub fn read_period_object(
+    object_uuid: &str,
+    period_uuid: &str,
+    cache_directory_path: &str,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+    read_object_slice(object_uuid, cache_directory_path, LineSlice::Period(period_uuid))
+}
+
+fn read_message(store: &dyn CacheStore, file_name: &str) -> ::std::result::Result<::capnp::message::Reader<capnp::serialize::OwnedSegments>, capnp::Error> {
+    let content = match store.get(file_name) {
+        Ok(Some(content)) => content,
+        Ok(None) => {
+            println!("Error opening line file {}: file not found", file_name);
+            return Err(capnp::Error::failed(String::from("Cannot read line file")));
+        },
         Err(e) => {
-            println!("Error opening line file {}: {}", object_file_path_name, e);
+            println!("Error opening line file {}: {}", file_name, e);
             return Err(capnp::Error::failed(String::from("Cannot read line file")));
         }
     };
+    serialize_packed::read_message(content.as_slice(), ::capnp::message::ReaderOptions::new())
+}
+
+pub fn read_object_slice(
+    object_uuid: &str,
+    cache_directory_path: &str,
+    slice: LineSlice,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
 
-    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let store = cache_store::open(cache_directory_path)?;
+    let message_reader   = read_message(store.as_ref(), &object_file_name(object_uuid))?;
     let capnp_object = message_reader.get_root::<line::Reader>()?;
+    let trips_by_service = capnp_object.get_trips_by_service() == 1;
     
     let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap();
     let mut schedules   : serde_json::Value = json!({});
+    let line_trip_patterns = if capnp_object.has_trip_patterns() {
+        TripPatterns::read(capnp_object.get_trip_patterns()?)?
+    } else {
+        Vec::new()
+    };
 
     if capnp_object.has_schedules()
     {
         for schedule in capnp_object.get_schedules()?.iter() {
+
+            let service_uuid = schedule.get_service_uuid()?.to_str()?;
+            if let LineSlice::Service(requested_service_uuid) = slice {
+                if service_uuid != requested_service_uuid {
+                    continue;
+                }
+            }
             
             let mut schedule_json : serde_json::Value = json!({
                 "id": schedule.get_uuid()?.to_str()?,
-                "service_id": schedule.get_service_uuid()?.to_str()?,
+                "service_id": service_uuid,
                 "periods_group_shortname": empty_str_to_json_null(schedule.get_periods_group_shortname()?.to_str()?),
                 "allow_seconds_based_schedules": i8_to_json_boolean(schedule.get_allow_seconds_based_schedules()),
                 "is_frozen": i8_to_json_boolean(schedule.get_is_frozen()),
@@ -264,98 +519,66 @@ pub fn read_object(
 
             if schedule.has_periods()
             {
-                let mut periods : Vec<serde_json::Value> = Vec::with_capacity(schedule.get_periods()?.len() as usize);
-                for period in schedule.get_periods()?.iter() {
-
-                    let mut custom_start_at_str = String::from("");
-                    if period.get_custom_start_at_seconds() >= 0
-                    {
-                        let custom_start_at_seconds = period.get_custom_start_at_seconds() as u32;
-                        custom_start_at_str = seconds_since_midnight_to_time_str(&custom_start_at_seconds);
-                    }
-
-                    let mut custom_end_at_str = String::from("");
-                    if period.get_custom_end_at_seconds() >= 0
-                    {
-                        let custom_end_at_seconds = period.get_custom_end_at_seconds() as u32;
-                        custom_end_at_str = seconds_since_midnight_to_time_str(&custom_end_at_seconds);
+                let mut periods_indexes : Vec<u32> = Vec::with_capacity(schedule.get_periods()?.len() as usize);
+                for (j, period) in schedule.get_periods()?.iter().enumerate() {
+                    if let LineSlice::Period(requested_period_uuid) = slice {
+                        if period.get_uuid()?.to_str()? != requested_period_uuid {
+                            continue;
+                        }
                     }
+                    periods_indexes.push(j as u32);
+                }
+                if periods_indexes.is_empty() && matches!(slice, LineSlice::Period(_)) {
+                    continue;
+                }
 
-                    let mut period_json : serde_json::Value = json!({
-                        "period_shortname": empty_str_to_json_null(period.get_period_shortname()?.to_str()?),
-                        "outbound_path_id": empty_str_to_json_null(period.get_outbound_path_uuid()?.to_str()?),
-                        "inbound_path_id": empty_str_to_json_null(period.get_inbound_path_uuid()?.to_str()?),
-                        "custom_start_at_str": empty_str_to_json_null(custom_start_at_str.as_str()),
-                        "custom_end_at_str": empty_str_to_json_null(custom_end_at_str.as_str()),
-                        "start_at_hour": minus_one_f64_to_null(period.get_start_at_seconds() as f64 / 3600.0),
-                        "end_at_hour": minus_one_f64_to_null(period.get_end_at_seconds() as f64 / 3600.0),
-                        "interval_seconds": minus_one_i64_to_null(period.get_interval_seconds() as i64),
-                        "number_of_units": minus_one_i64_to_null(period.get_number_of_units() as i64),
-                        "is_frozen": i8_to_json_boolean(period.get_is_frozen()),
-                        "id": empty_str_to_json_null(period.get_uuid()?.to_str()?),
-                        "schedule_id": schedule.get_uuid()?.to_str()?
-                    });
-
-                    if period.has_trips()
+                // the trips file is only read once the schedule is known to be needed
+                let service_trips_message = if trips_by_service && slice != LineSlice::Headers {
+                    Some(read_message(store.as_ref(), &service_trips_file_name(object_uuid, service_uuid))?)
+                } else {
+                    None
+                };
+                let (service_periods_trips, service_trip_patterns) = match &service_trips_message {
+                    Some(message_reader) => {
+                        let capnp_service_trips = message_reader.get_root::<service_trips::Reader>()?;
+                        let service_trip_patterns = if capnp_service_trips.has_trip_patterns() {
+                            TripPatterns::read(capnp_service_trips.get_trip_patterns()?)?
+                        } else {
+                            Vec::new()
+                        };
+                        (Some(capnp_service_trips.get_periods()?), service_trip_patterns)
+                    },
+                    None => (None, Vec::new())
+                };
+                let trip_patterns = if service_periods_trips.is_some() { &service_trip_patterns } else { &line_trip_patterns };
+
+                let mut periods : Vec<serde_json::Value> = Vec::with_capacity(periods_indexes.len());
+                for j in periods_indexes {
+                    let period = schedule.get_periods()?.get(j);
+                    let mut period_json = read_period(&period, schedule.get_uuid()?.to_str()?)?;
+
+                    let trips = match (&service_periods_trips, slice) {
+                        (_, LineSlice::Headers) => None,
+                        (Some(service_periods_trips), _) if j < service_periods_trips.len() => Some(service_periods_trips.get(j).get_trips()?),
+                        (Some(_), _) => None,
+                        (None, _) if period.has_trips() => Some(period.get_trips()?),
+                        (None, _) => None
+                    };
+                    if let Some(trips) = trips
                     {
-                        let mut trips : Vec<serde_json::Value> = Vec::with_capacity(period.get_trips()?.len() as usize);
-                        for trip in period.get_trips()?.iter() {
-                            let mut trip_json : serde_json::Value = json!({
-                                "id": trip.get_uuid()?.to_str()?,
-                                "path_id": trip.get_path_uuid()?.to_str()?,
-                                "departure_time_seconds": minus_one_i64_to_null(trip.get_departure_time_seconds() as i64),
-                                "arrival_time_seconds": minus_one_i64_to_null(trip.get_arrival_time_seconds() as i64),
-                                "block_id": empty_str_to_json_null(trip.get_block_uuid()?.to_str()?),
-                                "total_capacity": minus_one_i64_to_null(trip.get_total_capacity() as i64),
-                                "seated_capacity": minus_one_i64_to_null(trip.get_seated_capacity() as i64),
-                                "is_frozen": i8_to_json_boolean(period.get_is_frozen()),
-                                "schedule_period_id": empty_str_to_json_null(period.get_uuid()?.to_str()?)
-                            });
-
-                            if trip.has_node_arrival_times_seconds()
-                            {
-                                let mut node_arrival_times_seconds : Vec<serde_json::Value> = Vec::with_capacity(trip.get_node_arrival_times_seconds()?.len() as usize);
-                                for node_arrival_time_seconds in trip.get_node_arrival_times_seconds()?.iter() {
-                                    node_arrival_times_seconds.push(json!(minus_one_i64_to_null(node_arrival_time_seconds as i64)));
-                                }
-                                trip_json["node_arrival_times_seconds"] = json!(node_arrival_times_seconds);
-                            }
-                            if trip.has_node_departure_times_seconds()
-                            {
-                                let mut node_departure_times_seconds : Vec<serde_json::Value> = Vec::with_capacity(trip.get_node_departure_times_seconds()?.len() as usize);
-                                for node_departure_time_seconds in trip.get_node_departure_times_seconds()?.iter() {
-                                    node_departure_times_seconds.push(json!(minus_one_i64_to_null(node_departure_time_seconds as i64)));
-                                }
-                                trip_json["node_departure_times_seconds"] = json!(node_departure_times_seconds);
-                            }
-                            if trip.has_nodes_can_board()
-                            {
-                                let mut nodes_can_board : Vec<serde_json::Value> = Vec::with_capacity(trip.get_nodes_can_board()?.len() as usize);
-                                for node_can_board in trip.get_nodes_can_board()?.iter() {
-                                    nodes_can_board.push(json!(i8_to_json_boolean(node_can_board)));
-                                }
-                                trip_json["nodes_can_board"] = json!(nodes_can_board);
-                            }
-                            if trip.has_nodes_can_unboard()
-                            {
-                                let mut nodes_can_unboard : Vec<serde_json::Value> = Vec::with_capacity(trip.get_nodes_can_unboard()?.len() as usize);
-                                for node_can_unboard in trip.get_nodes_can_unboard()?.iter() {
-                                    nodes_can_unboard.push(json!(i8_to_json_boolean(node_can_unboard)));
-                                }
-                                trip_json["nodes_can_unboard"] = json!(nodes_can_unboard);
-                            }
-
-                            trips.push(trip_json);
-                        }
-                        period_json["trips"] = json!(trips);
+                        period_json["trips"] = json!(read_trips(trips, &period, trip_patterns)?);
                     }
 
                     periods.push(period_json);
                 }
                 schedule_json["periods"] = json!(periods);
             }
+            else if matches!(slice, LineSlice::Period(_))
+            {
+                continue;
+            }
 
-            schedules[schedule.get_service_uuid().unwrap().to_str()?] = schedule_json;
+            schedules[service_uuid] = schedule_json;
         }
         
     }
@@ -386,3 +609,116 @@ pub fn read_object(
 
 }
 
+fn read_period(
+    period: &period::Reader,
+    schedule_uuid: &str,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let mut custom_start_at_str = String::from("");
+    if period.get_custom_start_at_seconds() >= 0
+    {
+        let custom_start_at_seconds = period.get_custom_start_at_seconds() as u32;
+        custom_start_at_str = seconds_since_midnight_to_time_str(&custom_start_at_seconds);
+    }
+
+    let mut custom_end_at_str = String::from("");
+    if period.get_custom_end_at_seconds() >= 0
+    {
+        let custom_end_at_seconds = period.get_custom_end_at_seconds() as u32;
+        custom_end_at_str = seconds_since_midnight_to_time_str(&custom_end_at_seconds);
+    }
+
+    let period_json : serde_json::Value = json!({
+        "period_shortname": empty_str_to_json_null(period.get_period_shortname()?.to_str()?),
+        "outbound_path_id": empty_str_to_json_null(period.get_outbound_path_uuid()?.to_str()?),
+        "inbound_path_id": empty_str_to_json_null(period.get_inbound_path_uuid()?.to_str()?),
+        "custom_start_at_str": empty_str_to_json_null(custom_start_at_str.as_str()),
+        "custom_end_at_str": empty_str_to_json_null(custom_end_at_str.as_str()),
+        "start_at_hour": minus_one_f64_to_null(period.get_start_at_seconds() as f64 / 3600.0),
+        "end_at_hour": minus_one_f64_to_null(period.get_end_at_seconds() as f64 / 3600.0),
+        "interval_seconds": minus_one_i64_to_null(period.get_interval_seconds() as i64),
+        "number_of_units": minus_one_i64_to_null(period.get_number_of_units() as i64),
+        "is_frozen": i8_to_json_boolean(period.get_is_frozen()),
+        "id": empty_str_to_json_null(period.get_uuid()?.to_str()?),
+        "vehicle_type_id": empty_str_to_json_null(period.get_vehicle_type_uuid()?.to_str()?),
+        "schedule_id": schedule_uuid
+    });
+
+    Ok(period_json)
+}
+
+fn read_trips(
+    capnp_trips: ::capnp::struct_list::Reader<'_, trip::Owned>,
+    period: &period::Reader,
+    trip_patterns: &[TripPattern],
+) -> ::std::result::Result<Vec<serde_json::Value>, capnp::Error> {
+
+    let mut trips : Vec<serde_json::Value> = Vec::with_capacity(capnp_trips.len() as usize);
+    for trip in capnp_trips.iter() {
+        let mut trip_json : serde_json::Value = json!({
+            "id": trip.get_uuid()?.to_str()?,
+            "path_id": trip.get_path_uuid()?.to_str()?,
+            "departure_time_seconds": minus_one_i64_to_null(trip.get_departure_time_seconds() as i64),
+            "arrival_time_seconds": minus_one_i64_to_null(trip.get_arrival_time_seconds() as i64),
+            "block_id": empty_str_to_json_null(trip.get_block_uuid()?.to_str()?),
+            "total_capacity": minus_one_i64_to_null(trip.get_total_capacity() as i64),
+            "seated_capacity": minus_one_i64_to_null(trip.get_seated_capacity() as i64),
+            "vehicle_type_id": empty_str_to_json_null(trip.get_vehicle_type_uuid()?.to_str()?),
+            "is_frozen": i8_to_json_boolean(period.get_is_frozen()),
+            "schedule_period_id": empty_str_to_json_null(period.get_uuid()?.to_str()?)
+        });
+
+        let pattern_id = trip.get_pattern_id();
+        if pattern_id > 0
+        {
+            let trip_pattern = match trip_patterns.get(pattern_id as usize - 1) {
+                Some(trip_pattern) => trip_pattern,
+                None => return Err(capnp::Error::failed(format!("Unknown trip pattern {} for trip {}", pattern_id, trip.get_uuid()?.to_str()?)))
+            };
+            let (node_arrival_times_seconds, node_departure_times_seconds) = trip_pattern.node_times(trip.get_departure_time_seconds());
+            trip_json["node_arrival_times_seconds"] = json!(node_arrival_times_seconds.into_iter().map(|time| minus_one_i64_to_null(time as i64)).collect::<Vec<serde_json::Value>>());
+            trip_json["node_departure_times_seconds"] = json!(node_departure_times_seconds.into_iter().map(|time| minus_one_i64_to_null(time as i64)).collect::<Vec<serde_json::Value>>());
+            trip_json["nodes_can_board"] = json!(trip_pattern.nodes_can_board);
+            trip_json["nodes_can_unboard"] = json!(trip_pattern.nodes_can_unboard);
+            trips.push(trip_json);
+            continue;
+        }
+
+        if trip.has_node_arrival_times_seconds()
+        {
+            let mut node_arrival_times_seconds : Vec<serde_json::Value> = Vec::with_capacity(trip.get_node_arrival_times_seconds()?.len() as usize);
+            for node_arrival_time_seconds in trip.get_node_arrival_times_seconds()?.iter() {
+                node_arrival_times_seconds.push(json!(minus_one_i64_to_null(node_arrival_time_seconds as i64)));
+            }
+            trip_json["node_arrival_times_seconds"] = json!(node_arrival_times_seconds);
+        }
+        if trip.has_node_departure_times_seconds()
+        {
+            let mut node_departure_times_seconds : Vec<serde_json::Value> = Vec::with_capacity(trip.get_node_departure_times_seconds()?.len() as usize);
+            for node_departure_time_seconds in trip.get_node_departure_times_seconds()?.iter() {
+                node_departure_times_seconds.push(json!(minus_one_i64_to_null(node_departure_time_seconds as i64)));
+            }
+            trip_json["node_departure_times_seconds"] = json!(node_departure_times_seconds);
+        }
+        if trip.has_nodes_can_board()
+        {
+            let mut nodes_can_board : Vec<serde_json::Value> = Vec::with_capacity(trip.get_nodes_can_board()?.len() as usize);
+            for node_can_board in trip.get_nodes_can_board()?.iter() {
+                nodes_can_board.push(json!(i8_to_json_boolean(node_can_board)));
+            }
+            trip_json["nodes_can_board"] = json!(nodes_can_board);
+        }
+        if trip.has_nodes_can_unboard()
+        {
+            let mut nodes_can_unboard : Vec<serde_json::Value> = Vec::with_capacity(trip.get_nodes_can_unboard()?.len() as usize);
+            for node_can_unboard in trip.get_nodes_can_unboard()?.iter() {
+                nodes_can_unboard.push(json!(i8_to_json_boolean(node_can_unboard)));
+            }
+            trip_json["nodes_can_unboard"] = json!(nodes_can_unboard);
+        }
+
+        trips.push(trip_json);
+    }
+    Ok(trips)
+
+}
@@ -9,10 +9,18 @@
 /* Define and expose all data types in the module */
 
 pub mod agency_collection;
+pub mod fare_collection;
+pub mod household_collection;
 pub mod line_collection;
 pub mod line;
 pub mod node_collection;
 pub mod node;
+pub mod od_trip_collection;
 pub mod path_collection;
+pub mod person_collection;
 pub mod scenario_collection;
 pub mod service_collection;
+pub mod station_collection;
+pub mod trip_pattern;
+pub mod vehicle_type_collection;
+pub mod zone_collection;
@@ -7,11 +7,9 @@
  */
 
 use crate::node_capnp::{node};
-use std::fs::File;
-use std::path::Path;
+use crate::cache_store;
 use capnp::serialize_packed;
 use serde_json;
-use std::io::BufReader;
 use geojson::{Value as GeojsonValue};
 use crate::utils::{ 
     required_string, 
@@ -47,15 +45,8 @@ pub fn write_object(
         Err(MyError::new("node uuid is invalid or empty"))
     }*/
 
-    let object_file_path_name = format!("{}/node_{}.capnpbin", cache_directory_path, object_uuid);
-    let path = Path::new(&object_file_path_name);
+    let object_file_name = format!("node_{}.capnpbin", object_uuid);
 
-    let file_check = File::create(&path);
-
-    let file = file_check.unwrap();
-
-    /* TODO: deal with error when creating object file using uuid */
-    
     let mut capnp_data = message.init_root::<node::Builder>();
 
     let geometry = geojson::Geometry::from_json_value(json_object["geography"].clone());
@@ -113,7 +104,9 @@ pub fn write_object(
         }
     }
 
-    serialize_packed::write_message(file, &message)
+    let mut content : Vec<u8> = Vec::new();
+    serialize_packed::write_message(&mut content, &message)?;
+    cache_store::open(cache_directory_path)?.put(&object_file_name, &content)
 
 }
 
@@ -123,19 +116,20 @@ pub fn read_object(
     cache_directory_path: &str,
 ) -> ::std::result::Result<serde_json::Value, capnp::Error> {
 
-    let object_file_path_name = format!("{}/node_{}.capnpbin", cache_directory_path, object_uuid);
-    let path = Path::new(&object_file_path_name);
-    let file = File::open(&path);
-    
-    let file = match file {
-        Ok(f) => f,
+    let object_file_name = format!("node_{}.capnpbin", object_uuid);
+    let content = match cache_store::open(cache_directory_path)?.get(&object_file_name) {
+        Ok(Some(content)) => content,
+        Ok(None) => {
+            println!("Error opening node file {}/{}: file not found", cache_directory_path, object_file_name);
+            return Err(capnp::Error::failed(String::from("Cannot read node file")));
+        },
         Err(e) => {
-            println!("Error opening node file {}: {}", object_file_path_name, e);
+            println!("Error opening node file {}/{}: {}", cache_directory_path, object_file_name, e);
             return Err(capnp::Error::failed(String::from("Cannot read node file")));
         }
     };
 
-    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let message_reader   = serialize_packed::read_message(content.as_slice(), ::capnp::message::ReaderOptions::new())?;
     let capnp_object = message_reader.get_root::<node::Reader>()?;
     
     let integer_id = capnp_object.get_id() as u32;
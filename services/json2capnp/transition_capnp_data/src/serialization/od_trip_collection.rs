@@ -0,0 +1,104 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Origin-destination trips of the demand data, with their times, mode and expansion factor */
+
+use crate::odTripCollection_capnp::od_trip_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    empty_str_to_json_null,
+    json_value_or_null_to_i64_or_minus_one,
+    minus_one_i64_to_null,
+    json_value_or_null_to_thousandths_or_minus_one,
+    minus_one_thousandths_to_null,
+    json_point_to_i32_coordinates,
+    i32_coordinates_to_json_point
+};
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    let json_objects = json["odTrips"].as_array().ok_or_else(|| capnp::Error::failed(String::from("OD trips are missing or not an array")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_od_trips(json_objects.len() as u32);
+
+    for (i, json_data) in json_objects.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        let uuid = required_string(json_data.get("id"));
+        let (origin_longitude, origin_latitude) = json_point_to_i32_coordinates(&json_data["origin_geography"])
+            .ok_or_else(|| capnp::Error::failed(format!("OD trip {} origin_geography is missing or not a point", uuid)))?;
+        let (destination_longitude, destination_latitude) = json_point_to_i32_coordinates(&json_data["destination_geography"])
+            .ok_or_else(|| capnp::Error::failed(format!("OD trip {} destination_geography is missing or not a point", uuid)))?;
+
+        capnp_data.set_uuid(uuid);
+        capnp_data.set_id(json_data["integer_id"].as_u64().unwrap_or(0) as u32);
+        capnp_data.set_internal_id(optional_string(json_data.get("internal_id")));
+        capnp_data.set_data_source_uuid(optional_string(json_data.get("data_source_id")));
+        capnp_data.set_household_uuid(optional_string(json_data.get("household_id")));
+        capnp_data.set_person_uuid(optional_string(json_data.get("person_id")));
+        capnp_data.set_origin_latitude(origin_latitude);
+        capnp_data.set_origin_longitude(origin_longitude);
+        capnp_data.set_destination_latitude(destination_latitude);
+        capnp_data.set_destination_longitude(destination_longitude);
+        capnp_data.set_departure_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data["departure_time_seconds"]) as i32);
+        capnp_data.set_arrival_time_seconds(json_value_or_null_to_i64_or_minus_one(&json_data["arrival_time_seconds"]) as i32);
+        capnp_data.set_mode(optional_string(json_data.get("mode")));
+        capnp_data.set_origin_activity(optional_string(json_data.get("origin_activity")));
+        capnp_data.set_destination_activity(optional_string(json_data.get("destination_activity")));
+        capnp_data.set_expansion_factor(json_value_or_null_to_thousandths_or_minus_one(&json_data["expansion_factor"]));
+        capnp_data.set_data(json_data.get("data").unwrap_or(&json!({})).to_string().as_str());
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_od_trips()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_od_trips()?.iter() {
+
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        collection_json_vec.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "integer_id": capnp_object.get_id(),
+            "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+            "data_source_id": empty_str_to_json_null(capnp_object.get_data_source_uuid()?.to_str()?),
+            "household_id": empty_str_to_json_null(capnp_object.get_household_uuid()?.to_str()?),
+            "person_id": empty_str_to_json_null(capnp_object.get_person_uuid()?.to_str()?),
+            "origin_geography": i32_coordinates_to_json_point(capnp_object.get_origin_longitude(), capnp_object.get_origin_latitude()),
+            "destination_geography": i32_coordinates_to_json_point(capnp_object.get_destination_longitude(), capnp_object.get_destination_latitude()),
+            "departure_time_seconds": minus_one_i64_to_null(capnp_object.get_departure_time_seconds() as i64),
+            "arrival_time_seconds": minus_one_i64_to_null(capnp_object.get_arrival_time_seconds() as i64),
+            "mode": empty_str_to_json_null(capnp_object.get_mode()?.to_str()?),
+            "origin_activity": empty_str_to_json_null(capnp_object.get_origin_activity()?.to_str()?),
+            "destination_activity": empty_str_to_json_null(capnp_object.get_destination_activity()?.to_str()?),
+            "expansion_factor": minus_one_thousandths_to_null(capnp_object.get_expansion_factor()),
+            "data": data_attributes
+        }));
+
+    }
+
+    Ok(json!({
+        "odTrips": serde_json::Value::Array(collection_json_vec)
+    }))
+
+}
@@ -14,13 +14,68 @@ use geojson::GeoJson;
 use geobuf;
 use protobuf::Message;
 
+/// Precision of the encoded polylines when not specified, as in the Google
+/// polyline algorithm
+pub const DEFAULT_POLYLINE_PRECISION: u32 = 5;
+
+/// How the path geographies are represented in the collection json
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GeometryEncoding {
+    GeoJson,
+    // Features have a null geometry and an encoded "polyline" string instead
+    Polyline { precision: u32 },
+}
+
+fn validate_polyline_precision(precision: u32) -> Result<u32, capnp::Error> {
+    if precision == 0 || precision > 9 {
+        return Err(capnp::Error::failed(format!("Invalid polyline precision {}, must be between 1 and 9", precision)));
+    }
+    Ok(precision)
+}
+
+pub fn encode_polyline(geometry: &serde_json::Value, precision: u32) -> Result<String, capnp::Error> {
+    let coordinates = geometry["coordinates"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Path geometry is not a LineString")))?;
+    polyline::encode_coordinates(coordinates.iter().map(|coordinate| (
+        coordinate[0].as_f64().unwrap_or(0.0),
+        coordinate[1].as_f64().unwrap_or(0.0)
+    ).into()), validate_polyline_precision(precision)?).map_err(capnp::Error::failed)
+}
+
+pub fn decode_polyline(encoded_polyline: &str, precision: u32) -> Result<serde_json::Value, capnp::Error> {
+    let line_string = polyline::decode_polyline(encoded_polyline, validate_polyline_precision(precision)?).map_err(capnp::Error::failed)?;
+    let coordinates : Vec<[f64; 2]> = line_string.0.iter().map(|coordinate| [coordinate.x, coordinate.y]).collect();
+    Ok(json!({
+        "type": "LineString",
+        "coordinates": coordinates
+    }))
+}
+
+// Replace the "polyline" of the features by their LineString geometry
+fn polylines_to_geometries(paths_json: &mut serde_json::Value, precision: u32) -> Result<(), capnp::Error> {
+    if let Some(features) = paths_json["features"].as_array_mut() {
+        for feature in features.iter_mut() {
+            if let Some(encoded_polyline) = feature.get("polyline").and_then(|encoded_polyline| encoded_polyline.as_str()).map(String::from) {
+                feature["geometry"] = decode_polyline(&encoded_polyline, precision)?;
+                feature.as_object_mut().unwrap().remove("polyline");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The path features may have an encoded "polyline" instead of a geometry,
+/// at the "polyline_precision" of the json (5 by default)
 pub fn write_collection(
     json: &serde_json::Value,
-    file: &mut std::fs::File,
+    file: &mut dyn std::io::Write,
 ) -> ::std::result::Result<(), capnp::Error> {
     let mut message = ::capnp::message::Builder::new_default();
+
+    let mut paths_json = json["paths"].clone();
+    let polyline_precision = json["polyline_precision"].as_u64().map(|precision| precision as u32).unwrap_or(DEFAULT_POLYLINE_PRECISION);
+    polylines_to_geometries(&mut paths_json, polyline_precision)?;
     
-    let geojson_data : GeoJson = GeoJson::from_json_value((json["paths"]).clone()).unwrap();
+    let geojson_data : GeoJson = GeoJson::from_json_value(paths_json).unwrap();
 
     match geojson_data {
         geojson::GeoJson::FeatureCollection(feature_collection) => {
@@ -116,8 +171,19 @@ pub fn write_collection(
 
 
 pub fn read_collection(
-    file: &mut std::fs::File,
+    file: &mut dyn std::io::Read,
 ) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+    read_collection_with_geometry_encoding(file, GeometryEncoding::GeoJson)
+}
+
+pub fn read_collection_with_geometry_encoding(
+    file: &mut dyn std::io::Read,
+    geometry_encoding: GeometryEncoding,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    if let GeometryEncoding::Polyline { precision } = geometry_encoding {
+        validate_polyline_precision(precision)?;
+    }
 
     let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
     let capnp_collection = message_reader.get_root::<collection::Reader>()?;
@@ -169,16 +235,26 @@ pub fn read_collection(
         geojson["id"] = json!(integer_id);
         geojson["properties"] = properties_json;
 
+        if let GeometryEncoding::Polyline { precision } = geometry_encoding {
+            geojson["polyline"] = if geojson["geometry"].is_null() { json!(null) } else { json!(encode_polyline(&geojson["geometry"], precision)?) };
+            geojson["geometry"] = json!(null);
+        }
+
         collection_json_vec.push(geojson);
 
     }
 
-    Ok(json!({
+    let mut collection_json = json!({
         "paths": {
             "type": "FeatureCollection",
             "features": serde_json::Value::Array(collection_json_vec)
         }
-    }))
+    });
+    if let GeometryEncoding::Polyline { precision } = geometry_encoding {
+        collection_json["polyline_precision"] = json!(precision);
+    }
+
+    Ok(collection_json)
 
 }
 
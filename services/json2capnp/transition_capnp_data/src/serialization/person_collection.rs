@@ -0,0 +1,90 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Persons of the demand data, linked to their household */
+
+use crate::personCollection_capnp::person_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    empty_str_to_json_null,
+    json_boolean_to_i8,
+    i8_to_json_boolean,
+    json_value_or_null_to_i64_or_minus_one,
+    minus_one_i64_to_null,
+    json_value_or_null_to_thousandths_or_minus_one,
+    minus_one_thousandths_to_null
+};
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    let json_objects = json["persons"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Persons are missing or not an array")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_persons(json_objects.len() as u32);
+
+    for (i, json_data) in json_objects.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        capnp_data.set_uuid(required_string(json_data.get("id")));
+        capnp_data.set_id(json_data["integer_id"].as_u64().unwrap_or(0) as u32);
+        capnp_data.set_internal_id(optional_string(json_data.get("internal_id")));
+        capnp_data.set_data_source_uuid(optional_string(json_data.get("data_source_id")));
+        capnp_data.set_household_uuid(optional_string(json_data.get("household_id")));
+        capnp_data.set_age(json_value_or_null_to_i64_or_minus_one(&json_data["age"]) as i16);
+        capnp_data.set_gender(optional_string(json_data.get("gender")));
+        capnp_data.set_occupation(optional_string(json_data.get("occupation")));
+        capnp_data.set_driving_license_owner(json_boolean_to_i8(&json_data["driving_license_owner"]));
+        capnp_data.set_transit_pass_owner(json_boolean_to_i8(&json_data["transit_pass_owner"]));
+        capnp_data.set_expansion_factor(json_value_or_null_to_thousandths_or_minus_one(&json_data["expansion_factor"]));
+        capnp_data.set_data(json_data.get("data").unwrap_or(&json!({})).to_string().as_str());
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_persons()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_persons()?.iter() {
+
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        collection_json_vec.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "integer_id": capnp_object.get_id(),
+            "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+            "data_source_id": empty_str_to_json_null(capnp_object.get_data_source_uuid()?.to_str()?),
+            "household_id": empty_str_to_json_null(capnp_object.get_household_uuid()?.to_str()?),
+            "age": minus_one_i64_to_null(capnp_object.get_age() as i64),
+            "gender": empty_str_to_json_null(capnp_object.get_gender()?.to_str()?),
+            "occupation": empty_str_to_json_null(capnp_object.get_occupation()?.to_str()?),
+            "driving_license_owner": i8_to_json_boolean(capnp_object.get_driving_license_owner()),
+            "transit_pass_owner": i8_to_json_boolean(capnp_object.get_transit_pass_owner()),
+            "expansion_factor": minus_one_thousandths_to_null(capnp_object.get_expansion_factor()),
+            "data": data_attributes
+        }));
+
+    }
+
+    Ok(json!({
+        "persons": serde_json::Value::Array(collection_json_vec)
+    }))
+
+}
@@ -20,7 +20,7 @@ use crate::utils::{
 
 pub fn write_collection(
     json: &serde_json::Value,
-    file: &mut std::fs::File,
+    file: &mut dyn std::io::Write,
 ) -> ::std::result::Result<(), capnp::Error> {
     let mut message = ::capnp::message::Builder::new_default();
 
@@ -220,7 +220,7 @@ pub fn write_collection(
 
 
 pub fn read_collection(
-    file: &mut std::fs::File,
+    file: &mut dyn std::io::Read,
 ) -> ::std::result::Result<serde_json::Value, capnp::Error> {
 
     let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
@@ -0,0 +1,112 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Stations grouping the nodes (platforms) referencing them by station_id */
+
+use crate::stationCollection_capnp::station_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    json_boolean_to_i8,
+    empty_str_to_json_null,
+    i8_to_json_boolean,
+    json_point_to_i32_coordinates,
+    i32_coordinates_to_json_point
+};
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    if json["stations"]["type"] != "FeatureCollection" {
+        return Err(capnp::Error::failed(String::from("Stations geojson is invalid, empty or not a FeatureCollection")));
+    }
+    let features = json["stations"]["features"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Stations geojson has no features")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_stations(features.len() as u32);
+    for (i, feature) in features.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        let properties = &feature["properties"];
+        let (longitude, latitude) = json_point_to_i32_coordinates(&feature["geometry"]).unwrap_or((-1, -1));
+
+        capnp_data.set_uuid(required_string(properties.get("id"))); // required
+        capnp_data.set_id(properties["integer_id"].as_u64().unwrap_or(0) as u32);
+        capnp_data.set_internal_id(optional_string(properties.get("internal_id")));
+        capnp_data.set_code(optional_string(properties.get("code")));
+        capnp_data.set_name(optional_string(properties.get("name")));
+        capnp_data.set_latitude(latitude);
+        capnp_data.set_longitude(longitude);
+        capnp_data.set_color(optional_string(properties.get("color")));
+        capnp_data.set_description(optional_string(properties.get("description")));
+        capnp_data.set_data(properties.get("data").unwrap_or(&json!({})).to_string().as_str());
+        capnp_data.set_is_enabled(json_boolean_to_i8(&properties["is_enabled"]));
+        capnp_data.set_is_frozen(json_boolean_to_i8(&properties["is_frozen"]));
+
+        let nodes_uuids : Vec<&str> = properties["nodes"].as_array().map(|nodes| nodes.iter().filter_map(|node_uuid| node_uuid.as_str()).collect()).unwrap_or_default();
+        let mut capnp_nodes_uuids = capnp_data.init_nodes_uuids(nodes_uuids.len() as u32);
+        for (j, node_uuid) in nodes_uuids.iter().enumerate() {
+            capnp_nodes_uuids.set(j as u32, *node_uuid);
+        }
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_stations()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_stations()?.iter() {
+
+        let integer_id = capnp_object.get_id();
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        let mut nodes_uuids : Vec<serde_json::Value> = Vec::with_capacity(capnp_object.get_nodes_uuids()?.len() as usize);
+        for node_uuid in capnp_object.get_nodes_uuids()?.iter() {
+            nodes_uuids.push(json!(node_uuid?.to_str()?));
+        }
+
+        collection_json_vec.push(json!({
+            "type": "Feature",
+            "id": integer_id,
+            "geometry": i32_coordinates_to_json_point(capnp_object.get_longitude(), capnp_object.get_latitude()),
+            "properties": {
+                "id": capnp_object.get_uuid()?.to_str()?,
+                "integer_id": integer_id,
+                "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+                "code": empty_str_to_json_null(capnp_object.get_code()?.to_str()?),
+                "name": empty_str_to_json_null(capnp_object.get_name()?.to_str()?),
+                "color": empty_str_to_json_null(capnp_object.get_color()?.to_str()?),
+                "description": empty_str_to_json_null(capnp_object.get_description()?.to_str()?),
+                "is_enabled": i8_to_json_boolean(capnp_object.get_is_enabled()),
+                "is_frozen": i8_to_json_boolean(capnp_object.get_is_frozen()),
+                "nodes": nodes_uuids,
+                "data": data_attributes
+            }
+        }));
+
+    }
+
+    Ok(json!({
+        "stations": {
+            "type": "FeatureCollection",
+            "features": serde_json::Value::Array(collection_json_vec)
+        }
+    }))
+
+}
@@ -0,0 +1,178 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Trip patterns: node times and boarding flags shared by the trips of a line
+ * object, each trip keeping only its departure time and the id of its pattern */
+
+use std::collections::HashMap;
+use crate::line_capnp::trip_pattern;
+use crate::utils::{json_value_or_null_to_i64_or_minus_one, json_boolean_to_i8};
+
+// Node times as stored in the trips, -1 for no time
+fn json_node_times(trip: &serde_json::Value, attribute: &str) -> Vec<i32> {
+    trip[attribute].as_array().map(|times| times.iter().map(|time| json_value_or_null_to_i64_or_minus_one(time) as i32).collect()).unwrap_or_default()
+}
+
+// Boarding flags, None if any of them is not a boolean, as a bitset has no null
+fn json_node_flags(trip: &serde_json::Value, attribute: &str) -> Option<Vec<bool>> {
+    trip[attribute].as_array().map(|flags| flags.iter().map(|flag| match json_boolean_to_i8(flag) {
+        1 => Some(true),
+        0 => Some(false),
+        _ => None
+    }).collect()).unwrap_or(Some(Vec::new()))
+}
+
+// Difference to the previous time, -1 when there is no time
+fn time_delta(time: i32, previous_time: &mut i32) -> Option<i32> {
+    if time == -1 {
+        return Some(-1);
+    }
+    if time < *previous_time {
+        return None;
+    }
+    let delta = time - *previous_time;
+    *previous_time = time;
+    Some(delta)
+}
+
+fn flags_to_bitset(flags: &[bool]) -> Vec<u8> {
+    let mut bitset = vec![0u8; flags.len().div_ceil(8)];
+    for (i, flag) in flags.iter().enumerate() {
+        if *flag {
+            bitset[i / 8] |= 1 << (i % 8);
+        }
+    }
+    bitset
+}
+
+fn bitset_to_flags(bitset: &[u8], nodes_count: usize) -> Vec<bool> {
+    (0..nodes_count).map(|i| bitset.get(i / 8).is_some_and(|byte| byte & (1 << (i % 8)) != 0)).collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TripPattern {
+    pub path_uuid: String,
+    pub travel_times_seconds: Vec<i32>,
+    pub dwell_times_seconds: Vec<i32>,
+    pub nodes_can_board: Vec<bool>,
+    pub nodes_can_unboard: Vec<bool>,
+}
+
+impl TripPattern {
+
+    /// Pattern of a trip, in the format of the line objects. None when the
+    /// trip cannot be rebuilt exactly from a pattern: no departure time, null
+    /// boarding flags, node lists of different lengths or times going back.
+    pub fn from_trip_json(trip: &serde_json::Value) -> Option<Self> {
+        let departure_time = json_value_or_null_to_i64_or_minus_one(&trip["departure_time_seconds"]) as i32;
+        if departure_time < 0 {
+            return None;
+        }
+        let arrival_times = json_node_times(trip, "node_arrival_times_seconds");
+        let departure_times = json_node_times(trip, "node_departure_times_seconds");
+        let nodes_can_board = json_node_flags(trip, "nodes_can_board")?;
+        let nodes_can_unboard = json_node_flags(trip, "nodes_can_unboard")?;
+        let nodes_count = arrival_times.len();
+        if departure_times.len() != nodes_count || nodes_can_board.len() != nodes_count || nodes_can_unboard.len() != nodes_count {
+            return None;
+        }
+
+        let mut travel_times_seconds = Vec::with_capacity(nodes_count);
+        let mut dwell_times_seconds = Vec::with_capacity(nodes_count);
+        let mut previous_time = departure_time;
+        for (arrival_time, node_departure_time) in arrival_times.into_iter().zip(departure_times) {
+            travel_times_seconds.push(time_delta(arrival_time, &mut previous_time)?);
+            dwell_times_seconds.push(time_delta(node_departure_time, &mut previous_time)?);
+        }
+
+        Some(TripPattern {
+            path_uuid: trip["path_id"].as_str().unwrap_or("").to_string(),
+            travel_times_seconds,
+            dwell_times_seconds,
+            nodes_can_board,
+            nodes_can_unboard,
+        })
+    }
+
+    /// Node arrival and departure times of a trip leaving at departure_time,
+    /// -1 for no time, as stored in the trips
+    pub fn node_times(&self, departure_time: i32) -> (Vec<i32>, Vec<i32>) {
+        let mut previous_time = departure_time;
+        let mut add_delta = |delta: i32| if delta == -1 { -1 } else { previous_time += delta; previous_time };
+        self.travel_times_seconds.iter().zip(self.dwell_times_seconds.iter()).map(|(travel_time, dwell_time)| {
+            let arrival_time = add_delta(*travel_time);
+            (arrival_time, add_delta(*dwell_time))
+        }).unzip()
+    }
+
+    pub fn write(&self, mut capnp_pattern: trip_pattern::Builder) -> Result<(), capnp::Error> {
+        capnp_pattern.set_path_uuid(self.path_uuid.as_str());
+        capnp_pattern.set_travel_times_seconds(&self.travel_times_seconds[..])?;
+        capnp_pattern.set_dwell_times_seconds(&self.dwell_times_seconds[..])?;
+        capnp_pattern.set_nodes_can_board(&flags_to_bitset(&self.nodes_can_board));
+        capnp_pattern.set_nodes_can_unboard(&flags_to_bitset(&self.nodes_can_unboard));
+        Ok(())
+    }
+
+    pub fn read(capnp_pattern: trip_pattern::Reader) -> Result<Self, capnp::Error> {
+        let travel_times_seconds : Vec<i32> = capnp_pattern.get_travel_times_seconds()?.iter().collect();
+        let nodes_count = travel_times_seconds.len();
+        Ok(TripPattern {
+            path_uuid: capnp_pattern.get_path_uuid()?.to_string()?,
+            travel_times_seconds,
+            dwell_times_seconds: capnp_pattern.get_dwell_times_seconds()?.iter().collect(),
+            nodes_can_board: bitset_to_flags(capnp_pattern.get_nodes_can_board()?, nodes_count),
+            nodes_can_unboard: bitset_to_flags(capnp_pattern.get_nodes_can_unboard()?, nodes_count),
+        })
+    }
+}
+
+/// Distinct patterns of the trips written in the same file, numbered from 1
+/// in the order they are first found
+#[derive(Debug, Default)]
+pub struct TripPatterns {
+    patterns: Vec<TripPattern>,
+    ids: HashMap<TripPattern, u32>,
+}
+
+impl TripPatterns {
+
+    /// Id of the pattern of a trip, 0 if the trip cannot use a pattern
+    pub fn pattern_id(&mut self, trip: &serde_json::Value) -> u32 {
+        let pattern = match TripPattern::from_trip_json(trip) {
+            Some(pattern) => pattern,
+            None => return 0
+        };
+        if let Some(id) = self.ids.get(&pattern) {
+            return *id;
+        }
+        self.patterns.push(pattern.clone());
+        let id = self.patterns.len() as u32;
+        self.ids.insert(pattern, id);
+        id
+    }
+
+    pub fn len(&self) -> usize {
+        self.patterns.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.patterns.is_empty()
+    }
+
+    pub fn write(&self, mut capnp_patterns: ::capnp::struct_list::Builder<'_, trip_pattern::Owned>) -> Result<(), capnp::Error> {
+        for (i, pattern) in self.patterns.iter().enumerate() {
+            pattern.write(capnp_patterns.reborrow().get(i as u32))?;
+        }
+        Ok(())
+    }
+
+    pub fn read(capnp_patterns: ::capnp::struct_list::Reader<'_, trip_pattern::Owned>) -> Result<Vec<TripPattern>, capnp::Error> {
+        capnp_patterns.iter().map(TripPattern::read).collect()
+    }
+}
@@ -0,0 +1,94 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+use crate::vehicleTypeCollection_capnp::vehicle_type_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    json_boolean_to_i8,
+    empty_str_to_json_null,
+    i8_to_json_boolean,
+    json_value_or_null_to_i64_or_minus_one,
+    minus_one_i64_to_null,
+    json_value_or_null_to_thousandths_or_minus_one,
+    minus_one_thousandths_to_null
+};
+
+fn json_cost_to_cents_or_minus_one(input: &serde_json::Value) -> i32 {
+    input.as_f64().map_or(-1, |cost| (cost * 100.0).round() as i32)
+}
+
+fn minus_one_cents_to_null(input: i32) -> serde_json::Value {
+    if input < 0 { json!(null) } else { json!(input as f64 / 100.0) }
+}
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    let json_objects = json["vehicle_types"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Vehicle types are missing or not an array")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_vehicle_types(json_objects.len() as u32);
+
+    for (i, json_data) in json_objects.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        capnp_data.set_uuid(required_string(json_data.get("id")));
+        capnp_data.set_internal_id(optional_string(json_data.get("internal_id")));
+        capnp_data.set_name(optional_string(json_data.get("name")));
+        capnp_data.set_mode(optional_string(json_data.get("mode")));
+        capnp_data.set_seated_capacity(json_value_or_null_to_i64_or_minus_one(&json_data["seated_capacity"]) as i16);
+        capnp_data.set_standing_capacity(json_value_or_null_to_i64_or_minus_one(&json_data["standing_capacity"]) as i16);
+        capnp_data.set_length_meters(json_value_or_null_to_thousandths_or_minus_one(&json_data["length_meters"]));
+        capnp_data.set_energy_type(optional_string(json_data.get("energy_type")));
+        capnp_data.set_cost_per_km_cents(json_cost_to_cents_or_minus_one(&json_data["cost_per_km"]));
+        capnp_data.set_cost_per_hour_cents(json_cost_to_cents_or_minus_one(&json_data["cost_per_hour"]));
+        capnp_data.set_description(optional_string(json_data.get("description")));
+        capnp_data.set_data(json_data.get("data").unwrap_or(&json!({})).to_string().as_str());
+        capnp_data.set_is_frozen(json_boolean_to_i8(json_data.get("is_frozen").unwrap_or(&json!(null))));
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_vehicle_types()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_vehicle_types()?.iter() {
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+        collection_json_vec.push(json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+            "name": empty_str_to_json_null(capnp_object.get_name()?.to_str()?),
+            "mode": empty_str_to_json_null(capnp_object.get_mode()?.to_str()?),
+            "seated_capacity": minus_one_i64_to_null(capnp_object.get_seated_capacity() as i64),
+            "standing_capacity": minus_one_i64_to_null(capnp_object.get_standing_capacity() as i64),
+            "length_meters": minus_one_thousandths_to_null(capnp_object.get_length_meters()),
+            "energy_type": empty_str_to_json_null(capnp_object.get_energy_type()?.to_str()?),
+            "cost_per_km": minus_one_cents_to_null(capnp_object.get_cost_per_km_cents()),
+            "cost_per_hour": minus_one_cents_to_null(capnp_object.get_cost_per_hour_cents()),
+            "description": empty_str_to_json_null(capnp_object.get_description()?.to_str()?),
+            "is_frozen": i8_to_json_boolean(capnp_object.get_is_frozen()),
+            "data": data_attributes
+        }));
+    }
+
+    Ok(json!({
+        "vehicle_types": serde_json::Value::Array(collection_json_vec)
+    }))
+}
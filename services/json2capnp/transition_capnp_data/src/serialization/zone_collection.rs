@@ -0,0 +1,110 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Analysis zones (traffic analysis zones, census areas) with their polygon geography */
+
+use crate::zoneCollection_capnp::zone_collection as collection;
+use capnp::serialize_packed;
+use std::io::BufReader;
+use protobuf::Message;
+use crate::utils::{
+    required_string,
+    optional_string_json_null_to_empty_str as optional_string,
+    empty_str_to_json_null
+};
+
+pub fn write_collection(
+    json: &serde_json::Value,
+    file: &mut dyn std::io::Write,
+) -> ::std::result::Result<(), capnp::Error> {
+    let mut message = ::capnp::message::Builder::new_default();
+
+    if json["zones"]["type"] != "FeatureCollection" {
+        return Err(capnp::Error::failed(String::from("Zones geojson is invalid, empty or not a FeatureCollection")));
+    }
+    let features = json["zones"]["features"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Zones geojson has no features")))?;
+
+    let collection_capnp = message.init_root::<collection::Builder>();
+    let mut capnp = collection_capnp.init_zones(features.len() as u32);
+    for (i, feature) in features.iter().enumerate() {
+        let mut capnp_data = capnp.reborrow().get(i as u32);
+        let properties = &feature["properties"];
+        let uuid = required_string(properties.get("id"));
+
+        let geometry = &feature["geometry"];
+        if geometry["type"] != "Polygon" && geometry["type"] != "MultiPolygon" {
+            return Err(capnp::Error::failed(format!("Zone {} geometry is not a Polygon or MultiPolygon", uuid)));
+        }
+        let geojson_json = json!({
+            "type": "Feature",
+            "properties": {},
+            "geometry": geometry
+        });
+        let geobuf = geobuf::encode::Encoder::encode(&geojson_json, 6, 2)
+            .map_err(|e| capnp::Error::failed(format!("Cannot encode zone {} geometry: {}", uuid, e)))?
+            .write_to_bytes()
+            .map_err(|e| capnp::Error::failed(format!("Cannot encode zone {} geometry: {}", uuid, e)))?;
+
+        capnp_data.set_uuid(uuid); // required
+        capnp_data.set_id(properties["integer_id"].as_u64().unwrap_or(0) as u32);
+        capnp_data.set_internal_id(optional_string(properties.get("internal_id")));
+        capnp_data.set_shortname(optional_string(properties.get("shortname")));
+        capnp_data.set_name(optional_string(properties.get("name")));
+        capnp_data.set_data_source_uuid(optional_string(properties.get("data_source_id")));
+        capnp_data.set_data(properties.get("data").unwrap_or(&json!({})).to_string().as_str());
+        capnp_data.set_geography(&geobuf);
+    }
+
+    serialize_packed::write_message(file, &message)
+}
+
+
+pub fn read_collection(
+    file: &mut dyn std::io::Read,
+) -> ::std::result::Result<serde_json::Value, capnp::Error> {
+
+    let message_reader   = serialize_packed::read_message(BufReader::new(file), ::capnp::message::ReaderOptions::new())?;
+    let capnp_collection = message_reader.get_root::<collection::Reader>()?;
+
+    let mut collection_json_vec : Vec<serde_json::Value> = Vec::with_capacity(capnp_collection.get_zones()?.len() as usize);
+
+    for capnp_object in capnp_collection.get_zones()?.iter() {
+
+        let integer_id = capnp_object.get_id();
+        let data_attributes : serde_json::Value = serde_json::from_str(capnp_object.get_data()?.to_str()?).unwrap_or(json!({}));
+
+        let mut geobuf_data = geobuf::geobuf_pb::Data::new();
+        geobuf_data.merge_from_bytes(capnp_object.get_geography()?).map_err(|e| capnp::Error::failed(format!("Cannot decode zone geometry: {}", e)))?;
+        let mut geojson : serde_json::Value = geobuf::decode::Decoder::decode(&geobuf_data).unwrap_or(json!({
+            "type": "Feature",
+            "geometry": null
+        }));
+
+        geojson["id"] = json!(integer_id);
+        geojson["properties"] = json!({
+            "id": capnp_object.get_uuid()?.to_str()?,
+            "integer_id": integer_id,
+            "internal_id": empty_str_to_json_null(capnp_object.get_internal_id()?.to_str()?),
+            "shortname": empty_str_to_json_null(capnp_object.get_shortname()?.to_str()?),
+            "name": empty_str_to_json_null(capnp_object.get_name()?.to_str()?),
+            "data_source_id": empty_str_to_json_null(capnp_object.get_data_source_uuid()?.to_str()?),
+            "data": data_attributes
+        });
+
+        collection_json_vec.push(geojson);
+
+    }
+
+    Ok(json!({
+        "zones": {
+            "type": "FeatureCollection",
+            "features": serde_json::Value::Array(collection_json_vec)
+        }
+    }))
+
+}
@@ -0,0 +1,167 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Interpret the weekdays and dates of the services (serviceCollection.Service) */
+
+use std::collections::BTreeSet;
+use chrono::{Datelike, NaiveDate};
+
+const WEEKDAYS: [&str; 7] = ["monday", "tuesday", "wednesday", "thursday", "friday", "saturday", "sunday"];
+
+/// Longest date range of the queries, the ranges come from requests and the
+/// overlaps compare every pair of services on every date
+pub const MAX_RANGE_DAYS: i64 = 5 * 366;
+
+/// Parse a YYYY-MM-DD date, as saved in the services
+pub fn parse_date(date_str: &str) -> Result<NaiveDate, capnp::Error> {
+    // chrono accepts years and months without padding, but the services don't
+    let date_format_is_valid = date_str.len() == 10 && date_str.char_indices().all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+    match NaiveDate::parse_from_str(date_str, "%Y-%m-%d") {
+        Ok(date) if date_format_is_valid => Ok(date),
+        _ => Err(capnp::Error::failed(format!("Invalid date {}, expected YYYY-MM-DD", date_str)))
+    }
+}
+
+pub fn format_date(date: &NaiveDate) -> String {
+    date.format("%Y-%m-%d").to_string()
+}
+
+fn dates_between(start_date: NaiveDate, end_date: NaiveDate) -> Result<impl Iterator<Item = NaiveDate>, capnp::Error> {
+    let days_count = (end_date - start_date).num_days().max(-1) + 1;
+    if days_count > MAX_RANGE_DAYS {
+        return Err(capnp::Error::failed(format!(
+            "Date range from {} to {} is too long, the maximum is {} days", format_date(&start_date), format_date(&end_date), MAX_RANGE_DAYS
+        )));
+    }
+    Ok((0..days_count).map(move |day| start_date + chrono::Duration::days(day)))
+}
+
+#[derive(Debug, Clone)]
+pub struct ServiceCalendar {
+    pub uuid: String,
+    // from monday to sunday
+    pub weekdays: [bool; 7],
+    // a missing start or end date leaves the period open on that side
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub only_dates: BTreeSet<NaiveDate>,
+    pub except_dates: BTreeSet<NaiveDate>,
+}
+
+impl ServiceCalendar {
+
+    /// Parse a service json, as returned by service_collection::read_collection.
+    /// Every invalid date of the service is reported in the error.
+    pub fn from_json(service_json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        let uuid = service_json["id"].as_str().unwrap_or("").to_string();
+        let mut errors : Vec<String> = Vec::new();
+
+        let mut optional_date = |attribute: &str| -> Option<NaiveDate> {
+            match service_json[attribute].as_str() {
+                None | Some("") => None,
+                Some(date_str) => parse_date(date_str).map_err(|e| errors.push(format!("{}: {}", attribute, e.extra))).ok()
+            }
+        };
+        let start_date = optional_date("start_date");
+        let end_date = optional_date("end_date");
+
+        let mut dates = |attribute: &str| -> BTreeSet<NaiveDate> {
+            service_json[attribute].as_array().map(|dates| dates.iter().filter_map(|date| {
+                parse_date(date.as_str().unwrap_or("")).map_err(|e| errors.push(format!("{}: {}", attribute, e.extra))).ok()
+            }).collect()).unwrap_or_default()
+        };
+        let only_dates = dates("only_dates");
+        let except_dates = dates("except_dates");
+
+        if let (Some(start_date), Some(end_date)) = (start_date, end_date) {
+            if start_date > end_date {
+                errors.push(format!("start_date {} is after end_date {}", format_date(&start_date), format_date(&end_date)));
+            }
+        }
+
+        if !errors.is_empty() {
+            return Err(capnp::Error::failed(format!("Service {} has invalid dates: {}", uuid, errors.join(", "))));
+        }
+
+        let mut weekdays = [false; 7];
+        for (i, weekday) in WEEKDAYS.iter().enumerate() {
+            weekdays[i] = service_json[*weekday].as_bool().unwrap_or(false);
+        }
+
+        Ok(ServiceCalendar { uuid, weekdays, start_date, end_date, only_dates, except_dates })
+    }
+
+    /// Like in GTFS calendars, the except dates are removed from the service
+    /// weekdays within its period, and the only dates are added to them. As
+    /// in Service.isValidForDate, the service never runs outside its period,
+    /// even on its only dates.
+    pub fn runs_on(&self, date: &NaiveDate) -> bool {
+        let in_period = self.start_date.is_none_or(|start_date| start_date <= *date) && self.end_date.is_none_or(|end_date| *date <= end_date);
+        if !in_period || self.except_dates.contains(date) {
+            return false;
+        }
+        self.only_dates.contains(date) || self.weekdays[date.weekday().num_days_from_monday() as usize]
+    }
+
+    /// Dates on which the service runs, between two dates inclusively
+    pub fn dates_in_range(&self, start_date: NaiveDate, end_date: NaiveDate) -> Result<Vec<NaiveDate>, capnp::Error> {
+        Ok(dates_between(start_date, end_date)?.filter(|date| self.runs_on(date)).collect())
+    }
+}
+
+/// Parse all the services of the json returned by service_collection::read_collection
+pub fn calendars_from_collection_json(json: &serde_json::Value) -> Result<Vec<ServiceCalendar>, capnp::Error> {
+    json["services"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Services json is not an array")))?
+        .iter().map(ServiceCalendar::from_json).collect()
+}
+
+/// Validate the dates of every service, returning the errors by service uuid
+pub fn validate_collection_json(json: &serde_json::Value) -> serde_json::Value {
+    let invalid_services : Vec<serde_json::Value> = json["services"].as_array().map(|services| services.iter().filter_map(|service_json| {
+        ServiceCalendar::from_json(service_json).err().map(|error| json!({
+            "id": service_json["id"],
+            "error": error.extra
+        }))
+    }).collect()).unwrap_or_default();
+    json!({
+        "valid": invalid_services.is_empty(),
+        "invalid_services": invalid_services
+    })
+}
+
+pub fn services_on_date<'a>(calendars: &'a [ServiceCalendar], date: &NaiveDate) -> Vec<&'a str> {
+    calendars.iter().filter(|calendar| calendar.runs_on(date)).map(|calendar| calendar.uuid.as_str()).collect()
+}
+
+/// Pairs of services running on at least one common date between two dates
+/// inclusively, with the number of common dates and the first one
+pub fn overlapping_services(calendars: &[ServiceCalendar], start_date: NaiveDate, end_date: NaiveDate) -> Result<serde_json::Value, capnp::Error> {
+    let dates : Vec<NaiveDate> = dates_between(start_date, end_date)?.collect();
+    let running_dates : Vec<Vec<bool>> = calendars.iter().map(|calendar| dates.iter().map(|date| calendar.runs_on(date)).collect()).collect();
+
+    let mut overlaps : Vec<serde_json::Value> = Vec::new();
+    for i in 0..calendars.len() {
+        for j in (i + 1)..calendars.len() {
+            let common_dates : Vec<&NaiveDate> = dates.iter().enumerate().filter(|(d, _)| running_dates[i][*d] && running_dates[j][*d]).map(|(_, date)| date).collect();
+            if let Some(first_date) = common_dates.first() {
+                overlaps.push(json!({
+                    "services_ids": [calendars[i].uuid, calendars[j].uuid],
+                    "common_dates_count": common_dates.len(),
+                    "first_common_date": format_date(first_date)
+                }));
+            }
+        }
+    }
+    Ok(json!({
+        "overlaps": overlaps
+    }))
+}
+
+pub fn dates_to_json(dates: &[NaiveDate]) -> serde_json::Value {
+    json!(dates.iter().map(format_date).collect::<Vec<String>>())
+}
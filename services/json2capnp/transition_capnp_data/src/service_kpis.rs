@@ -0,0 +1,254 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Supply indicators of the line schedules: trips, vehicle-km, vehicle-hours and headways */
+
+use std::collections::{BTreeMap, HashMap};
+use crate::cache::read_collection_file;
+use crate::path_analytics::{json_coordinates, line_length_meters};
+use crate::scenario_resolution::resolve_scenario;
+use crate::serialization::{line, path_collection};
+use crate::vehicle_types::VehicleTypes;
+
+const CSV_HEADER: [&str; 16] = [
+    "level", "line_id", "line_shortname", "service_id", "period_shortname",
+    "trips_count", "vehicle_hours", "vehicle_km", "average_headway_seconds", "max_headway_seconds",
+    "first_departure_seconds", "last_arrival_seconds", "span_of_service_seconds",
+    "seated_capacity", "total_capacity", "trips_without_path_count",
+];
+
+#[derive(Debug, Clone, Default)]
+pub struct ServiceKpis {
+    pub trips_count: usize,
+    pub vehicle_hours: f64,
+    pub vehicle_km: f64,
+    // between consecutive departures on the same path, None with less than 2
+    // trips per path. Like the span, only computed for the trips of a service.
+    pub average_headway_seconds: Option<f64>,
+    pub max_headway_seconds: Option<i64>,
+    pub first_departure_seconds: Option<i64>,
+    pub last_arrival_seconds: Option<i64>,
+    pub seated_capacity: i64,
+    pub total_capacity: i64,
+    // trips on paths missing from the cache are not counted in the vehicle-km
+    pub trips_without_path_count: usize,
+}
+
+impl ServiceKpis {
+
+    /// Compute the indicators of a set of trips, in the format of the line
+    /// objects, using the path lengths in meters by path uuid
+    pub fn from_trips(trips: &[&serde_json::Value], path_lengths_meters: &HashMap<String, f64>) -> Self {
+        let mut kpis = ServiceKpis { trips_count: trips.len(), ..Default::default() };
+        let mut departures_by_path : HashMap<&str, Vec<i64>> = HashMap::new();
+
+        for trip in trips {
+            let path_uuid = trip["path_id"].as_str().unwrap_or("");
+            let departure_time = trip["departure_time_seconds"].as_i64();
+            let arrival_time = trip["arrival_time_seconds"].as_i64();
+            if let (Some(departure_time), Some(arrival_time)) = (departure_time, arrival_time) {
+                kpis.vehicle_hours += (arrival_time - departure_time) as f64 / 3600.0;
+            }
+            if let Some(departure_time) = departure_time {
+                kpis.first_departure_seconds = Some(kpis.first_departure_seconds.map_or(departure_time, |first| first.min(departure_time)));
+                departures_by_path.entry(path_uuid).or_default().push(departure_time);
+            }
+            if let Some(arrival_time) = arrival_time {
+                kpis.last_arrival_seconds = Some(kpis.last_arrival_seconds.map_or(arrival_time, |last| last.max(arrival_time)));
+            }
+            match path_lengths_meters.get(path_uuid) {
+                Some(length_meters) => kpis.vehicle_km += length_meters / 1000.0,
+                None => kpis.trips_without_path_count += 1
+            }
+            kpis.seated_capacity += trip["seated_capacity"].as_i64().unwrap_or(0);
+            kpis.total_capacity += trip["total_capacity"].as_i64().unwrap_or(0);
+        }
+
+        let mut headways : Vec<i64> = Vec::new();
+        for departures in departures_by_path.values_mut() {
+            departures.sort_unstable();
+            headways.extend(departures.windows(2).map(|pair| pair[1] - pair[0]));
+        }
+        if !headways.is_empty() {
+            kpis.average_headway_seconds = Some(headways.iter().sum::<i64>() as f64 / headways.len() as f64);
+            kpis.max_headway_seconds = headways.iter().max().copied();
+        }
+        kpis
+    }
+
+    /// Compute the indicators of the trips of several services. The trips of
+    /// different services do not run on the same days, so the headways and
+    /// the span are only computed when there is a single service.
+    pub fn from_services_trips(trips_by_service: &[Vec<&serde_json::Value>], path_lengths_meters: &HashMap<String, f64>) -> Self {
+        if trips_by_service.len() == 1 {
+            return ServiceKpis::from_trips(&trips_by_service[0], path_lengths_meters);
+        }
+        let trips : Vec<&serde_json::Value> = trips_by_service.iter().flatten().copied().collect();
+        ServiceKpis {
+            average_headway_seconds: None,
+            max_headway_seconds: None,
+            first_departure_seconds: None,
+            last_arrival_seconds: None,
+            ..ServiceKpis::from_trips(&trips, path_lengths_meters)
+        }
+    }
+
+    pub fn span_of_service_seconds(&self) -> Option<i64> {
+        match (self.first_departure_seconds, self.last_arrival_seconds) {
+            (Some(first_departure), Some(last_arrival)) => Some(last_arrival - first_departure),
+            _ => None
+        }
+    }
+
+    pub fn to_json(&self) -> serde_json::Value {
+        json!({
+            "trips_count": self.trips_count,
+            "vehicle_hours": self.vehicle_hours,
+            "vehicle_km": self.vehicle_km,
+            "average_headway_seconds": self.average_headway_seconds,
+            "max_headway_seconds": self.max_headway_seconds,
+            "first_departure_seconds": self.first_departure_seconds,
+            "last_arrival_seconds": self.last_arrival_seconds,
+            "span_of_service_seconds": self.span_of_service_seconds(),
+            "seated_capacity": self.seated_capacity,
+            "total_capacity": self.total_capacity,
+            "trips_without_path_count": self.trips_without_path_count
+        })
+    }
+}
+
+/// Length of the path geographies of the cache, by path uuid
+pub fn read_path_lengths_meters(cache_directory_path: &str) -> Result<HashMap<String, f64>, capnp::Error> {
+    let paths_json = read_collection_file(cache_directory_path, "paths", &path_collection::read_collection)?;
+    Ok(path_lengths_meters(paths_json["paths"]["features"].as_array().unwrap()))
+}
+
+fn path_lengths_meters(path_features: &[serde_json::Value]) -> HashMap<String, f64> {
+    path_features.iter().filter_map(|path| {
+        path["properties"]["id"].as_str().map(|path_uuid| (path_uuid.to_string(), line_length_meters(&json_coordinates(&path["geometry"]))))
+    }).collect()
+}
+
+fn schedule_periods(schedule: &serde_json::Value) -> &[serde_json::Value] {
+    schedule["periods"].as_array().map(|periods| periods.as_slice()).unwrap_or(&[])
+}
+
+fn period_trips(period: &serde_json::Value) -> Vec<&serde_json::Value> {
+    period["trips"].as_array().map(|trips| trips.iter().collect()).unwrap_or_default()
+}
+
+/// Indicators of a line, as returned in the "line" attribute of
+/// line::read_object, by service and by period. The headways and span of the
+/// line are only given when it has a single service.
+pub fn line_kpis(line_json: &serde_json::Value, path_lengths_meters: &HashMap<String, f64>) -> serde_json::Value {
+    let mut schedules : Vec<&serde_json::Value> = line_json["scheduleByServiceId"].as_object().map(|schedules| schedules.values().collect()).unwrap_or_default();
+    schedules.sort_by_key(|schedule| schedule["service_id"].as_str().unwrap_or(""));
+
+    let mut line_trips_by_service : Vec<Vec<&serde_json::Value>> = Vec::new();
+    let mut services : Vec<serde_json::Value> = Vec::new();
+    for schedule in schedules {
+        let mut service_trips : Vec<&serde_json::Value> = Vec::new();
+        let mut periods : Vec<serde_json::Value> = Vec::new();
+        for period in schedule_periods(schedule) {
+            let trips = period_trips(period);
+            let mut period_kpis = ServiceKpis::from_trips(&trips, path_lengths_meters).to_json();
+            period_kpis["period_shortname"] = period["period_shortname"].clone();
+            period_kpis["start_at_hour"] = period["start_at_hour"].clone();
+            period_kpis["end_at_hour"] = period["end_at_hour"].clone();
+            periods.push(period_kpis);
+            service_trips.extend(trips);
+        }
+        services.push(json!({
+            "service_id": schedule["service_id"],
+            "kpis": ServiceKpis::from_trips(&service_trips, path_lengths_meters).to_json(),
+            "periods": periods
+        }));
+        line_trips_by_service.push(service_trips);
+    }
+
+    json!({
+        "line_id": line_json["id"],
+        "line_shortname": line_json["shortname"],
+        "kpis": ServiceKpis::from_services_trips(&line_trips_by_service, path_lengths_meters).to_json(),
+        "services": services
+    })
+}
+
+/// Indicators of a line object saved in the `lines` subdirectory of the cache.
+/// Trips without explicit capacity get the one of their vehicle type.
+pub fn cached_line_kpis(cache_directory_path: &str, line_uuid: &str) -> Result<serde_json::Value, capnp::Error> {
+    let path_lengths_meters = read_path_lengths_meters(cache_directory_path)?;
+    let vehicle_types = VehicleTypes::load(cache_directory_path)?;
+    let mut line_object_json = line::read_object(&line_uuid.to_string(), &format!("{}/lines", cache_directory_path))?;
+    vehicle_types.resolve_line_capacities(&mut line_object_json["line"]);
+    Ok(line_kpis(&line_object_json["line"], &path_lengths_meters))
+}
+
+/// Indicators of the lines, services and periods effective in a scenario,
+/// after applying its filters, and their total for the whole scenario, with
+/// headways and span only when the scenario has a single service.
+/// Trips without explicit capacity get the one of their vehicle type.
+pub fn scenario_kpis(cache_directory_path: &str, scenario_uuid: &str) -> Result<serde_json::Value, capnp::Error> {
+    let mut resolved_scenario = resolve_scenario(cache_directory_path, scenario_uuid)?;
+    let path_lengths_meters = read_path_lengths_meters(cache_directory_path)?;
+    let vehicle_types = VehicleTypes::load(cache_directory_path)?;
+    for line_json in resolved_scenario.lines.iter_mut() {
+        vehicle_types.resolve_line_capacities(line_json);
+    }
+
+    let mut scenario_trips_by_service : BTreeMap<&str, Vec<&serde_json::Value>> = BTreeMap::new();
+    for line_json in resolved_scenario.lines.iter() {
+        for schedule in line_json["scheduleByServiceId"].as_object().map(|schedules| schedules.values().collect()).unwrap_or_else(Vec::new) {
+            let service_trips = scenario_trips_by_service.entry(schedule["service_id"].as_str().unwrap_or("")).or_default();
+            for period in schedule_periods(schedule) {
+                service_trips.extend(period_trips(period));
+            }
+        }
+    }
+    let scenario_trips_by_service : Vec<Vec<&serde_json::Value>> = scenario_trips_by_service.into_values().collect();
+
+    Ok(json!({
+        "scenario_id": scenario_uuid,
+        "kpis": ServiceKpis::from_services_trips(&scenario_trips_by_service, &path_lengths_meters).to_json(),
+        "lines": resolved_scenario.lines.iter().map(|line_json| line_kpis(line_json, &path_lengths_meters)).collect::<Vec<serde_json::Value>>()
+    }))
+}
+
+fn csv_row(level: &str, line_json: &serde_json::Value, service_id: &serde_json::Value, period_shortname: &serde_json::Value, kpis: &serde_json::Value) -> Vec<String> {
+    let cell = |value: &serde_json::Value| match value {
+        serde_json::Value::Null => String::new(),
+        serde_json::Value::String(value) => value.clone(),
+        value => value.to_string()
+    };
+    let mut row = vec![level.to_string(), cell(&line_json["line_id"]), cell(&line_json["line_shortname"]), cell(service_id), cell(period_shortname)];
+    row.extend(CSV_HEADER[5..].iter().map(|attribute| cell(&kpis[*attribute])));
+    row
+}
+
+/// Flatten the json returned by scenario_kpis to csv, with one row per
+/// period, service, line and for the scenario, identified by the level column
+pub fn scenario_kpis_to_csv(scenario_kpis_json: &serde_json::Value) -> Result<String, capnp::Error> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let mut write_row = |row: Vec<String>| writer.write_record(&row).map_err(|e| capnp::Error::failed(e.to_string()));
+
+    write_row(CSV_HEADER.iter().map(|header| header.to_string()).collect())?;
+    let null = json!(null);
+    for line_json in scenario_kpis_json["lines"].as_array().unwrap_or(&Vec::new()) {
+        for service_json in line_json["services"].as_array().unwrap_or(&Vec::new()) {
+            for period_json in service_json["periods"].as_array().unwrap_or(&Vec::new()) {
+                write_row(csv_row("period", line_json, &service_json["service_id"], &period_json["period_shortname"], period_json))?;
+            }
+            write_row(csv_row("service", line_json, &service_json["service_id"], &null, &service_json["kpis"]))?;
+        }
+        write_row(csv_row("line", line_json, &null, &null, &line_json["kpis"]))?;
+    }
+    write_row(csv_row("scenario", &null, &null, &null, &scenario_kpis_json["kpis"]))?;
+
+    let bytes = writer.into_inner().map_err(|e| capnp::Error::failed(e.to_string()))?;
+    String::from_utf8(bytes).map_err(|e| capnp::Error::failed(e.to_string()))
+}
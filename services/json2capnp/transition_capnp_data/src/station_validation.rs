@@ -0,0 +1,74 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Consistency between the nodes station_id and the stations nodes */
+
+use std::collections::{HashMap, HashSet};
+use crate::cache::read_collection_file;
+use crate::serialization::{node_collection, station_collection};
+
+fn feature_properties(collection: &serde_json::Value) -> Vec<&serde_json::Value> {
+    collection["features"].as_array().map(|features| features.iter().map(|feature| &feature["properties"]).collect()).unwrap_or_default()
+}
+
+/// Check that the station of every node exists and lists the node, and that
+/// every node of a station exists and references that station. Nodes
+/// without a station are ignored.
+pub fn validate_stations(nodes: &serde_json::Value, stations: &serde_json::Value) -> serde_json::Value {
+    let mut errors : Vec<String> = Vec::new();
+
+    let mut node_station_by_uuid : HashMap<&str, Option<&str>> = HashMap::new();
+    for node in feature_properties(nodes) {
+        node_station_by_uuid.insert(node["id"].as_str().unwrap_or(""), node["station_id"].as_str().filter(|station_uuid| !station_uuid.is_empty()));
+    }
+    let mut station_nodes_by_uuid : HashMap<&str, HashSet<&str>> = HashMap::new();
+    for station in feature_properties(stations) {
+        let station_uuid = station["id"].as_str().unwrap_or("");
+        let station_nodes = station_nodes_by_uuid.entry(station_uuid).or_default();
+        for node_uuid in station["nodes"].as_array().map(|nodes| nodes.iter().filter_map(|node_uuid| node_uuid.as_str()).collect::<Vec<&str>>()).unwrap_or_default() {
+            station_nodes.insert(node_uuid);
+            match node_station_by_uuid.get(node_uuid) {
+                None => errors.push(format!("Station {} has unknown node {}", station_uuid, node_uuid)),
+                Some(node_station_uuid) if *node_station_uuid != Some(station_uuid) => {
+                    errors.push(format!("Station {} has node {} which references station {}", station_uuid, node_uuid, node_station_uuid.unwrap_or("null")))
+                },
+                _ => ()
+            }
+        }
+    }
+
+    let mut unresolved_nodes : Vec<&str> = Vec::new();
+    for node in feature_properties(nodes) {
+        let node_uuid = node["id"].as_str().unwrap_or("");
+        if let Some(Some(station_uuid)) = node_station_by_uuid.get(node_uuid) {
+            match station_nodes_by_uuid.get(station_uuid) {
+                None => {
+                    errors.push(format!("Node {} references unknown station {}", node_uuid, station_uuid));
+                    unresolved_nodes.push(node_uuid);
+                },
+                Some(station_nodes) if !station_nodes.contains(node_uuid) => errors.push(format!("Node {} references station {} which does not list it", node_uuid, station_uuid)),
+                _ => ()
+            }
+        }
+    }
+
+    json!({
+        "valid": errors.is_empty(),
+        "errors": errors,
+        "stations_count": station_nodes_by_uuid.len(),
+        "nodes_with_station_count": node_station_by_uuid.values().filter(|station_uuid| station_uuid.is_some()).count(),
+        "nodes_with_unknown_station": unresolved_nodes
+    })
+}
+
+/// Check the stations of the nodes in a cache directory
+pub fn validate_station_collection(cache_directory_path: &str) -> Result<serde_json::Value, capnp::Error> {
+    let nodes_json = read_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?;
+    let stations_json = read_collection_file(cache_directory_path, "stations", &station_collection::read_collection)?;
+    Ok(validate_stations(&nodes_json["nodes"], &stations_json["stations"]))
+}
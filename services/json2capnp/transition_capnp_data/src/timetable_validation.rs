@@ -0,0 +1,175 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Check the trip timetables of the line objects against the path nodes */
+
+use std::collections::HashMap;
+use std::path::Path;
+use crate::cache::read_collection_file;
+use crate::serialization::{line, path_collection};
+
+const TRIP_ARRAYS: [&str; 4] = ["node_arrival_times_seconds", "node_departure_times_seconds", "nodes_can_board", "nodes_can_unboard"];
+
+/// Number of nodes of each path of the cache, by path uuid
+pub fn read_paths_nodes_counts(cache_directory_path: &str) -> Result<HashMap<String, usize>, capnp::Error> {
+    let paths_json = read_collection_file(cache_directory_path, "paths", &path_collection::read_collection)?;
+    Ok(paths_json["paths"]["features"].as_array().unwrap().iter().filter_map(|path| {
+        let properties = &path["properties"];
+        properties["id"].as_str().map(|path_uuid| (path_uuid.to_string(), properties["nodes"].as_array().map(|nodes| nodes.len()).unwrap_or(0)))
+    }).collect())
+}
+
+// null and -1 both mean no time, -1 is how the line objects save it
+fn optional_time(time: &serde_json::Value) -> Option<i64> {
+    time.as_i64().filter(|time| *time >= 0)
+}
+
+/// Check a trip against the number of nodes of its path, None if the path
+/// is unknown. The node arrays must have one value per path node, the first
+/// node has no arrival and the last one no departure, passengers cannot
+/// unboard at the first node nor board at the last one, and the times never
+/// decrease along the trip.
+pub fn validate_trip(trip_json: &serde_json::Value, path_nodes_count: Option<usize>) -> Vec<String> {
+    let mut errors : Vec<String> = Vec::new();
+    let nodes_count = match path_nodes_count {
+        Some(nodes_count) => nodes_count,
+        None => {
+            errors.push(format!("Unknown path {}", trip_json["path_id"]));
+            return errors;
+        }
+    };
+
+    let mut arrays_are_valid = true;
+    for attribute in TRIP_ARRAYS.iter() {
+        let length = trip_json[*attribute].as_array().map(|values| values.len()).unwrap_or(0);
+        if length != nodes_count {
+            errors.push(format!("{} has {} values for {} path nodes", attribute, length, nodes_count));
+            arrays_are_valid = false;
+        }
+    }
+    if !arrays_are_valid || nodes_count == 0 {
+        return errors;
+    }
+
+    let arrival_times : Vec<Option<i64>> = trip_json["node_arrival_times_seconds"].as_array().unwrap().iter().map(optional_time).collect();
+    let departure_times : Vec<Option<i64>> = trip_json["node_departure_times_seconds"].as_array().unwrap().iter().map(optional_time).collect();
+    let can_board = &trip_json["nodes_can_board"];
+    let can_unboard = &trip_json["nodes_can_unboard"];
+    let last = nodes_count - 1;
+
+    if arrival_times[0].is_some() {
+        errors.push(String::from("First node has an arrival time"));
+    }
+    if departure_times[last].is_some() {
+        errors.push(String::from("Last node has a departure time"));
+    }
+    if can_unboard[0] == json!(true) {
+        errors.push(String::from("First node allows unboarding"));
+    }
+    if can_board[last] == json!(true) {
+        errors.push(String::from("Last node allows boarding"));
+    }
+
+    let mut previous_time : Option<(i64, usize)> = None;
+    for i in 0..nodes_count {
+        if i > 0 && arrival_times[i].is_none() {
+            errors.push(format!("Node {} has no arrival time", i));
+        }
+        if i < last && departure_times[i].is_none() {
+            errors.push(format!("Node {} has no departure time", i));
+        }
+        if let (Some(arrival_time), Some(departure_time)) = (arrival_times[i], departure_times[i]) {
+            if departure_time < arrival_time {
+                errors.push(format!("Node {} departure time {} is before its arrival time {}", i, departure_time, arrival_time));
+            }
+        }
+        if let Some(arrival_time) = arrival_times[i] {
+            if let Some((previous_time, previous_node)) = previous_time.filter(|(previous_time, _)| arrival_time < *previous_time) {
+                errors.push(format!("Node {} arrival time {} is before node {} departure time {}", i, arrival_time, previous_node, previous_time));
+            }
+        }
+        if let Some(departure_time) = departure_times[i] {
+            previous_time = Some((departure_time, i));
+        }
+    }
+
+    errors
+}
+
+/// Validate every trip of a line object, as returned by line::read_object,
+/// and report the invalid ones with their index in their period.
+pub fn validate_line_object(line_object_json: &serde_json::Value, paths_nodes_counts: &HashMap<String, usize>) -> serde_json::Value {
+    let line_json = &line_object_json["line"];
+    let mut invalid_trips : Vec<serde_json::Value> = Vec::new();
+    let mut trips_count : usize = 0;
+
+    let mut schedules : Vec<&serde_json::Value> = line_json["scheduleByServiceId"].as_object().map(|schedules| schedules.values().collect()).unwrap_or_default();
+    schedules.sort_by_key(|schedule| schedule["service_id"].as_str().unwrap_or(""));
+    for schedule in schedules {
+        for (period_index, period) in schedule["periods"].as_array().unwrap_or(&Vec::new()).iter().enumerate() {
+            for (trip_index, trip) in period["trips"].as_array().unwrap_or(&Vec::new()).iter().enumerate() {
+                trips_count += 1;
+                let path_nodes_count = trip["path_id"].as_str().and_then(|path_uuid| paths_nodes_counts.get(path_uuid)).copied();
+                let errors = validate_trip(trip, path_nodes_count);
+                if !errors.is_empty() {
+                    invalid_trips.push(json!({
+                        "service_id": schedule["service_id"],
+                        "schedule_id": schedule["id"],
+                        "period_index": period_index,
+                        "period_shortname": period["period_shortname"],
+                        "trip_index": trip_index,
+                        "trip_id": trip["id"],
+                        "errors": errors
+                    }));
+                }
+            }
+        }
+    }
+
+    json!({
+        "line_id": line_json["id"],
+        "valid": invalid_trips.is_empty(),
+        "trips_count": trips_count,
+        "invalid_trips": invalid_trips
+    })
+}
+
+/// Validate the line object saved in the `lines` subdirectory of the cache
+/// against the paths of the cache
+pub fn validate_cached_line_object(cache_directory_path: &str, line_uuid: &str) -> Result<serde_json::Value, capnp::Error> {
+    let paths_nodes_counts = read_paths_nodes_counts(cache_directory_path)?;
+    let line_object_json = line::read_object(&line_uuid.to_string(), &format!("{}/lines", cache_directory_path))?;
+    Ok(validate_line_object(&line_object_json, &paths_nodes_counts))
+}
+
+/// Strict version of line::write_object: the line object is only written if
+/// all its trips are valid. Like in the cache, the paths are read from the
+/// parent directory of the lines directory.
+pub fn write_line_object_strict(lines_directory_path: &str, json: &serde_json::Value) -> Result<(), capnp::Error> {
+    write_line_object_strict_with_options(lines_directory_path, json, &line::LineWriteOptions::default())
+}
+
+/// Strict version of line::write_object_with_options
+pub fn write_line_object_strict_with_options(lines_directory_path: &str, json: &serde_json::Value, options: &line::LineWriteOptions) -> Result<(), capnp::Error> {
+    let cache_directory_path = Path::new(lines_directory_path).parent().and_then(|path| path.to_str())
+        .ok_or_else(|| capnp::Error::failed(format!("Lines directory {} has no parent directory", lines_directory_path)))?;
+    let validation = validate_line_object(json, &read_paths_nodes_counts(cache_directory_path)?);
+    if validation["valid"] != json!(true) {
+        let trip_errors : Vec<String> = validation["invalid_trips"].as_array().unwrap().iter().map(|invalid_trip| {
+            format!(
+                "service {} period {} trip {}: {}",
+                invalid_trip["service_id"].as_str().unwrap_or(""),
+                invalid_trip["period_index"],
+                invalid_trip["trip_index"],
+                invalid_trip["errors"].as_array().unwrap().iter().filter_map(|error| error.as_str()).collect::<Vec<&str>>().join(", ")
+            )
+        }).collect();
+        return Err(capnp::Error::failed(format!("Line {} has invalid trips: {}", validation["line_id"].as_str().unwrap_or(""), trip_errors.join("; "))));
+    }
+    line::write_object_with_options(lines_directory_path, json, options)
+}
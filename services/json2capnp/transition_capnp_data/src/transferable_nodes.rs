@@ -0,0 +1,115 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Compute the walking transfers between nodes from the node collection */
+
+use crate::node_spatial_index::NodeSpatialIndex;
+use crate::serialization::{node, node_collection};
+use crate::cache::read_collection_file;
+
+#[derive(Debug, Clone, Copy)]
+pub struct TransferableNodesParameters {
+    pub max_walking_distance_meters: f64,
+    // multiplies the straight line distance to approximate the walking distance
+    pub detour_factor: f64,
+    pub walking_speed_meters_per_second: f64,
+}
+
+impl Default for TransferableNodesParameters {
+    // Same as the default transfer preferences: 15 minutes at 5 km/h, bird distance
+    fn default() -> Self {
+        TransferableNodesParameters {
+            max_walking_distance_meters: 15.0 * 60.0 * 1.3888888888,
+            detour_factor: 1.0,
+            walking_speed_meters_per_second: 1.3888888888,
+        }
+    }
+}
+
+impl TransferableNodesParameters {
+    pub fn validate(&self) -> Result<(), capnp::Error> {
+        if self.max_walking_distance_meters.is_nan() || self.max_walking_distance_meters < 0.0 {
+            return Err(capnp::Error::failed(format!("Invalid max walking distance: {}", self.max_walking_distance_meters)));
+        }
+        if self.detour_factor.is_nan() || self.detour_factor < 1.0 {
+            return Err(capnp::Error::failed(format!("Invalid detour factor, must be at least 1: {}", self.detour_factor)));
+        }
+        if self.walking_speed_meters_per_second.is_nan() || self.walking_speed_meters_per_second <= 0.0 {
+            return Err(capnp::Error::failed(format!("Invalid walking speed: {}", self.walking_speed_meters_per_second)));
+        }
+        Ok(())
+    }
+}
+
+/// Transferable nodes of the node at `node_index` in the index, in the
+/// `data.transferableNodes` format of the node objects. Like the ones
+/// computed by the backend, the node itself comes first with 0 travel time.
+pub fn node_transferable_nodes(index: &NodeSpatialIndex, node_index: usize, parameters: &TransferableNodesParameters) -> serde_json::Value {
+    let node = &index.nodes()[node_index];
+    let mut nodes_ids : Vec<&str> = vec![node.uuid.as_str()];
+    let mut walking_travel_times_seconds : Vec<i64> = vec![0];
+    let mut walking_distances_meters : Vec<i64> = vec![0];
+
+    if node.is_enabled {
+        let max_bird_distance_meters = parameters.max_walking_distance_meters / parameters.detour_factor;
+        for node_distance in index.within_center_distance(node.longitude, node.latitude, max_bird_distance_meters) {
+            if node_distance.node.uuid == node.uuid {
+                continue;
+            }
+            // distances and travel times are saved as Int16 in the node objects
+            let walking_distance_meters = (node_distance.center_distance_meters * parameters.detour_factor).ceil();
+            nodes_ids.push(node_distance.node.uuid.as_str());
+            walking_distances_meters.push(walking_distance_meters.min(i16::MAX as f64) as i64);
+            walking_travel_times_seconds.push((walking_distance_meters / parameters.walking_speed_meters_per_second).ceil().min(i16::MAX as f64) as i64);
+        }
+    }
+
+    json!({
+        "nodesIds": nodes_ids,
+        "walkingTravelTimesSeconds": walking_travel_times_seconds,
+        "walkingDistancesMeters": walking_distances_meters
+    })
+}
+
+/// Compute the transferable nodes of every node of the collection file in
+/// the cache directory and write them, with the other node attributes from
+/// the collection, to the node objects in `nodes_directory_path`.
+/// Returns the number of nodes and transfers written.
+pub fn write_transferable_nodes(
+    cache_directory_path: &str,
+    nodes_directory_path: &str,
+    parameters: &TransferableNodesParameters,
+) -> Result<serde_json::Value, capnp::Error> {
+
+    parameters.validate()?;
+
+    let collection_json = read_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?;
+    // index positions match the order of the features
+    let index = NodeSpatialIndex::from_collection_json(&collection_json)?;
+
+    let mut transfers_count : usize = 0;
+    for (node_index, feature) in collection_json["nodes"]["features"].as_array().unwrap().iter().enumerate() {
+        let transferable_nodes = node_transferable_nodes(&index, node_index, parameters);
+        transfers_count += transferable_nodes["nodesIds"].as_array().unwrap().len() - 1;
+
+        let mut node_json = feature["properties"].clone();
+        node_json["geography"] = feature["geometry"].clone();
+        if !node_json["data"].is_object() {
+            node_json["data"] = json!({});
+        }
+        node_json["data"]["transferableNodes"] = transferable_nodes;
+
+        node::write_object(nodes_directory_path, &json!({ "node": node_json }))?;
+    }
+
+    Ok(json!({
+        "nodes_count": index.len(),
+        "transfers_count": transfers_count
+    }))
+
+}
@@ -0,0 +1,754 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Connection scan transit routing on the lines, paths and nodes of a scenario */
+
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
+use crate::cache::read_collection_file;
+use crate::cache_store;
+use crate::fare_calculator::{journey_fare_legs, FareCalculator};
+use crate::node_spatial_index::NodeSpatialIndex;
+use crate::scenario_resolution::resolve_scenario;
+use crate::serialization::{node, node_collection};
+use crate::transferable_nodes::{node_transferable_nodes, TransferableNodesParameters};
+use crate::utils::haversine_distance_meters;
+
+const UNREACHED: i64 = i64::MAX;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RoutingParameters {
+    // minimum time between the arrival at a node and the boarding of a vehicle
+    pub min_waiting_time_seconds: i64,
+    pub max_access_egress_travel_time_seconds: i64,
+    pub max_transfer_travel_time_seconds: i64,
+    pub max_total_travel_time_seconds: i64,
+    pub walking_speed_meters_per_second: f64,
+    pub allow_same_line_transfers: bool,
+}
+
+impl Default for RoutingParameters {
+    // Same defaults as trRouting
+    fn default() -> Self {
+        RoutingParameters {
+            min_waiting_time_seconds: 180,
+            max_access_egress_travel_time_seconds: 900,
+            max_transfer_travel_time_seconds: 900,
+            max_total_travel_time_seconds: 10800,
+            walking_speed_meters_per_second: 1.3888888888,
+            allow_same_line_transfers: true,
+        }
+    }
+}
+
+impl RoutingParameters {
+
+    /// Read the parameters from a json object, missing ones keep their default value
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        let default_parameters = RoutingParameters::default();
+        let parameters = RoutingParameters {
+            min_waiting_time_seconds: json["min_waiting_time_seconds"].as_i64().unwrap_or(default_parameters.min_waiting_time_seconds),
+            max_access_egress_travel_time_seconds: json["max_access_egress_travel_time_seconds"].as_i64().unwrap_or(default_parameters.max_access_egress_travel_time_seconds),
+            max_transfer_travel_time_seconds: json["max_transfer_travel_time_seconds"].as_i64().unwrap_or(default_parameters.max_transfer_travel_time_seconds),
+            max_total_travel_time_seconds: json["max_total_travel_time_seconds"].as_i64().unwrap_or(default_parameters.max_total_travel_time_seconds),
+            walking_speed_meters_per_second: json["walking_speed_meters_per_second"].as_f64().unwrap_or(default_parameters.walking_speed_meters_per_second),
+            allow_same_line_transfers: json["allow_same_line_transfers"].as_bool().unwrap_or(default_parameters.allow_same_line_transfers),
+        };
+        parameters.validate()?;
+        Ok(parameters)
+    }
+
+    pub fn validate(&self) -> Result<(), capnp::Error> {
+        for (name, value) in [
+            ("min_waiting_time_seconds", self.min_waiting_time_seconds),
+            ("max_access_egress_travel_time_seconds", self.max_access_egress_travel_time_seconds),
+            ("max_transfer_travel_time_seconds", self.max_transfer_travel_time_seconds),
+            ("max_total_travel_time_seconds", self.max_total_travel_time_seconds),
+        ] {
+            if value < 0 {
+                return Err(capnp::Error::failed(format!("Invalid {}: {}", name, value)));
+            }
+        }
+        if self.walking_speed_meters_per_second.is_nan() || self.walking_speed_meters_per_second <= 0.0 {
+            return Err(capnp::Error::failed(format!("Invalid walking speed: {}", self.walking_speed_meters_per_second)));
+        }
+        Ok(())
+    }
+
+    fn walking_time_seconds(&self, distance_meters: f64) -> i64 {
+        (distance_meters / self.walking_speed_meters_per_second).ceil() as i64
+    }
+}
+
+/// Origin or destination of a trip
+#[derive(Debug, Clone)]
+pub enum Place {
+    Node(String),
+    Coordinates(f64, f64),
+}
+
+impl Place {
+
+    /// A node uuid string or a [longitude, latitude] array
+    pub fn from_json(json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        if let Some(node_uuid) = json.as_str() {
+            return Ok(Place::Node(node_uuid.to_string()));
+        }
+        match json.as_array().map(|coordinates| coordinates.iter().map(|coordinate| coordinate.as_f64()).collect::<Vec<Option<f64>>>()).as_deref() {
+            Some([Some(longitude), Some(latitude)]) => Ok(Place::Coordinates(*longitude, *latitude)),
+            _ => Err(capnp::Error::failed(format!("Invalid place {}, expected a node uuid or [longitude, latitude]", json)))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Connection {
+    from_node: usize,
+    to_node: usize,
+    departure_time_seconds: i64,
+    arrival_time_seconds: i64,
+    can_board: bool,
+    can_unboard: bool,
+    trip: usize,
+}
+
+#[derive(Debug, Clone)]
+struct NetworkTrip {
+    uuid: String,
+    line: usize,
+    path_uuid: String,
+}
+
+#[derive(Debug, Clone)]
+struct Transfer {
+    node: usize,
+    walking_time_seconds: i64,
+    walking_distance_meters: i64,
+}
+
+// How a node was reached
+#[derive(Debug, Clone, Copy)]
+enum Label {
+    Unreached,
+    Access { walking_time_seconds: i64, walking_distance_meters: i64 },
+    Vehicle { trip: usize, connection: usize },
+    Walk { from_node: usize, walking_time_seconds: i64, walking_distance_meters: i64 },
+}
+
+/// Labels of a connection scan from a set of access nodes
+pub struct ScanResult {
+    departure_time_seconds: i64,
+    // earliest time at which a vehicle can be boarded at each node, before the min waiting time
+    ready_times: Vec<i64>,
+    labels: Vec<Label>,
+    // earliest vehicle arrival at each node, used for the egress
+    vehicle_arrival_times: Vec<i64>,
+    vehicle_labels: Vec<Label>,
+    trip_boardings: Vec<Option<usize>>,
+}
+
+impl ScanResult {
+
+    /// Earliest time at which each node is reached, by vehicle or walking,
+    /// by node index, None for unreached nodes
+    pub fn arrival_times(&self) -> Vec<Option<i64>> {
+        self.ready_times.iter().map(|time| if *time == UNREACHED { None } else { Some(*time) }).collect()
+    }
+
+    pub fn departure_time_seconds(&self) -> i64 {
+        self.departure_time_seconds
+    }
+}
+
+/// Nodes, walking transfers and trip connections of a scenario, sorted by
+/// departure time for the connection scan
+pub struct TransitNetwork {
+    index: NodeSpatialIndex,
+    nodes_by_uuid: HashMap<String, usize>,
+    transfers: Vec<Vec<Transfer>>,
+    lines_uuids: Vec<String>,
+    trips: Vec<NetworkTrip>,
+    connections: Vec<Connection>,
+    // trips whose node times do not match their path nodes
+    ignored_trips_uuids: Vec<String>,
+}
+
+impl TransitNetwork {
+
+    /// Build the network from the node collection json, the transferable
+    /// nodes of each node, in the `data.transferableNodes` format, and the
+    /// lines and path features of the scenario. Nodes without transferable
+    /// nodes get the ones computed with the default parameters.
+    pub fn new(
+        nodes_collection_json: &serde_json::Value,
+        transferable_nodes_by_uuid: &HashMap<String, serde_json::Value>,
+        lines: &[serde_json::Value],
+        paths: &[serde_json::Value],
+    ) -> Result<Self, capnp::Error> {
+
+        let index = NodeSpatialIndex::from_collection_json(nodes_collection_json)?;
+        let nodes_by_uuid : HashMap<String, usize> = index.nodes().iter().enumerate().map(|(i, node)| (node.uuid.clone(), i)).collect();
+
+        let default_transferable_nodes_parameters = TransferableNodesParameters::default();
+        let transfers : Vec<Vec<Transfer>> = index.nodes().iter().enumerate().map(|(i, indexed_node)| {
+            let computed_transferable_nodes;
+            let transferable_nodes = match transferable_nodes_by_uuid.get(&indexed_node.uuid) {
+                Some(transferable_nodes) => transferable_nodes,
+                None => {
+                    computed_transferable_nodes = node_transferable_nodes(&index, i, &default_transferable_nodes_parameters);
+                    &computed_transferable_nodes
+                }
+            };
+            let nodes_ids = transferable_nodes["nodesIds"].as_array().map(|nodes_ids| nodes_ids.as_slice()).unwrap_or(&[]);
+            nodes_ids.iter().enumerate().filter_map(|(j, node_uuid)| {
+                let node = *nodes_by_uuid.get(node_uuid.as_str()?)?;
+                if node == i {
+                    return None;
+                }
+                Some(Transfer {
+                    node,
+                    walking_time_seconds: transferable_nodes["walkingTravelTimesSeconds"][j].as_i64()?,
+                    walking_distance_meters: transferable_nodes["walkingDistancesMeters"][j].as_i64().unwrap_or(0),
+                })
+            }).collect()
+        }).collect();
+
+        let paths_nodes : HashMap<&str, Vec<Option<usize>>> = paths.iter().filter_map(|path| {
+            let properties = &path["properties"];
+            Some((properties["id"].as_str()?, properties["nodes"].as_array()?.iter().map(|node_uuid| node_uuid.as_str().and_then(|node_uuid| nodes_by_uuid.get(node_uuid).copied())).collect()))
+        }).collect();
+
+        let mut lines_uuids : Vec<String> = Vec::new();
+        let mut trips : Vec<NetworkTrip> = Vec::new();
+        let mut connections : Vec<Connection> = Vec::new();
+        let mut ignored_trips_uuids : Vec<String> = Vec::new();
+        for line_json in lines {
+            lines_uuids.push(line_json["id"].as_str().unwrap_or("").to_string());
+            let line = lines_uuids.len() - 1;
+            let schedules = line_json["scheduleByServiceId"].as_object().map(|schedules| schedules.values().collect()).unwrap_or_else(Vec::new);
+            for schedule in schedules {
+                for period in schedule["periods"].as_array().map(|periods| periods.as_slice()).unwrap_or(&[]) {
+                    for trip_json in period["trips"].as_array().map(|trips| trips.as_slice()).unwrap_or(&[]) {
+                        let trip_uuid = trip_json["id"].as_str().unwrap_or("").to_string();
+                        let path_uuid = trip_json["path_id"].as_str().unwrap_or("");
+                        let trip_connections = paths_nodes.get(path_uuid).and_then(|path_nodes| trip_connections(trip_json, path_nodes, trips.len()));
+                        match trip_connections {
+                            Some(trip_connections) => {
+                                connections.extend(trip_connections);
+                                trips.push(NetworkTrip { uuid: trip_uuid, line, path_uuid: path_uuid.to_string() });
+                            },
+                            None => ignored_trips_uuids.push(trip_uuid)
+                        }
+                    }
+                }
+            }
+        }
+        // connections of a trip stay in order when they have the same departure time
+        connections.sort_by_key(|connection| (connection.departure_time_seconds, connection.arrival_time_seconds));
+
+        Ok(TransitNetwork { index, nodes_by_uuid, transfers, lines_uuids, trips, connections, ignored_trips_uuids })
+    }
+
+    /// Load the network of a scenario from a cache directory: the node
+    /// collection and node objects, then the lines, paths and trips effective
+    /// in the scenario.
+    pub fn load(cache_directory_path: &str, scenario_uuid: &str) -> Result<Self, capnp::Error> {
+        let resolved_scenario = resolve_scenario(cache_directory_path, scenario_uuid)?;
+        let nodes_collection_json = read_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?;
+
+        let nodes_directory_path = format!("{}/nodes", cache_directory_path);
+        let mut transferable_nodes_by_uuid : HashMap<String, serde_json::Value> = HashMap::new();
+        for node_feature in nodes_collection_json["nodes"]["features"].as_array().unwrap() {
+            let node_uuid = node_feature["properties"]["id"].as_str().unwrap_or("").to_string();
+            // node objects are optional, the transferable nodes are then computed
+            if let Ok(node_object) = node::read_object(&node_uuid, &nodes_directory_path) {
+                let transferable_nodes = &node_object["node"]["data"]["transferableNodes"];
+                if transferable_nodes.is_object() {
+                    transferable_nodes_by_uuid.insert(node_uuid, transferable_nodes.clone());
+                }
+            }
+        }
+
+        TransitNetwork::new(&nodes_collection_json, &transferable_nodes_by_uuid, &resolved_scenario.lines, &resolved_scenario.paths)
+    }
+
+    pub fn nodes_count(&self) -> usize {
+        self.index.len()
+    }
+
+    pub fn trips_count(&self) -> usize {
+        self.trips.len()
+    }
+
+    pub fn connections_count(&self) -> usize {
+        self.connections.len()
+    }
+
+    pub fn ignored_trips_uuids(&self) -> &[String] {
+        &self.ignored_trips_uuids
+    }
+
+    pub fn node_index(&self) -> &NodeSpatialIndex {
+        &self.index
+    }
+
+    pub fn place_coordinates(&self, place: &Place) -> Result<(f64, f64), capnp::Error> {
+        match place {
+            Place::Coordinates(longitude, latitude) => Ok((*longitude, *latitude)),
+            Place::Node(node_uuid) => self.nodes_by_uuid.get(node_uuid)
+                .map(|node| (self.index.nodes()[*node].longitude, self.index.nodes()[*node].latitude))
+                .ok_or_else(|| capnp::Error::failed(format!("Node {} not found", node_uuid)))
+        }
+    }
+
+    /// Nodes within walking distance of a place for the access or egress,
+    /// with their walking time and distance
+    pub fn access_nodes(&self, place: &Place, parameters: &RoutingParameters) -> Result<Vec<(usize, i64, i64)>, capnp::Error> {
+        let (longitude, latitude) = self.place_coordinates(place)?;
+        let max_distance_meters = parameters.max_access_egress_travel_time_seconds as f64 * parameters.walking_speed_meters_per_second;
+        let nodes_by_uuid = &self.nodes_by_uuid;
+        Ok(self.index.within_center_distance(longitude, latitude, max_distance_meters).iter()
+            .filter(|node_distance| node_distance.node.is_enabled)
+            .map(|node_distance| (
+                nodes_by_uuid[&node_distance.node.uuid],
+                parameters.walking_time_seconds(node_distance.center_distance_meters),
+                node_distance.center_distance_meters.round() as i64
+            ))
+            .collect())
+    }
+
+    fn line_of_label(&self, label: &Label, vehicle_labels: &[Label]) -> Option<usize> {
+        match label {
+            Label::Vehicle { trip, .. } => Some(self.trips[*trip].line),
+            Label::Walk { from_node, .. } => match vehicle_labels[*from_node] {
+                Label::Vehicle { trip, .. } => Some(self.trips[trip].line),
+                _ => None
+            },
+            _ => None
+        }
+    }
+
+    /// Scan the connections departing after the departure time from the
+    /// access nodes, until the max total travel time. When egress nodes are
+    /// given, the scan stops once no connection can improve the arrival at
+    /// the destination.
+    pub fn scan(
+        &self,
+        access_nodes: &[(usize, i64, i64)],
+        egress_nodes: &[(usize, i64, i64)],
+        departure_time_seconds: i64,
+        parameters: &RoutingParameters,
+    ) -> ScanResult {
+        let nodes_count = self.nodes_count();
+        let mut scan = ScanResult {
+            departure_time_seconds,
+            ready_times: vec![UNREACHED; nodes_count],
+            labels: vec![Label::Unreached; nodes_count],
+            vehicle_arrival_times: vec![UNREACHED; nodes_count],
+            vehicle_labels: vec![Label::Unreached; nodes_count],
+            trip_boardings: vec![None; self.trips.len()],
+        };
+        for (node, walking_time_seconds, walking_distance_meters) in access_nodes {
+            if departure_time_seconds + walking_time_seconds < scan.ready_times[*node] {
+                scan.ready_times[*node] = departure_time_seconds + walking_time_seconds;
+                scan.labels[*node] = Label::Access { walking_time_seconds: *walking_time_seconds, walking_distance_meters: *walking_distance_meters };
+            }
+        }
+        let egress_times : HashMap<usize, i64> = egress_nodes.iter().map(|(node, walking_time_seconds, _)| (*node, *walking_time_seconds)).collect();
+        let mut best_destination_arrival = UNREACHED;
+        let max_time_seconds = departure_time_seconds + parameters.max_total_travel_time_seconds;
+
+        let first_connection = self.connections.partition_point(|connection| connection.departure_time_seconds < departure_time_seconds);
+        for (connection_index, connection) in self.connections.iter().enumerate().skip(first_connection) {
+            if connection.departure_time_seconds > max_time_seconds || connection.departure_time_seconds >= best_destination_arrival {
+                break;
+            }
+
+            if scan.trip_boardings[connection.trip].is_none() && connection.can_board {
+                let ready_time = scan.ready_times[connection.from_node];
+                let can_board = ready_time != UNREACHED
+                    && ready_time + parameters.min_waiting_time_seconds <= connection.departure_time_seconds
+                    && (parameters.allow_same_line_transfers
+                        || self.line_of_label(&scan.labels[connection.from_node], &scan.vehicle_labels) != Some(self.trips[connection.trip].line));
+                if can_board {
+                    scan.trip_boardings[connection.trip] = Some(connection_index);
+                }
+            }
+            if scan.trip_boardings[connection.trip].is_none() || !connection.can_unboard {
+                continue;
+            }
+
+            let arrival_time = connection.arrival_time_seconds;
+            if arrival_time >= scan.vehicle_arrival_times[connection.to_node] {
+                continue;
+            }
+            let vehicle_label = Label::Vehicle { trip: connection.trip, connection: connection_index };
+            scan.vehicle_arrival_times[connection.to_node] = arrival_time;
+            scan.vehicle_labels[connection.to_node] = vehicle_label;
+            if let Some(egress_time) = egress_times.get(&connection.to_node) {
+                best_destination_arrival = best_destination_arrival.min(arrival_time + egress_time);
+            }
+            if arrival_time < scan.ready_times[connection.to_node] {
+                scan.ready_times[connection.to_node] = arrival_time;
+                scan.labels[connection.to_node] = vehicle_label;
+            }
+            for transfer in self.transfers[connection.to_node].iter() {
+                if transfer.walking_time_seconds > parameters.max_transfer_travel_time_seconds {
+                    continue;
+                }
+                if arrival_time + transfer.walking_time_seconds < scan.ready_times[transfer.node] {
+                    scan.ready_times[transfer.node] = arrival_time + transfer.walking_time_seconds;
+                    scan.labels[transfer.node] = Label::Walk {
+                        from_node: connection.to_node,
+                        walking_time_seconds: transfer.walking_time_seconds,
+                        walking_distance_meters: transfer.walking_distance_meters,
+                    };
+                }
+            }
+        }
+        scan
+    }
+
+    fn node_uuid(&self, node: usize) -> &str {
+        &self.index.nodes()[node].uuid
+    }
+
+    // Build the journey ending by the egress from a node, following the labels back to the access
+    fn journey(&self, scan: &ScanResult, egress_node: usize, egress_walking_time_seconds: i64, egress_walking_distance_meters: i64) -> serde_json::Value {
+        let mut steps : Vec<serde_json::Value> = Vec::new();
+        let mut in_vehicle_time_seconds = 0;
+        let mut waiting_time_seconds = 0;
+        let mut transfer_walking_time_seconds = 0;
+        let mut access_walking_time_seconds = 0;
+        let mut boardings_count = 0;
+        let mut first_boarding_time_seconds = scan.departure_time_seconds;
+
+        steps.push(json!({
+            "action": "walking",
+            "type": "egress",
+            "travel_time_seconds": egress_walking_time_seconds,
+            "distance_meters": egress_walking_distance_meters
+        }));
+        let mut label = scan.vehicle_labels[egress_node];
+        loop {
+            match label {
+                Label::Vehicle { trip, connection: alighting_connection_index } => {
+                    let boarding_connection_index = scan.trip_boardings[trip].unwrap();
+                    let boarding_connection = &self.connections[boarding_connection_index];
+                    let alighting_connection = &self.connections[alighting_connection_index];
+                    let network_trip = &self.trips[trip];
+                    let ready_time = scan.ready_times[boarding_connection.from_node];
+                    in_vehicle_time_seconds += alighting_connection.arrival_time_seconds - boarding_connection.departure_time_seconds;
+                    waiting_time_seconds += boarding_connection.departure_time_seconds - ready_time;
+                    boardings_count += 1;
+                    first_boarding_time_seconds = boarding_connection.departure_time_seconds;
+                    steps.push(json!({
+                        "action": "unboarding",
+                        "line_id": self.lines_uuids[network_trip.line],
+                        "trip_id": network_trip.uuid,
+                        "node_id": self.node_uuid(alighting_connection.to_node),
+                        "arrival_time_seconds": alighting_connection.arrival_time_seconds,
+                        "in_vehicle_time_seconds": alighting_connection.arrival_time_seconds - boarding_connection.departure_time_seconds
+                    }));
+                    steps.push(json!({
+                        "action": "boarding",
+                        "line_id": self.lines_uuids[network_trip.line],
+                        "path_id": network_trip.path_uuid,
+                        "trip_id": network_trip.uuid,
+                        "node_id": self.node_uuid(boarding_connection.from_node),
+                        "departure_time_seconds": boarding_connection.departure_time_seconds,
+                        "waiting_time_seconds": boarding_connection.departure_time_seconds - ready_time
+                    }));
+                    label = scan.labels[boarding_connection.from_node];
+                },
+                Label::Walk { from_node, walking_time_seconds, walking_distance_meters } => {
+                    transfer_walking_time_seconds += walking_time_seconds;
+                    steps.push(json!({
+                        "action": "walking",
+                        "type": "transfer",
+                        "travel_time_seconds": walking_time_seconds,
+                        "distance_meters": walking_distance_meters
+                    }));
+                    label = scan.vehicle_labels[from_node];
+                },
+                Label::Access { walking_time_seconds, walking_distance_meters } => {
+                    access_walking_time_seconds = walking_time_seconds;
+                    steps.push(json!({
+                        "action": "walking",
+                        "type": "access",
+                        "travel_time_seconds": walking_time_seconds,
+                        "distance_meters": walking_distance_meters
+                    }));
+                    break;
+                },
+                Label::Unreached => break
+            }
+        }
+        steps.reverse();
+
+        let arrival_time_seconds = scan.vehicle_arrival_times[egress_node] + egress_walking_time_seconds;
+        json!({
+            "status": "success",
+            "departure_time_seconds": scan.departure_time_seconds,
+            "arrival_time_seconds": arrival_time_seconds,
+            "total_travel_time_seconds": arrival_time_seconds - scan.departure_time_seconds,
+            "in_vehicle_time_seconds": in_vehicle_time_seconds,
+            "access_travel_time_seconds": access_walking_time_seconds,
+            "egress_travel_time_seconds": egress_walking_time_seconds,
+            "transfer_walking_time_seconds": transfer_walking_time_seconds,
+            "waiting_time_seconds": waiting_time_seconds,
+            "first_waiting_time_seconds": first_boarding_time_seconds - scan.departure_time_seconds - access_walking_time_seconds,
+            "number_of_transfers": (boardings_count - 1).max(0),
+            "steps": steps
+        })
+    }
+
+    // Best egress from a scan, as (node, walking time, walking distance)
+    fn best_egress(&self, scan: &ScanResult, egress_nodes: &[(usize, i64, i64)], parameters: &RoutingParameters) -> Option<(usize, i64, i64)> {
+        let max_arrival_time = scan.departure_time_seconds + parameters.max_total_travel_time_seconds;
+        egress_nodes.iter()
+            .filter(|(node, walking_time_seconds, _)| scan.vehicle_arrival_times[*node] != UNREACHED && scan.vehicle_arrival_times[*node] + walking_time_seconds <= max_arrival_time)
+            .min_by_key(|(node, walking_time_seconds, _)| (scan.vehicle_arrival_times[*node] + walking_time_seconds, *node))
+            .copied()
+    }
+
+    fn walking_only_travel_time_seconds(&self, origin: &Place, destination: &Place, parameters: &RoutingParameters) -> Result<Option<i64>, capnp::Error> {
+        let (origin_longitude, origin_latitude) = self.place_coordinates(origin)?;
+        let (destination_longitude, destination_latitude) = self.place_coordinates(destination)?;
+        let walking_time_seconds = parameters.walking_time_seconds(haversine_distance_meters(origin_longitude, origin_latitude, destination_longitude, destination_latitude));
+        Ok(if walking_time_seconds <= parameters.max_access_egress_travel_time_seconds { Some(walking_time_seconds) } else { None })
+    }
+
+    /// Earliest arrival journey from the origin to the destination, leaving
+    /// at the departure time. The status is "no_routing_found" when the
+    /// destination cannot be reached by transit within the limits. The
+    /// walking only travel time is given when it is within the access limit.
+    pub fn earliest_arrival(
+        &self,
+        origin: &Place,
+        destination: &Place,
+        departure_time_seconds: i64,
+        parameters: &RoutingParameters,
+    ) -> Result<serde_json::Value, capnp::Error> {
+        parameters.validate()?;
+        let access_nodes = self.access_nodes(origin, parameters)?;
+        let egress_nodes = self.access_nodes(destination, parameters)?;
+
+        let scan = self.scan(&access_nodes, &egress_nodes, departure_time_seconds, parameters);
+        let mut journey = match self.best_egress(&scan, &egress_nodes, parameters) {
+            Some((egress_node, walking_time_seconds, walking_distance_meters)) => self.journey(&scan, egress_node, walking_time_seconds, walking_distance_meters),
+            None => json!({ "status": "no_routing_found", "departure_time_seconds": departure_time_seconds })
+        };
+        journey["walking_only_travel_time_seconds"] = json!(self.walking_only_travel_time_seconds(origin, destination, parameters)?);
+        Ok(journey)
+    }
+
+    /// Latest departure times from the origin catching each boardable
+    /// connection of the access nodes, between two times, in increasing
+    /// order. Leaving at any other time only adds waiting at the origin.
+    pub fn access_departure_times(
+        &self,
+        access_nodes: &[(usize, i64, i64)],
+        start_time_seconds: i64,
+        end_time_seconds: i64,
+        parameters: &RoutingParameters,
+    ) -> Vec<i64> {
+        let access_times : HashMap<usize, i64> = access_nodes.iter().map(|(node, walking_time_seconds, _)| (*node, *walking_time_seconds)).collect();
+        let mut departure_times : Vec<i64> = self.connections.iter()
+            .filter(|connection| connection.can_board)
+            .filter_map(|connection| access_times.get(&connection.from_node).map(|walking_time_seconds| connection.departure_time_seconds - parameters.min_waiting_time_seconds - walking_time_seconds))
+            .filter(|departure_time| *departure_time >= start_time_seconds && *departure_time <= end_time_seconds)
+            .collect::<HashSet<i64>>().into_iter().collect();
+        departure_times.sort_unstable();
+        departure_times
+    }
+
+    /// Pareto optimal journeys leaving between two times: a journey is kept
+    /// if no journey leaving later arrives as early. Each journey leaves at
+    /// the latest time that catches its first vehicle.
+    pub fn profile(
+        &self,
+        origin: &Place,
+        destination: &Place,
+        start_time_seconds: i64,
+        end_time_seconds: i64,
+        parameters: &RoutingParameters,
+    ) -> Result<serde_json::Value, capnp::Error> {
+        parameters.validate()?;
+        if end_time_seconds < start_time_seconds {
+            return Err(capnp::Error::failed(format!("Invalid time range: {} is before {}", end_time_seconds, start_time_seconds)));
+        }
+        let access_nodes = self.access_nodes(origin, parameters)?;
+        let egress_nodes = self.access_nodes(destination, parameters)?;
+
+        let mut journeys : Vec<serde_json::Value> = Vec::new();
+        let mut best_arrival_time = UNREACHED;
+        for departure_time_seconds in self.access_departure_times(&access_nodes, start_time_seconds, end_time_seconds, parameters).into_iter().rev() {
+            let scan = self.scan(&access_nodes, &egress_nodes, departure_time_seconds, parameters);
+            if let Some((egress_node, walking_time_seconds, walking_distance_meters)) = self.best_egress(&scan, &egress_nodes, parameters) {
+                let arrival_time = scan.vehicle_arrival_times[egress_node] + walking_time_seconds;
+                if arrival_time < best_arrival_time {
+                    best_arrival_time = arrival_time;
+                    journeys.push(self.journey(&scan, egress_node, walking_time_seconds, walking_distance_meters));
+                }
+            }
+        }
+        journeys.reverse();
+
+        Ok(json!({
+            "journeys": journeys,
+            "walking_only_travel_time_seconds": self.walking_only_travel_time_seconds(origin, destination, parameters)?
+        }))
+    }
+}
+
+// Modification times of the directories of a local cache. The local
+// directory store renames the files it writes, so they change with any file.
+// None for the other stores, their networks are kept until cleared.
+fn local_cache_version(cache_directory_path: &str) -> Option<Vec<Option<SystemTime>>> {
+    if !cache_store::is_local(cache_directory_path) {
+        return None;
+    }
+    Some(["", "lines", "nodes"].iter().map(|directory| {
+        std::path::Path::new(cache_directory_path).join(directory).metadata().and_then(|metadata| metadata.modified()).ok()
+    }).collect())
+}
+
+struct LoadedNetwork {
+    cache_directory_path: String,
+    scenario_uuid: String,
+    version: Option<Vec<Option<SystemTime>>>,
+    network: Arc<TransitNetwork>,
+}
+
+/// Networks loaded by a long running process, like the json2capnp server,
+/// so the queries on a scenario do not load its network again. The network
+/// of a local cache is loaded again once a file of the cache directory, its
+/// lines or its nodes changed. For the other stores, clear the networks after
+/// writing to the cache.
+pub struct TransitNetworkCache {
+    max_networks_count: usize,
+    // most recently used last
+    networks: Mutex<Vec<LoadedNetwork>>,
+}
+
+impl TransitNetworkCache {
+
+    /// Keep at most max_networks_count networks, 0 to load every network
+    pub fn new(max_networks_count: usize) -> Self {
+        TransitNetworkCache { max_networks_count, networks: Mutex::new(Vec::new()) }
+    }
+
+    pub fn get(&self, cache_directory_path: &str, scenario_uuid: &str) -> Result<Arc<TransitNetwork>, capnp::Error> {
+        let version = local_cache_version(cache_directory_path);
+        {
+            let mut networks = self.networks.lock().unwrap();
+            let position = networks.iter().position(|loaded_network| loaded_network.cache_directory_path == cache_directory_path && loaded_network.scenario_uuid == scenario_uuid);
+            if let Some(position) = position {
+                let loaded_network = networks.remove(position);
+                if loaded_network.version == version {
+                    let network = loaded_network.network.clone();
+                    networks.push(loaded_network);
+                    return Ok(network);
+                }
+            }
+        }
+
+        // loaded without the lock, so the queries on the loaded networks are not blocked
+        let network = Arc::new(TransitNetwork::load(cache_directory_path, scenario_uuid)?);
+        if self.max_networks_count > 0 {
+            let mut networks = self.networks.lock().unwrap();
+            networks.retain(|loaded_network| loaded_network.cache_directory_path != cache_directory_path || loaded_network.scenario_uuid != scenario_uuid);
+            if networks.len() >= self.max_networks_count {
+                networks.remove(0);
+            }
+            networks.push(LoadedNetwork {
+                cache_directory_path: cache_directory_path.to_string(),
+                scenario_uuid: scenario_uuid.to_string(),
+                version,
+                network: network.clone()
+            });
+        }
+        Ok(network)
+    }
+
+    pub fn clear(&self) {
+        self.networks.lock().unwrap().clear();
+    }
+
+}
+
+/// Answer a routing query on the network of a cache: `scenario_id`, `origin`
+/// and `destination` places, either a `departure_time_seconds` for an
+/// earliest arrival query or a `departure_time_range_seconds` [start, end]
+/// for a profile query, and the routing parameters. With `with_fare`, each
+/// journey has its `fare` from the fares of the cache, or the fare `error`.
+/// The network of the scenario is loaded for the query, see
+/// route_query_with_networks to answer many queries.
+pub fn route_query(cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    route_query_with_networks(&TransitNetworkCache::new(0), cache_directory_path, query)
+}
+
+/// Answer a routing query like route_query, on a network of the cache
+pub fn route_query_with_networks(networks: &TransitNetworkCache, cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    let scenario_uuid = query["scenario_id"].as_str().ok_or_else(|| capnp::Error::failed(String::from("Missing scenario_id")))?;
+    let origin = Place::from_json(&query["origin"])?;
+    let destination = Place::from_json(&query["destination"])?;
+    let parameters = RoutingParameters::from_json(query)?;
+    let network = networks.get(cache_directory_path, scenario_uuid)?;
+
+    let mut result = match (query["departure_time_seconds"].as_i64(), query["departure_time_range_seconds"].as_array()) {
+        (_, Some(range)) if range.len() == 2 && range.iter().all(|time| time.is_i64()) => {
+            network.profile(&origin, &destination, range[0].as_i64().unwrap(), range[1].as_i64().unwrap(), &parameters)
+        },
+        (Some(departure_time_seconds), None) => network.earliest_arrival(&origin, &destination, departure_time_seconds, &parameters),
+        _ => Err(capnp::Error::failed(String::from("Missing departure_time_seconds or invalid departure_time_range_seconds")))
+    }?;
+
+    if query["with_fare"].as_bool().unwrap_or(false) {
+        let calculator = FareCalculator::load(cache_directory_path)?;
+        let mut journeys : Vec<&mut serde_json::Value> = match result["journeys"].as_array_mut() {
+            Some(journeys) => journeys.iter_mut().collect(),
+            None => vec![&mut result]
+        };
+        for journey in journeys.iter_mut().filter(|journey| journey["status"] == "success") {
+            journey["fare"] = calculator.price(&journey_fare_legs(journey)).unwrap_or_else(|error| json!({ "error": error.extra }));
+        }
+    }
+    Ok(result)
+}
+
+// Connections between the consecutive nodes of a trip, None if the node
+// times and boardability do not match the path nodes
+fn trip_connections(trip_json: &serde_json::Value, path_nodes: &[Option<usize>], trip: usize) -> Option<Vec<Connection>> {
+    let arrival_times = trip_json["node_arrival_times_seconds"].as_array()?;
+    let departure_times = trip_json["node_departure_times_seconds"].as_array()?;
+    let can_board = trip_json["nodes_can_board"].as_array()?;
+    let can_unboard = trip_json["nodes_can_unboard"].as_array()?;
+    let nodes_count = path_nodes.len();
+    if [arrival_times.len(), departure_times.len(), can_board.len(), can_unboard.len()].iter().any(|length| *length != nodes_count) {
+        return None;
+    }
+
+    let mut connections : Vec<Connection> = Vec::with_capacity(nodes_count.saturating_sub(1));
+    for i in 1..nodes_count {
+        let departure_time_seconds = departure_times[i - 1].as_i64().filter(|time| *time >= 0)?;
+        let arrival_time_seconds = arrival_times[i].as_i64().filter(|time| *time >= 0)?;
+        connections.push(Connection {
+            from_node: path_nodes[i - 1]?,
+            to_node: path_nodes[i]?,
+            departure_time_seconds,
+            arrival_time_seconds,
+            can_board: can_board[i - 1].as_bool().unwrap_or(true),
+            can_unboard: can_unboard[i].as_bool().unwrap_or(true),
+            trip,
+        });
+    }
+    Some(connections)
+}
@@ -7,6 +7,10 @@
  */
 
 use regex::Regex;
+use geo::{Distance, Haversine, Point};
+
+/// Same mean earth radius as the one used by the geo haversine algorithms
+pub const EARTH_RADIUS_METERS: f64 = 6371008.8;
 
 /*pub fn string_or_null_to_empty_string(input: &std::string::String) -> std::string::String {
     if input == "null" {
@@ -78,6 +82,40 @@ pub fn minus_one_f64_to_null(input: f64) -> serde_json::Value {
     }
 }
 
+/// Multiply by 1000 and round, -1 if null: for the expansion factors stored as integers
+pub fn json_value_or_null_to_thousandths_or_minus_one(input: &serde_json::Value) -> i32 {
+    match input.as_f64() {
+        Some(value) => (value * 1000.0).round() as i32,
+        None => -1,
+    }
+}
+
+pub fn minus_one_thousandths_to_null(input: i32) -> serde_json::Value {
+    if input == -1 {
+        json!(null)
+    } else {
+        json!(input as f64 / 1000.0)
+    }
+}
+
+/// Longitude and latitude of a geojson point geometry, multiplied by 1000000
+pub fn json_point_to_i32_coordinates(input: &serde_json::Value) -> Option<(i32, i32)> {
+    if input["type"] != "Point" {
+        return None;
+    }
+    match (input["coordinates"][0].as_f64(), input["coordinates"][1].as_f64()) {
+        (Some(longitude), Some(latitude)) => Some(((longitude * 1000000.0).round() as i32, (latitude * 1000000.0).round() as i32)),
+        _ => None
+    }
+}
+
+pub fn i32_coordinates_to_json_point(longitude: i32, latitude: i32) -> serde_json::Value {
+    json!({
+        "type": "Point",
+        "coordinates": [(longitude as f64) / 1000000.0, (latitude as f64) / 1000000.0]
+    })
+}
+
 pub fn json_boolean_to_i8(input: &serde_json::Value) -> i8 {
     match input.as_bool() {
         Some(true) => 1,
@@ -136,4 +174,8 @@ pub fn seconds_since_midnight_to_time_str(&seconds_since_midnight: &u32) -> Stri
     let seconds = seconds_since_midnight - hours * 3600 - minutes * 60;
     let time_string = if seconds == 0 { format!("{:02}:{:02}", hours, minutes) } else { format!("{:02}:{:02}:{:02}", hours, minutes, seconds) };
     time_string
-}
\ No newline at end of file
+}
+
+pub fn haversine_distance_meters(longitude_a: f64, latitude_a: f64, longitude_b: f64, latitude_b: f64) -> f64 {
+    Haversine.distance(Point::new(longitude_a, latitude_a), Point::new(longitude_b, latitude_b))
+}
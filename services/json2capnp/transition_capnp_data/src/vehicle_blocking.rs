@@ -0,0 +1,333 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Chain the trips of the line schedules into vehicle blocks with a minimal fleet size */
+
+use std::collections::{HashMap, VecDeque};
+use uuid::Uuid;
+use crate::cache::read_collection_file;
+use crate::serialization::{line, line_collection, path_collection};
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct BlockingParameters {
+    // minimum time between the arrival of a trip and the departure of the next
+    // trip of the same vehicle, not counting the dead head travel time
+    pub min_layover_seconds: i64,
+}
+
+impl BlockingParameters {
+    pub fn validate(&self) -> Result<(), capnp::Error> {
+        if self.min_layover_seconds < 0 {
+            return Err(capnp::Error::failed(format!("Invalid min layover: {}", self.min_layover_seconds)));
+        }
+        Ok(())
+    }
+}
+
+// position of a trip in the line objects
+#[derive(Debug, Clone, Copy)]
+struct TripPosition {
+    line: usize,
+    period: usize,
+    trip: usize,
+    departure_time_seconds: i64,
+    arrival_time_seconds: i64,
+}
+
+fn schedule_for_service<'a>(line_json: &'a serde_json::Value, service_uuid: &str) -> Option<&'a serde_json::Value> {
+    line_json["scheduleByServiceId"].as_object().and_then(|schedules| {
+        schedules.values().find(|schedule| schedule["service_id"] == json!(service_uuid))
+    })
+}
+
+fn trip_at<'a>(lines: &'a [serde_json::Value], service_uuid: &str, position: &TripPosition) -> &'a serde_json::Value {
+    &schedule_for_service(&lines[position.line], service_uuid).unwrap()["periods"][position.period]["trips"][position.trip]
+}
+
+/// Dead head travel time from the end of the first path to the start of the
+/// second one. Within a line, it comes from the `deadHeadTravelTimesBetweenPathsByPathId`
+/// of the line data. Otherwise, paths ending at the node where the other one
+/// starts are connected without dead head. None if the paths are not connected.
+fn dead_head_seconds(
+    from_line: &serde_json::Value,
+    to_line: &serde_json::Value,
+    from_path_uuid: &str,
+    to_path_uuid: &str,
+    paths_terminal_nodes: &HashMap<String, (String, String)>,
+) -> Option<i64> {
+    if from_line["id"] == to_line["id"] {
+        let dead_head = &from_line["data"]["deadHeadTravelTimesBetweenPathsByPathId"][from_path_uuid][to_path_uuid];
+        if let Some(dead_head_seconds) = dead_head.as_f64() {
+            return Some(dead_head_seconds.ceil() as i64);
+        }
+    }
+    match (paths_terminal_nodes.get(from_path_uuid), paths_terminal_nodes.get(to_path_uuid)) {
+        (Some((_, from_last_node)), Some((to_first_node, _))) if from_last_node == to_first_node => Some(0),
+        _ => None
+    }
+}
+
+// Hopcroft-Karp maximum matching of the trips to their successor trip. Each
+// matched pair is a vehicle going from one trip to the next, so the fleet size
+// is the number of trips minus the size of the matching.
+fn maximum_matching(successors: &[Vec<usize>]) -> Vec<Option<usize>> {
+    let count = successors.len();
+    let mut next : Vec<Option<usize>> = vec![None; count];
+    let mut previous : Vec<Option<usize>> = vec![None; count];
+    let mut distances : Vec<usize> = vec![0; count];
+
+    loop {
+        // breadth first search from the free trips, layered by distance
+        let mut queue : VecDeque<usize> = VecDeque::new();
+        for trip in 0..count {
+            if next[trip].is_none() {
+                distances[trip] = 0;
+                queue.push_back(trip);
+            } else {
+                distances[trip] = usize::MAX;
+            }
+        }
+        let mut found_augmenting_path = false;
+        while let Some(trip) = queue.pop_front() {
+            for &successor in successors[trip].iter() {
+                match previous[successor] {
+                    None => found_augmenting_path = true,
+                    Some(matched) if distances[matched] == usize::MAX => {
+                        distances[matched] = distances[trip] + 1;
+                        queue.push_back(matched);
+                    },
+                    _ => {}
+                }
+            }
+        }
+        if !found_augmenting_path {
+            return next;
+        }
+
+        fn augment(trip: usize, successors: &[Vec<usize>], next: &mut [Option<usize>], previous: &mut [Option<usize>], distances: &mut [usize]) -> bool {
+            for &successor in successors[trip].iter() {
+                let can_augment = match previous[successor] {
+                    None => true,
+                    Some(matched) => distances[matched] == distances[trip] + 1 && augment(matched, successors, next, previous, distances)
+                };
+                if can_augment {
+                    next[trip] = Some(successor);
+                    previous[successor] = Some(trip);
+                    return true;
+                }
+            }
+            distances[trip] = usize::MAX;
+            false
+        }
+        for trip in 0..count {
+            if next[trip].is_none() {
+                augment(trip, successors, &mut next, &mut previous, &mut distances);
+            }
+        }
+    }
+}
+
+// Maximum number of blocks in service at the same time during a period,
+// a vehicle being in service from the departure of its first trip to the
+// arrival of its last one
+fn peak_vehicles(blocks_spans: &[(i64, i64)], start_time_seconds: i64, end_time_seconds: i64) -> usize {
+    let candidate_times = std::iter::once(start_time_seconds)
+        .chain(blocks_spans.iter().map(|(start, _)| *start).filter(|start| *start >= start_time_seconds && *start < end_time_seconds));
+    candidate_times.map(|time| blocks_spans.iter().filter(|(start, end)| *start <= time && time < *end).count()).max().unwrap_or(0)
+}
+
+fn block_service(
+    lines: &mut [serde_json::Value],
+    service_uuid: &str,
+    paths_terminal_nodes: &HashMap<String, (String, String)>,
+    parameters: &BlockingParameters,
+) -> serde_json::Value {
+
+    let mut trips : Vec<TripPosition> = Vec::new();
+    let mut unblocked_trips_ids : Vec<serde_json::Value> = Vec::new();
+    let mut periods : Vec<(serde_json::Value, f64, f64)> = Vec::new();
+    for (line_index, line_json) in lines.iter().enumerate() {
+        let schedule = match schedule_for_service(line_json, service_uuid) {
+            Some(schedule) => schedule,
+            None => continue
+        };
+        for (period_index, period) in schedule["periods"].as_array().map(|periods| periods.as_slice()).unwrap_or(&[]).iter().enumerate() {
+            if let (Some(start_at_hour), Some(end_at_hour)) = (period["start_at_hour"].as_f64(), period["end_at_hour"].as_f64()) {
+                if !periods.iter().any(|(shortname, start, end)| *shortname == period["period_shortname"] && *start == start_at_hour && *end == end_at_hour) {
+                    periods.push((period["period_shortname"].clone(), start_at_hour, end_at_hour));
+                }
+            }
+            for (trip_index, trip) in period["trips"].as_array().map(|trips| trips.as_slice()).unwrap_or(&[]).iter().enumerate() {
+                match (trip["departure_time_seconds"].as_i64(), trip["arrival_time_seconds"].as_i64()) {
+                    (Some(departure_time_seconds), Some(arrival_time_seconds)) => trips.push(TripPosition {
+                        line: line_index,
+                        period: period_index,
+                        trip: trip_index,
+                        departure_time_seconds,
+                        arrival_time_seconds,
+                    }),
+                    _ => unblocked_trips_ids.push(trip["id"].clone())
+                }
+            }
+        }
+    }
+    trips.sort_by_key(|trip| (trip.departure_time_seconds, trip.arrival_time_seconds));
+
+    let successors : Vec<Vec<usize>> = trips.iter().enumerate().map(|(i, from_trip)| {
+        let from_trip_json = trip_at(lines, service_uuid, from_trip);
+        let from_path_uuid = from_trip_json["path_id"].as_str().unwrap_or("");
+        let earliest_departure = from_trip.arrival_time_seconds + parameters.min_layover_seconds;
+        (i + 1..trips.len()).filter(|j| {
+            let to_trip = &trips[*j];
+            if to_trip.departure_time_seconds < earliest_departure {
+                return false;
+            }
+            let to_path_uuid = trip_at(lines, service_uuid, to_trip)["path_id"].as_str().unwrap_or("");
+            dead_head_seconds(&lines[from_trip.line], &lines[to_trip.line], from_path_uuid, to_path_uuid, paths_terminal_nodes)
+                .is_some_and(|dead_head_seconds| earliest_departure + dead_head_seconds <= to_trip.departure_time_seconds)
+        }).collect()
+    }).collect();
+
+    let next = maximum_matching(&successors);
+    let mut has_previous = vec![false; trips.len()];
+    for successor in next.iter().flatten() {
+        has_previous[*successor] = true;
+    }
+
+    let mut blocks : Vec<serde_json::Value> = Vec::new();
+    let mut blocks_spans : Vec<(i64, i64)> = Vec::new();
+    for first_trip in (0..trips.len()).filter(|trip| !has_previous[*trip]) {
+        let mut block_trips : Vec<usize> = vec![first_trip];
+        while let Some(successor) = next[*block_trips.last().unwrap()] {
+            block_trips.push(successor);
+        }
+
+        let first_trip_uuid = trip_at(lines, service_uuid, &trips[first_trip])["id"].as_str().unwrap_or("").to_string();
+        let block_uuid = Uuid::new_v5(&Uuid::NAMESPACE_OID, format!("block:{}:{}", service_uuid, first_trip_uuid).as_bytes()).to_string();
+        let mut trips_ids : Vec<serde_json::Value> = Vec::new();
+        let mut lines_ids : Vec<serde_json::Value> = Vec::new();
+        for trip in block_trips.iter() {
+            let position = trips[*trip];
+            let line_json = &mut lines[position.line];
+            if !lines_ids.contains(&line_json["id"]) {
+                lines_ids.push(line_json["id"].clone());
+            }
+            let schedule_key = line_json["scheduleByServiceId"].as_object().unwrap().iter()
+                .find(|(_, schedule)| schedule["service_id"] == json!(service_uuid)).map(|(key, _)| key.clone()).unwrap();
+            let trip_json = &mut line_json["scheduleByServiceId"][schedule_key]["periods"][position.period]["trips"][position.trip];
+            trip_json["block_id"] = json!(block_uuid);
+            trips_ids.push(trip_json["id"].clone());
+        }
+
+        let span = (trips[first_trip].departure_time_seconds, trips[*block_trips.last().unwrap()].arrival_time_seconds);
+        blocks_spans.push(span);
+        blocks.push(json!({
+            "id": block_uuid,
+            "trips_ids": trips_ids,
+            "lines_ids": lines_ids,
+            "start_time_seconds": span.0,
+            "end_time_seconds": span.1
+        }));
+    }
+
+    let peak_vehicles_by_period : Vec<serde_json::Value> = periods.iter().map(|(period_shortname, start_at_hour, end_at_hour)| json!({
+        "period_shortname": period_shortname,
+        "start_at_hour": start_at_hour,
+        "end_at_hour": end_at_hour,
+        "vehicles_count": peak_vehicles(&blocks_spans, (start_at_hour * 3600.0) as i64, (end_at_hour * 3600.0) as i64)
+    })).collect();
+
+    json!({
+        "service_id": service_uuid,
+        "vehicles_count": blocks.len(),
+        "blocks": blocks,
+        "peak_vehicles_by_period": peak_vehicles_by_period,
+        "unblocked_trips_ids": unblocked_trips_ids
+    })
+}
+
+/// Assign the trips of the lines, as returned in the "line" attribute of
+/// line::read_object, to vehicle blocks, separately for each service. With
+/// more than one line, vehicles can be interlined between the lines. The
+/// `block_id` of the trips are replaced by the computed blocks, and trips
+/// without departure or arrival time are left unblocked.
+pub fn compute_blocks(
+    lines: &mut [serde_json::Value],
+    paths_terminal_nodes: &HashMap<String, (String, String)>,
+    parameters: &BlockingParameters,
+) -> Result<serde_json::Value, capnp::Error> {
+
+    parameters.validate()?;
+
+    let mut services_uuids : Vec<String> = lines.iter()
+        .filter_map(|line_json| line_json["scheduleByServiceId"].as_object())
+        .flat_map(|schedules| schedules.values().filter_map(|schedule| schedule["service_id"].as_str().map(String::from)))
+        .collect();
+    services_uuids.sort();
+    services_uuids.dedup();
+
+    let services : Vec<serde_json::Value> = services_uuids.iter()
+        .map(|service_uuid| block_service(lines, service_uuid, paths_terminal_nodes, parameters))
+        .collect();
+
+    Ok(json!({
+        "lines_ids": lines.iter().map(|line_json| line_json["id"].clone()).collect::<Vec<serde_json::Value>>(),
+        "services": services
+    }))
+}
+
+/// First and last node of each path of the cache, by path uuid
+pub fn read_paths_terminal_nodes(cache_directory_path: &str) -> Result<HashMap<String, (String, String)>, capnp::Error> {
+    let paths_json = read_collection_file(cache_directory_path, "paths", &path_collection::read_collection)?;
+    Ok(paths_json["paths"]["features"].as_array().unwrap().iter().filter_map(|path| {
+        let properties = &path["properties"];
+        let nodes = properties["nodes"].as_array()?;
+        Some((
+            properties["id"].as_str()?.to_string(),
+            (nodes.first()?.as_str()?.to_string(), nodes.last()?.as_str()?.to_string())
+        ))
+    }).collect())
+}
+
+/// Compute the blocks of a line of the cache or, when `interlining` is set,
+/// of all the lines of its agency. If `write` is set, the line objects with
+/// the new block ids are written back to the `lines` subdirectory.
+pub fn compute_cached_blocks(
+    cache_directory_path: &str,
+    line_uuid: &str,
+    interlining: bool,
+    parameters: &BlockingParameters,
+    write: bool,
+) -> Result<serde_json::Value, capnp::Error> {
+
+    let lines_directory_path = format!("{}/lines", cache_directory_path);
+    let line_json = line::read_object(&line_uuid.to_string(), &lines_directory_path)?["line"].clone();
+
+    let mut lines : Vec<serde_json::Value> = vec![line_json];
+    if interlining {
+        let lines_json = read_collection_file(cache_directory_path, "lines", &line_collection::read_collection)?;
+        for agency_line in lines_json["lines"].as_array().unwrap() {
+            if agency_line["agency_id"] == lines[0]["agency_id"] && agency_line["id"] != lines[0]["id"] {
+                let agency_line_uuid = agency_line["id"].as_str().unwrap_or("").to_string();
+                // lines without schedules have no object to read
+                if let Ok(agency_line_object) = line::read_object(&agency_line_uuid, &lines_directory_path) {
+                    lines.push(agency_line_object["line"].clone());
+                }
+            }
+        }
+    }
+
+    let blocks = compute_blocks(&mut lines, &read_paths_terminal_nodes(cache_directory_path)?, parameters)?;
+    if write {
+        for line_json in lines {
+            // the blocks do not change the layout of the line files
+            line::rewrite_object(&lines_directory_path, &json!({ "line": line_json }))?;
+        }
+    }
+    Ok(blocks)
+}
@@ -0,0 +1,106 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Vehicle types of the cache (vehicleTypes.capnpbin) and the capacity of the trips using them */
+
+use std::collections::HashMap;
+use crate::cache::read_optional_collection_file;
+use crate::serialization::vehicle_type_collection;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct VehicleType {
+    pub uuid: String,
+    pub mode: Option<String>,
+    pub seated_capacity: Option<i64>,
+    pub standing_capacity: Option<i64>,
+    pub cost_per_km: Option<f64>,
+    pub cost_per_hour: Option<f64>,
+}
+
+impl VehicleType {
+
+    /// Seated and standing capacity, None when neither is known
+    pub fn total_capacity(&self) -> Option<i64> {
+        match (self.seated_capacity, self.standing_capacity) {
+            (None, None) => None,
+            (seated, standing) => Some(seated.unwrap_or(0) + standing.unwrap_or(0))
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct VehicleTypes {
+    by_uuid: HashMap<String, VehicleType>,
+}
+
+impl VehicleTypes {
+
+    /// Build the catalogue from the json returned by
+    /// vehicle_type_collection::read_collection
+    pub fn from_collection_json(vehicle_types_json: &serde_json::Value) -> Self {
+        let by_uuid = vehicle_types_json["vehicle_types"].as_array().map(|vehicle_types| vehicle_types.as_slice()).unwrap_or_default().iter().filter_map(|vehicle_type| {
+            let uuid = vehicle_type["id"].as_str()?.to_string();
+            Some((uuid.clone(), VehicleType {
+                uuid,
+                mode: vehicle_type["mode"].as_str().map(|mode| mode.to_string()),
+                seated_capacity: vehicle_type["seated_capacity"].as_i64(),
+                standing_capacity: vehicle_type["standing_capacity"].as_i64(),
+                cost_per_km: vehicle_type["cost_per_km"].as_f64(),
+                cost_per_hour: vehicle_type["cost_per_hour"].as_f64(),
+            }))
+        }).collect();
+        VehicleTypes { by_uuid }
+    }
+
+    /// Read the vehicle types of a cache. Caches without vehicle types
+    /// give an empty catalogue, the trips then keep their own capacity.
+    pub fn load(cache_directory_path: &str) -> Result<Self, capnp::Error> {
+        match read_optional_collection_file(cache_directory_path, "vehicleTypes", &vehicle_type_collection::read_collection)? {
+            Some(vehicle_types_json) => Ok(VehicleTypes::from_collection_json(&vehicle_types_json)),
+            None => Ok(VehicleTypes::default())
+        }
+    }
+
+    pub fn get(&self, vehicle_type_uuid: &str) -> Option<&VehicleType> {
+        self.by_uuid.get(vehicle_type_uuid)
+    }
+
+    /// Vehicle type of a trip, or of its period when the trip has none
+    pub fn trip_vehicle_type(&self, trip: &serde_json::Value, period_vehicle_type_uuid: Option<&str>) -> Option<&VehicleType> {
+        trip["vehicle_type_id"].as_str().or(period_vehicle_type_uuid).and_then(|vehicle_type_uuid| self.get(vehicle_type_uuid))
+    }
+
+    /// Set the seated and total capacity of the trips of a line object, as
+    /// returned in the "line" attribute of line::read_object, from their
+    /// vehicle type when they are not explicit. Returns the number of trips
+    /// whose capacity was resolved from a vehicle type.
+    pub fn resolve_line_capacities(&self, line_json: &mut serde_json::Value) -> usize {
+        let mut resolved_count = 0;
+        for schedule in line_json["scheduleByServiceId"].as_object_mut().into_iter().flat_map(|schedules| schedules.values_mut()) {
+            for period in schedule["periods"].as_array_mut().into_iter().flatten() {
+                let period_vehicle_type_uuid = period["vehicle_type_id"].as_str().map(|vehicle_type_uuid| vehicle_type_uuid.to_string());
+                for trip in period["trips"].as_array_mut().into_iter().flatten() {
+                    if let Some(vehicle_type) = self.trip_vehicle_type(trip, period_vehicle_type_uuid.as_deref()) {
+                        let seated_capacity = if trip["seated_capacity"].is_null() { vehicle_type.seated_capacity } else { None };
+                        let total_capacity = if trip["total_capacity"].is_null() { vehicle_type.total_capacity() } else { None };
+                        if let Some(seated_capacity) = seated_capacity {
+                            trip["seated_capacity"] = json!(seated_capacity);
+                        }
+                        if let Some(total_capacity) = total_capacity {
+                            trip["total_capacity"] = json!(total_capacity);
+                        }
+                        if seated_capacity.is_some() || total_capacity.is_some() {
+                            resolved_count += 1;
+                        }
+                    }
+                }
+            }
+        }
+        resolved_count
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * Copyright 2025 Polytechnique Montreal and contributors
+ *
+ * This software may be used and distributed according to the terms of the
+ * GNU General Public License version 2 or any later version.
+ *
+ */
+
+/* Point in polygon lookups of nodes, OD trips and points in the zone collection (zones.capnpbin) */
+
+use geo::{BoundingRect, Coord, Intersects, LineString, MultiPolygon, Point, Polygon};
+use rstar::{RTree, RTreeObject, AABB};
+use crate::cache::read_collection_file;
+use crate::serialization::{node_collection, od_trip_collection, zone_collection};
+
+#[derive(Debug, Clone)]
+pub struct IndexedZone {
+    pub uuid: String,
+    pub integer_id: u32,
+    pub geometry: MultiPolygon<f64>,
+}
+
+// Bounding box of a zone in longitude/latitude, to find the zone candidates
+// before the exact point in polygon test
+struct ZoneEnvelope {
+    index: usize,
+    envelope: AABB<[f64; 2]>,
+}
+
+impl RTreeObject for ZoneEnvelope {
+    type Envelope = AABB<[f64; 2]>;
+
+    fn envelope(&self) -> Self::Envelope {
+        self.envelope
+    }
+}
+
+pub struct ZoneIndex {
+    zones: Vec<IndexedZone>,
+    tree: RTree<ZoneEnvelope>,
+}
+
+fn json_ring(ring: &serde_json::Value) -> Option<LineString<f64>> {
+    ring.as_array()?.iter().map(|coordinate| Some(Coord { x: coordinate[0].as_f64()?, y: coordinate[1].as_f64()? })).collect::<Option<Vec<Coord<f64>>>>().map(LineString::new)
+}
+
+fn json_polygon(rings: &serde_json::Value) -> Option<Polygon<f64>> {
+    let mut rings = rings.as_array()?.iter().map(json_ring).collect::<Option<Vec<LineString<f64>>>>()?;
+    if rings.is_empty() {
+        return None;
+    }
+    let exterior = rings.remove(0);
+    Some(Polygon::new(exterior, rings))
+}
+
+/// Convert a geojson Polygon or MultiPolygon geometry, None for other or invalid geometries
+pub fn json_geometry_to_multipolygon(geometry: &serde_json::Value) -> Option<MultiPolygon<f64>> {
+    match geometry["type"].as_str() {
+        Some("Polygon") => Some(MultiPolygon::new(vec![json_polygon(&geometry["coordinates"])?])),
+        Some("MultiPolygon") => geometry["coordinates"].as_array()?.iter().map(json_polygon).collect::<Option<Vec<Polygon<f64>>>>().map(MultiPolygon::new),
+        _ => None
+    }
+}
+
+impl ZoneIndex {
+
+    pub fn new(zones: Vec<IndexedZone>) -> Self {
+        let envelopes = zones.iter().enumerate().filter_map(|(index, zone)| {
+            zone.geometry.bounding_rect().map(|rect| ZoneEnvelope { index, envelope: AABB::from_corners([rect.min().x, rect.min().y], [rect.max().x, rect.max().y]) })
+        }).collect();
+        ZoneIndex {
+            zones,
+            tree: RTree::bulk_load(envelopes),
+        }
+    }
+
+    /// Build the index from the json returned by zone_collection::read_collection
+    pub fn from_collection_json(json: &serde_json::Value) -> Result<Self, capnp::Error> {
+        let features = json["zones"]["features"].as_array().ok_or_else(|| capnp::Error::failed(String::from("Zones json is not a FeatureCollection")))?;
+        let mut zones : Vec<IndexedZone> = Vec::with_capacity(features.len());
+        for feature in features {
+            let properties = &feature["properties"];
+            let uuid = properties["id"].as_str().unwrap_or("").to_string();
+            let geometry = json_geometry_to_multipolygon(&feature["geometry"]).ok_or_else(|| capnp::Error::failed(format!("Zone {} geometry is not a valid Polygon or MultiPolygon", uuid)))?;
+            zones.push(IndexedZone {
+                uuid,
+                integer_id: properties["integer_id"].as_u64().unwrap_or(0) as u32,
+                geometry,
+            });
+        }
+        Ok(ZoneIndex::new(zones))
+    }
+
+    pub fn zones(&self) -> &[IndexedZone] {
+        &self.zones
+    }
+
+    /// Indices of the zones containing the point, in the order of the
+    /// collection. A point on the boundary of adjacent zones is in all of them.
+    pub fn zones_containing(&self, longitude: f64, latitude: f64) -> Vec<usize> {
+        let point = Point::new(longitude, latitude);
+        let mut indices : Vec<usize> = self.tree.locate_in_envelope_intersecting(&AABB::from_point([longitude, latitude]))
+            .map(|envelope| envelope.index)
+            .filter(|index| self.zones[*index].geometry.intersects(&point))
+            .collect();
+        indices.sort_unstable();
+        indices
+    }
+
+    fn zones_uuids(&self, longitude: f64, latitude: f64) -> Vec<&str> {
+        self.zones_containing(longitude, latitude).into_iter().map(|index| self.zones[index].uuid.as_str()).collect()
+    }
+}
+
+fn point_coordinates(geometry: &serde_json::Value) -> Option<(f64, f64)> {
+    Some((geometry["coordinates"][0].as_f64()?, geometry["coordinates"][1].as_f64()?))
+}
+
+/// Locate points, nodes and OD trips of a cache in its zones. The query has
+/// `points` [{ id, coordinates: [longitude, latitude] }], and `nodes` and
+/// `od_trips` booleans to locate the nodes and OD trips of the cache. The
+/// result has the zones of each located object and, for each zone, the count
+/// of points, nodes, OD trip origins and destinations it contains, with the
+/// expansion factor weighted OD trips (weight 1 when not set).
+pub fn locate_in_zones_query(cache_directory_path: &str, query: &serde_json::Value) -> Result<serde_json::Value, capnp::Error> {
+    let zone_index = ZoneIndex::from_collection_json(&read_collection_file(cache_directory_path, "zones", &zone_collection::read_collection)?)?;
+    let zones_count = zone_index.zones().len();
+    let mut result = json!({});
+
+    let mut points_counts = vec![0; zones_count];
+    if let Some(points) = query["points"].as_array() {
+        let mut located_points : Vec<serde_json::Value> = Vec::with_capacity(points.len());
+        for (i, point) in points.iter().enumerate() {
+            let (longitude, latitude) = point_coordinates(point).ok_or_else(|| capnp::Error::failed(format!("Invalid coordinates for point {}", i)))?;
+            let indices = zone_index.zones_containing(longitude, latitude);
+            indices.iter().for_each(|index| points_counts[*index] += 1);
+            located_points.push(json!({ "id": point["id"], "zones": zone_index.zones_uuids(longitude, latitude) }));
+        }
+        result["points"] = json!(located_points);
+    }
+
+    let mut nodes_counts = vec![0; zones_count];
+    if query["nodes"].as_bool().unwrap_or(false) {
+        let nodes_json = read_collection_file(cache_directory_path, "nodes", &node_collection::read_collection)?;
+        let mut located_nodes : Vec<serde_json::Value> = Vec::new();
+        for node in nodes_json["nodes"]["features"].as_array().unwrap() {
+            let (longitude, latitude) = point_coordinates(&node["geometry"]).unwrap_or((0.0, 0.0));
+            let indices = zone_index.zones_containing(longitude, latitude);
+            indices.iter().for_each(|index| nodes_counts[*index] += 1);
+            located_nodes.push(json!({ "id": node["properties"]["id"], "zones": zone_index.zones_uuids(longitude, latitude) }));
+        }
+        result["nodes"] = json!(located_nodes);
+    }
+
+    let mut origins_counts = vec![0; zones_count];
+    let mut destinations_counts = vec![0; zones_count];
+    let mut origins_weights = vec![0.0; zones_count];
+    let mut destinations_weights = vec![0.0; zones_count];
+    if query["od_trips"].as_bool().unwrap_or(false) {
+        let od_trips_json = read_collection_file(cache_directory_path, "odTrips", &od_trip_collection::read_collection)?;
+        let mut located_od_trips : Vec<serde_json::Value> = Vec::new();
+        for od_trip in od_trips_json["odTrips"].as_array().unwrap() {
+            let weight = od_trip["expansion_factor"].as_f64().unwrap_or(1.0);
+            let (origin_longitude, origin_latitude) = point_coordinates(&od_trip["origin_geography"]).unwrap_or((0.0, 0.0));
+            let (destination_longitude, destination_latitude) = point_coordinates(&od_trip["destination_geography"]).unwrap_or((0.0, 0.0));
+            for index in zone_index.zones_containing(origin_longitude, origin_latitude) {
+                origins_counts[index] += 1;
+                origins_weights[index] += weight;
+            }
+            for index in zone_index.zones_containing(destination_longitude, destination_latitude) {
+                destinations_counts[index] += 1;
+                destinations_weights[index] += weight;
+            }
+            located_od_trips.push(json!({
+                "id": od_trip["id"],
+                "origin_zones": zone_index.zones_uuids(origin_longitude, origin_latitude),
+                "destination_zones": zone_index.zones_uuids(destination_longitude, destination_latitude)
+            }));
+        }
+        result["od_trips"] = json!(located_od_trips);
+    }
+
+    result["zones"] = json!(zone_index.zones().iter().enumerate().map(|(index, zone)| json!({
+        "id": zone.uuid,
+        "integer_id": zone.integer_id,
+        "points_count": points_counts[index],
+        "nodes_count": nodes_counts[index],
+        "od_trips_origins_count": origins_counts[index],
+        "od_trips_destinations_count": destinations_counts[index],
+        "od_trips_origins_weight": origins_weights[index],
+        "od_trips_destinations_weight": destinations_weights[index]
+    })).collect::<Vec<serde_json::Value>>());
+    Ok(result)
+}